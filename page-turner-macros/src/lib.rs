@@ -0,0 +1,376 @@
+//! Derive macros for the [page-turner](https://docs.rs/page-turner) crate. Enable the `macros`
+//! feature of `page-turner` to use them through `page_turner::RequestAhead` and
+//! `page_turner::OffsetPagination`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Expr, Fields, Index, Member};
+
+const PAGE_FIELD: &str = "page_field";
+const OFFSET_FIELD: &str = "offset";
+const LIMIT_FIELD: &str = "limit";
+
+/// Implements `RequestAhead` for a request struct by cloning it and incrementing the field marked
+/// with `#[page_field]`. The step defaults to `1` and can be set with `#[page_field(step = 50)]`,
+/// i.e. for offset fields. The struct must implement `Clone` and the field must support `+=` with
+/// the step. Generic structs implement `RequestAhead` whenever they implement `Clone`.
+///
+/// The step is any expression of the field type: a literal, a constant or a value computed from
+/// the current request, which is available as `self`, like `step = self.per_page`. APIs that
+/// don't advance by a fixed amount compute the whole next value with `next = <expr>` instead,
+/// i.e. `#[page_field(next = self.offset * 2)]`. `step` and `next` can't be combined.
+///
+/// Tuple structs mark a positional field. Enums mark exactly one field in every variant, usually
+/// the cursor of the variant. The next request of a variant without a page field can't be made,
+/// so such variants are rejected at compile time.
+///
+/// ```
+/// use page_turner::RequestAhead;
+///
+/// #[derive(Clone, RequestAhead)]
+/// struct ListUsers {
+///     #[page_field(step = 50)]
+///     offset: u64,
+///     limit: u64,
+/// }
+///
+/// let request = ListUsers { offset: 0, limit: 50 };
+/// assert_eq!(request.next_request().offset, 50);
+/// assert_eq!(request.nth_request(2).offset, 150);
+///
+/// #[derive(Clone, RequestAhead)]
+/// struct ListRepos {
+///     per_page: u32,
+///     #[page_field(step = self.per_page)]
+///     offset: u32,
+/// }
+///
+/// let request = ListRepos { per_page: 30, offset: 0 };
+/// assert_eq!(request.nth_request(1).offset, 60);
+///
+/// #[derive(Clone, RequestAhead)]
+/// struct ScanLog {
+///     #[page_field(next = self.since.max(1) * 2)]
+///     since: u64,
+/// }
+///
+/// let request = ScanLog { since: 0 };
+/// assert_eq!(request.nth_request(2).since, 8);
+///
+/// #[derive(Clone, RequestAhead)]
+/// enum ListMessages {
+///     Before(String, #[page_field] u32),
+///     Since(#[page_field] u64),
+/// }
+///
+/// let request = ListMessages::Before("general".to_owned(), 1);
+/// assert!(matches!(request.next_request(), ListMessages::Before(_, 2)));
+/// ```
+#[proc_macro_derive(RequestAhead, attributes(page_field))]
+pub fn derive_request_ahead(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    request_ahead_impl(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn request_ahead_impl(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let next_request = match &input.data {
+        Data::Struct(data) => {
+            let Some((member, advance)) = page_field(&data.fields)? else {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "mark the page field with `#[page_field]`",
+                ));
+            };
+
+            match advance {
+                Advance::Step(step) => quote!(request.#member += #step;),
+                Advance::Next(next) => quote!(request.#member = #next;),
+            }
+        }
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+
+            for variant in &data.variants {
+                let variant_name = &variant.ident;
+                let Some((member, advance)) = page_field(&variant.fields)? else {
+                    return Err(syn::Error::new(
+                        variant_name.span(),
+                        format!(
+                            "mark the page field of `{name}::{variant_name}` with `#[page_field]`"
+                        ),
+                    ));
+                };
+
+                let advance = match advance {
+                    Advance::Step(step) => quote!(*page += #step),
+                    Advance::Next(next) => quote!(*page = #next),
+                };
+                arms.push(quote!(Self::#variant_name { #member: page, .. } => #advance,));
+            }
+
+            if arms.is_empty() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "mark the page field of a variant with `#[page_field]`",
+                ));
+            }
+
+            quote! {
+                match &mut request {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                name.span(),
+                "`RequestAhead` can only be derived for structs and enums",
+            ))
+        }
+    };
+
+    // Generic parameters are kept as is and the type is required to be `Clone`, which is usually
+    // derived with the same bounds on its parameters
+    let mut generics = input.generics.clone();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(#name #ty_generics: ::core::clone::Clone));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::page_turner::RequestAhead for #name #ty_generics #where_clause {
+            fn next_request(&self) -> Self {
+                let mut request = ::core::clone::Clone::clone(self);
+                #next_request
+                request
+            }
+        }
+    })
+}
+
+/// Implements offset pagination for a request struct with an offset field marked with `#[offset]`
+/// and a page size field marked with `#[limit]`. Both fields must have the same integer type and
+/// the struct must implement `Clone`. The derive generates:
+///
+/// - `RequestAhead` that advances the offset by the limit, including a `nth_request` jumping to
+///   the page directly,
+/// - `starting_at(offset)` and `with_limit(limit)` constructors that return a copy of the request
+///   with the changed field,
+/// - `turned_page(items, total)` that builds a `TurnedPage` with the next request while items
+///   past the page remain according to the `total` count reported by the API.
+///
+/// ```
+/// use page_turner::{OffsetPagination, RequestAhead};
+///
+/// #[derive(Clone, OffsetPagination)]
+/// struct ListUsers {
+///     group: String,
+///     #[offset]
+///     offset: u64,
+///     #[limit]
+///     limit: u64,
+/// }
+///
+/// let request = ListUsers {
+///     group: "admins".to_owned(),
+///     offset: 0,
+///     limit: 50,
+/// }
+/// .starting_at(100);
+///
+/// assert_eq!(request.next_request().offset, 150);
+/// assert_eq!(request.nth_request(2).offset, 250);
+///
+/// let page = request.turned_page(vec!["alice"; 50], 180);
+/// assert_eq!(page.next_request.unwrap().offset, 150);
+///
+/// let page = request.turned_page(vec!["bob"; 30], 130);
+/// assert!(page.next_request.is_none());
+/// ```
+#[proc_macro_derive(OffsetPagination, attributes(offset, limit))]
+pub fn derive_offset_pagination(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    offset_pagination_impl(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn offset_pagination_impl(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let vis = &input.vis;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            name.span(),
+            "`OffsetPagination` can only be derived for structs",
+        ));
+    };
+
+    let Some((offset, ty)) = marked_field(&data.fields, OFFSET_FIELD)? else {
+        return Err(syn::Error::new(
+            name.span(),
+            "mark the offset field with `#[offset]`",
+        ));
+    };
+
+    let Some((limit, _)) = marked_field(&data.fields, LIMIT_FIELD)? else {
+        return Err(syn::Error::new(
+            name.span(),
+            "mark the page size field with `#[limit]`",
+        ));
+    };
+
+    let mut generics = input.generics.clone();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(#name #ty_generics: ::core::clone::Clone));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::page_turner::RequestAhead for #name #ty_generics #where_clause {
+            fn next_request(&self) -> Self {
+                let mut request = ::core::clone::Clone::clone(self);
+                request.#offset += request.#limit;
+                request
+            }
+
+            fn nth_request(&self, n: usize) -> Self {
+                let pages = n
+                    .checked_add(1)
+                    .and_then(|pages| <#ty as ::core::convert::TryFrom<usize>>::try_from(pages).ok())
+                    .expect("the amount of pages to skip overflows the offset type");
+
+                let mut request = ::core::clone::Clone::clone(self);
+                request.#offset += request.#limit * pages;
+                request
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the request with the offset set to `offset`
+            #vis fn starting_at(&self, offset: #ty) -> Self {
+                let mut request = ::core::clone::Clone::clone(self);
+                request.#offset = offset;
+                request
+            }
+
+            /// Returns the request with the page size set to `limit`
+            #vis fn with_limit(&self, limit: #ty) -> Self {
+                let mut request = ::core::clone::Clone::clone(self);
+                request.#limit = limit;
+                request
+            }
+
+            /// Makes a page with the next request while items past this page remain according to
+            /// the `total` count. A zero limit never advances, so it has no next page.
+            #vis fn turned_page<I>(&self, items: I, total: #ty) -> ::page_turner::TurnedPage<I, Self> {
+                let has_more = self.#limit > 0 && self.#offset.saturating_add(self.#limit) < total;
+                ::page_turner::TurnedPage::new(
+                    items,
+                    has_more.then(|| ::page_turner::RequestAhead::next_request(self)),
+                )
+            }
+        }
+    })
+}
+
+/// Returns the only field marked with `#[<attr>]` along with its type
+fn marked_field(fields: &Fields, attr: &str) -> syn::Result<Option<(Member, syn::Type)>> {
+    let mut marked = None;
+    for (ix, field) in fields.iter().enumerate() {
+        if !field.attrs.iter().any(|a| a.path().is_ident(attr)) {
+            continue;
+        }
+
+        if marked.is_some() {
+            return Err(syn::Error::new(
+                field.span(),
+                format!("only one field can be marked with `#[{attr}]`"),
+            ));
+        }
+
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(ix)),
+        };
+
+        marked = Some((member, field.ty.clone()));
+    }
+
+    Ok(marked)
+}
+
+/// How the page field of the next request is made from the current request
+enum Advance {
+    /// `#[page_field(step = <expr>)]` adds to the current value
+    Step(Expr),
+    /// `#[page_field(next = <expr>)]` replaces the current value
+    Next(Expr),
+}
+
+/// Returns the field marked with `#[page_field]` along with how it advances
+fn page_field(fields: &Fields) -> syn::Result<Option<(Member, Advance)>> {
+    let mut page_field = None;
+    for (ix, field) in fields.iter().enumerate() {
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(PAGE_FIELD))
+        {
+            if page_field.is_some() {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "only one field can be marked with `#[page_field]`",
+                ));
+            }
+
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index::from(ix)),
+            };
+
+            page_field = Some((member, page_advance(attr)?));
+        }
+    }
+
+    Ok(page_field)
+}
+
+/// Parses `#[page_field]`, `#[page_field(step = <expr>)]` and `#[page_field(next = <expr>)]`
+fn page_advance(attr: &syn::Attribute) -> syn::Result<Advance> {
+    let mut advance = None;
+
+    if let syn::Meta::List(_) = attr.meta {
+        attr.parse_nested_meta(|meta| {
+            let make: fn(Expr) -> Advance = if meta.path.is_ident("step") {
+                Advance::Step
+            } else if meta.path.is_ident("next") {
+                Advance::Next
+            } else {
+                return Err(
+                    meta.error("unsupported `page_field` argument, expected `step` or `next`")
+                );
+            };
+
+            if advance.is_some() {
+                return Err(meta.error("`step` and `next` can't be combined"));
+            }
+
+            advance = Some(make(meta.value()?.parse()?));
+            Ok(())
+        })?;
+    }
+
+    Ok(advance.unwrap_or_else(|| Advance::Step(syn::parse_quote!(1))))
+}
@@ -0,0 +1,193 @@
+use page_turner::prelude::*;
+use page_turner::OffsetPagination;
+
+#[derive(Debug, Clone, PartialEq, RequestAhead)]
+struct GetPage {
+    #[page_field]
+    page: u32,
+    query: String,
+}
+
+#[derive(Debug, Clone, PartialEq, RequestAhead)]
+struct GetOffset {
+    limit: usize,
+    #[page_field(step = 25)]
+    offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, RequestAhead)]
+struct Search<'q, F, const N: usize>
+where
+    F: AsRef<str>,
+{
+    query: &'q str,
+    filters: [F; N],
+    #[page_field]
+    page: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, RequestAhead)]
+struct GetChunk(String, #[page_field(step = 100)] u64);
+
+#[derive(Debug, Clone, PartialEq, RequestAhead)]
+enum ListMessages {
+    Before(String, #[page_field] u32),
+    Since {
+        #[page_field(step = 10)]
+        offset: usize,
+    },
+}
+
+const PAGE_SIZE: u32 = 40;
+
+#[derive(Debug, Clone, PartialEq, RequestAhead)]
+struct GetConstStep {
+    #[page_field(step = PAGE_SIZE)]
+    offset: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, RequestAhead)]
+struct GetFieldStep {
+    per_page: u32,
+    #[page_field(step = self.per_page)]
+    offset: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, RequestAhead)]
+struct GetNext {
+    #[page_field(next = self.window.max(1) * 2)]
+    window: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, RequestAhead)]
+enum ScanLog {
+    From(#[page_field(next = self.cursor() + 7)] u64),
+    Tail {
+        #[page_field(next = self.cursor() + 1)]
+        seq: u64,
+    },
+}
+
+impl ScanLog {
+    fn cursor(&self) -> u64 {
+        match self {
+            Self::From(cursor) | Self::Tail { seq: cursor } => *cursor,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, OffsetPagination)]
+struct ListUsers {
+    group: String,
+    #[offset]
+    offset: u64,
+    #[limit]
+    limit: u64,
+}
+
+#[test]
+fn derives_request_ahead() {
+    let request = GetPage {
+        page: 1,
+        query: "rust".to_owned(),
+    };
+
+    assert_eq!(
+        request.next_request(),
+        GetPage {
+            page: 2,
+            query: "rust".to_owned(),
+        }
+    );
+    assert_eq!(request.nth_request(3).page, 5);
+
+    let request = GetOffset {
+        limit: 25,
+        offset: 0,
+    };
+
+    assert_eq!(request.next_request().offset, 25);
+    assert_eq!(request.nth_request(1).offset, 50);
+    assert_eq!(request.nth_request(1).limit, 25);
+}
+
+#[test]
+fn derives_request_ahead_for_generic_structs() {
+    let request = Search {
+        query: "rust",
+        filters: ["lang:en".to_owned()],
+        page: 0,
+    };
+
+    let next = request.next_request();
+    assert_eq!(next.page, 1);
+    assert_eq!((next.query, next.filters), (request.query, request.filters));
+}
+
+#[test]
+fn derives_request_ahead_for_tuple_structs() {
+    let request = GetChunk("logs".to_owned(), 0);
+
+    assert_eq!(request.next_request(), GetChunk("logs".to_owned(), 100));
+    assert_eq!(request.nth_request(2).1, 300);
+}
+
+#[test]
+fn derives_request_ahead_for_enums() {
+    let request = ListMessages::Before("general".to_owned(), 1);
+    assert_eq!(
+        request.nth_request(1),
+        ListMessages::Before("general".to_owned(), 3)
+    );
+
+    let request = ListMessages::Since { offset: 0 };
+    assert_eq!(request.next_request(), ListMessages::Since { offset: 10 });
+}
+
+#[test]
+fn derives_offset_pagination() {
+    let request = ListUsers {
+        group: "admins".to_owned(),
+        offset: 0,
+        limit: 50,
+    }
+    .starting_at(100)
+    .with_limit(20);
+
+    assert_eq!((request.offset, request.limit), (100, 20));
+    assert_eq!(request.next_request().offset, 120);
+    assert_eq!(request.nth_request(0), request.next_request());
+    assert_eq!(request.nth_request(4).offset, 200);
+
+    let page = request.turned_page(vec![1; 20], 150);
+    assert_eq!(page.next_request.unwrap().offset, 120);
+
+    let page = request.turned_page(vec![1; 20], 120);
+    assert!(page.next_request.is_none());
+
+    let page = request.with_limit(0).turned_page(Vec::<u8>::new(), 150);
+    assert!(page.next_request.is_none());
+}
+
+#[test]
+fn derives_request_ahead_with_step_and_next_expressions() {
+    let request = GetConstStep { offset: 0 };
+    assert_eq!(request.nth_request(1).offset, 80);
+
+    let request = GetFieldStep {
+        per_page: 15,
+        offset: 30,
+    };
+    assert_eq!(request.next_request().offset, 45);
+    assert_eq!(request.nth_request(2).offset, 75);
+
+    let request = GetNext { window: 0 };
+    assert_eq!(request.next_request().window, 2);
+    assert_eq!(request.nth_request(3).window, 16);
+
+    let request = ScanLog::From(3);
+    assert_eq!(request.nth_request(1), ScanLog::From(17));
+
+    let request = ScanLog::Tail { seq: 3 };
+    assert_eq!(request.next_request(), ScanLog::Tail { seq: 4 });
+}
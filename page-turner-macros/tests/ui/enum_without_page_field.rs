@@ -0,0 +1,6 @@
+use page_turner::RequestAhead;
+
+#[derive(Clone, RequestAhead)]
+enum Request {}
+
+fn main() {}
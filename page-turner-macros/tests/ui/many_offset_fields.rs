@@ -0,0 +1,13 @@
+use page_turner::OffsetPagination;
+
+#[derive(Clone, OffsetPagination)]
+struct Request {
+    #[offset]
+    offset: usize,
+    #[offset]
+    skip: usize,
+    #[limit]
+    limit: usize,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use page_turner::RequestAhead;
+
+#[derive(Clone, RequestAhead)]
+struct Request {
+    #[page_field]
+    page: usize,
+    #[page_field]
+    offset: usize,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use page_turner::RequestAhead;
+
+#[derive(Clone, RequestAhead)]
+struct Request {
+    page: usize,
+}
+
+fn main() {}
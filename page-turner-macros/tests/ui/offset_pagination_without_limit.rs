@@ -0,0 +1,10 @@
+use page_turner::OffsetPagination;
+
+#[derive(Clone, OffsetPagination)]
+struct Request {
+    #[offset]
+    offset: usize,
+    limit: usize,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use page_turner::RequestAhead;
+
+#[derive(Clone, RequestAhead)]
+struct Request {
+    #[page_field(step = 2, next = self.page * 2)]
+    page: usize,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use page_turner::RequestAhead;
+
+#[derive(Clone, Copy, RequestAhead)]
+union Request {
+    page: usize,
+    offset: u64,
+}
+
+fn main() {}
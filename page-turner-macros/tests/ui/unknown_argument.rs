@@ -0,0 +1,9 @@
+use page_turner::RequestAhead;
+
+#[derive(Clone, RequestAhead)]
+struct Request {
+    #[page_field(increment = 2)]
+    page: usize,
+}
+
+fn main() {}
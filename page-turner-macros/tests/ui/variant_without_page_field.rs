@@ -0,0 +1,9 @@
+use page_turner::RequestAhead;
+
+#[derive(Clone, RequestAhead)]
+enum Request {
+    First,
+    Page(#[page_field] usize),
+}
+
+fn main() {}
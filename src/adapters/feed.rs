@@ -0,0 +1,200 @@
+//! A page turner for paged and archived RSS/Atom feeds described in
+//! [RFC 5005](https://datatracker.ietf.org/doc/html/rfc5005). Feeds are parsed with [`feed_rs`] so
+//! every feed format it understands is supported.
+//!
+//! ```
+//! use page_turner::adapters::feed::{FeedPageTurner, FetchFeed};
+//! use page_turner::prelude::*;
+//!
+//! struct Blog;
+//!
+//! impl FetchFeed for Blog {
+//!     type Error = String;
+//!
+//!     async fn fetch_feed(&self, url: &str) -> Result<Vec<u8>, Self::Error> {
+//!         let feed = match url {
+//!             "https://example.com/feed" => r#"<feed xmlns="http://www.w3.org/2005/Atom">
+//!                 <link rel="next" href="/feed?page=2"/>
+//!                 <entry><id>1</id><title>First</title></entry>
+//!             </feed>"#,
+//!             "https://example.com/feed?page=2" => r#"<feed xmlns="http://www.w3.org/2005/Atom">
+//!                 <entry><id>2</id><title>Second</title></entry>
+//!             </feed>"#,
+//!             _ => return Err(format!("{url} not found")),
+//!         };
+//!
+//!         Ok(feed.as_bytes().to_vec())
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let entries: Vec<_> = FeedPageTurner::paged(Blog)
+//!     .pages("https://example.com/feed".to_owned())
+//!     .items()
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//!
+//! assert_eq!(entries.len(), 2);
+//! assert_eq!(entries[1].id, "2");
+//! # }
+//! ```
+
+use super::resolve_url;
+use crate::mt::{PageTurner, TurnedPageResult};
+use crate::TurnedPage;
+use feed_rs::{
+    model::Entry,
+    parser::{self, ParseFeedError},
+};
+use std::future::Future;
+
+/// A client capable of downloading raw feed documents
+pub trait FetchFeed: Send + Sync {
+    type Error: Send;
+
+    fn fetch_feed(&self, url: &str) -> impl Send + Future<Output = Result<Vec<u8>, Self::Error>>;
+}
+
+/// Implements [`PageTurner`] for `String` feed URL requests yielding feed entries. The URL of the
+/// next feed document is taken from the feed level link with the configured relation.
+#[derive(Debug, Clone)]
+pub struct FeedPageTurner<C> {
+    client: C,
+    rel: &'static str,
+}
+
+impl<C> FeedPageTurner<C> {
+    /// Follows `rel="next"` links of [paged feeds](https://datatracker.ietf.org/doc/html/rfc5005#section-3)
+    pub fn paged(client: C) -> Self {
+        Self {
+            client,
+            rel: "next",
+        }
+    }
+
+    /// Follows `rel="prev-archive"` links of [archived
+    /// feeds](https://datatracker.ietf.org/doc/html/rfc5005#section-4) going back in time from
+    /// the subscription document.
+    pub fn archived(client: C) -> Self {
+        Self {
+            client,
+            rel: "prev-archive",
+        }
+    }
+}
+
+impl<C> PageTurner<String> for FeedPageTurner<C>
+where
+    C: FetchFeed,
+{
+    type PageItems = Vec<Entry>;
+    type PageError = FeedPageError<C::Error>;
+
+    async fn turn_page(&self, url: String) -> TurnedPageResult<Self, String> {
+        let body = self
+            .client
+            .fetch_feed(&url)
+            .await
+            .map_err(FeedPageError::Fetch)?;
+
+        let feed = parser::parse(body.as_slice()).map_err(FeedPageError::Parse)?;
+        let next_url = feed
+            .links
+            .iter()
+            .find(|link| link.rel.as_deref() == Some(self.rel))
+            .map(|link| resolve_url(&url, &link.href));
+
+        Ok(TurnedPage::new(feed.entries, next_url))
+    }
+}
+
+#[derive(Debug)]
+pub enum FeedPageError<E> {
+    Fetch(E),
+    Parse(ParseFeedError),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for FeedPageError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fetch(e) => write!(f, "failed to fetch a feed: {e}"),
+            Self::Parse(e) => write!(f, "failed to parse a feed: {e}"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for FeedPageError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Fetch(e) => Some(e),
+            Self::Parse(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+
+    struct Archive;
+
+    impl FetchFeed for Archive {
+        type Error = String;
+
+        async fn fetch_feed(&self, url: &str) -> Result<Vec<u8>, Self::Error> {
+            let feed = match url {
+                "https://example.com/rss/current.xml" => {
+                    r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom"><channel>
+                        <atom:link rel="prev-archive" href="2023.xml"/>
+                        <item><guid>3</guid></item>
+                        <item><guid>4</guid></item>
+                    </channel></rss>"#
+                }
+                "https://example.com/rss/2023.xml" => {
+                    r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom"><channel>
+                        <atom:link rel="next" href="ignored.xml"/>
+                        <item><guid>1</guid></item>
+                        <item><guid>2</guid></item>
+                    </channel></rss>"#
+                }
+                "https://example.com/rss/broken.xml" => "<rss",
+                _ => return Err(format!("{url} not found")),
+            };
+
+            Ok(feed.as_bytes().to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn follows_archive_links() {
+        let ids: Vec<_> = FeedPageTurner::archived(Archive)
+            .pages("https://example.com/rss/current.xml".to_owned())
+            .items()
+            .map_ok(|entry| entry.id)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(ids, ["3", "4", "1", "2"]);
+
+        let result = FeedPageTurner::paged(Archive)
+            .pages("https://example.com/rss/2023.xml".to_owned())
+            .try_collect::<Vec<_>>()
+            .await;
+
+        assert!(matches!(result, Err(FeedPageError::Fetch(_))));
+
+        let result = FeedPageTurner::paged(Archive)
+            .pages("https://example.com/rss/broken.xml".to_owned())
+            .try_collect::<Vec<_>>()
+            .await;
+
+        assert!(matches!(result, Err(FeedPageError::Parse(_))));
+    }
+}
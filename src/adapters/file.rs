@@ -0,0 +1,144 @@
+//! Page turners yielding batches of lines from local files and other seekable readers, so local
+//! and remote sources can be consumed through the same abstraction and tests can run without
+//! network.
+//!
+//! Requests are byte offsets of the first line of a page, start with `0` to read from the
+//! beginning. Reads are blocking, which is fine for local files but consider wrapping slow readers
+//! into a dedicated thread.
+//!
+//! ```
+//! use page_turner::adapters::file::ReadPageTurner;
+//! use page_turner::prelude::*;
+//! use std::io::Cursor;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let csv = ReadPageTurner::new(Cursor::new("id,name\n1,a\n2,b\n3,c\n"), 2);
+//! let pages: Vec<_> = csv.pages(0).try_collect().await.unwrap();
+//!
+//! assert_eq!(pages, [vec!["id,name", "1,a"], vec!["2,b", "3,c"]]);
+//! # }
+//! ```
+
+use crate::internal::lock_unpoisoned;
+use crate::mt::{PageTurner, TurnedPageResult};
+use crate::TurnedPage;
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read, Seek, SeekFrom},
+    path::Path,
+    sync::Mutex,
+};
+
+/// A page turner over lines of a file
+pub type FilePageTurner = ReadPageTurner<File>;
+
+impl FilePageTurner {
+    /// Opens the file at `path` to read it in pages of `batch_size` lines
+    pub fn open(path: impl AsRef<Path>, batch_size: usize) -> io::Result<Self> {
+        Ok(Self::new(File::open(path)?, batch_size))
+    }
+}
+
+/// Implements [`PageTurner`] for `u64` byte offset requests yielding pages of up to `batch_size`
+/// lines without line terminators. The reader is locked while a page is being read.
+#[derive(Debug)]
+pub struct ReadPageTurner<R> {
+    reader: Mutex<BufReader<R>>,
+    batch_size: usize,
+}
+
+impl<R> ReadPageTurner<R>
+where
+    R: Read + Seek,
+{
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is 0
+    pub fn new(reader: R, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch_size must be positive");
+
+        Self {
+            reader: Mutex::new(BufReader::new(reader)),
+            batch_size,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .into_inner()
+    }
+
+    fn read_page(&self, offset: u64) -> io::Result<TurnedPage<Vec<String>, u64>> {
+        let mut reader = lock_unpoisoned(&self.reader);
+
+        reader.seek(SeekFrom::Start(offset))?;
+
+        let mut lines = Vec::with_capacity(self.batch_size);
+        let mut position = offset;
+
+        while lines.len() < self.batch_size {
+            let mut line = String::new();
+            let read = reader.read_line(&mut line)?;
+
+            if read == 0 {
+                return Ok(TurnedPage::last(lines));
+            }
+
+            position += read as u64;
+            let content_len = line.trim_end_matches(['\n', '\r']).len();
+            line.truncate(content_len);
+            lines.push(line);
+        }
+
+        let next_offset = (!reader.fill_buf()?.is_empty()).then_some(position);
+        Ok(TurnedPage::new(lines, next_offset))
+    }
+}
+
+impl<R> PageTurner<u64> for ReadPageTurner<R>
+where
+    R: Send + Read + Seek,
+{
+    type PageItems = Vec<String>;
+    type PageError = io::Error;
+
+    async fn turn_page(&self, offset: u64) -> TurnedPageResult<Self, u64> {
+        self.read_page(offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+    use std::io::Cursor;
+
+    #[tokio::test]
+    async fn reads_batches() {
+        let lines = ReadPageTurner::new(Cursor::new("a\r\nb\n\nc"), 2);
+
+        let pages: Vec<_> = lines.pages(0).try_collect().await.unwrap();
+        assert_eq!(pages, [vec!["a", "b"], vec!["", "c"]]);
+
+        let pages: Vec<_> = lines.pages(3).try_collect().await.unwrap();
+        assert_eq!(pages, [vec!["b", ""], vec!["c"]]);
+
+        let pages: Vec<Vec<String>> = lines.pages(100).try_collect().await.unwrap();
+        assert_eq!(pages, [Vec::<String>::new()]);
+
+        let path = std::env::temp_dir().join(format!("page-turner-{}.txt", std::process::id()));
+        std::fs::write(&path, "1\n2\n3\n4\n").unwrap();
+
+        let file = FilePageTurner::open(&path, 2).unwrap();
+        let numbers: Vec<_> = file.pages(0).items().try_collect().await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(numbers, ["1", "2", "3", "4"]);
+    }
+}
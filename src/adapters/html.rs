@@ -0,0 +1,156 @@
+//! A page turner for websites that paginate content with "next page" links. You describe how to
+//! download a page with [`FetchHtml`], point at the next link with a CSS selector and extract
+//! items out of each downloaded document, [`HtmlPageTurner`] follows the links until the selector
+//! stops matching.
+//!
+//! ```
+//! use page_turner::adapters::html::{FetchHtml, HtmlPageTurner};
+//! use page_turner::prelude::*;
+//! use scraper::Selector;
+//!
+//! struct Site;
+//!
+//! impl FetchHtml for Site {
+//!     type Error = String;
+//!
+//!     async fn fetch_html(&self, url: &str) -> Result<String, Self::Error> {
+//!         match url {
+//!             "https://example.com/posts" => Ok(r#"<h2>First</h2><a rel="next" href="?page=2">"#.into()),
+//!             "https://example.com/posts?page=2" => Ok("<h2>Second</h2>".into()),
+//!             _ => Err(format!("{url} not found")),
+//!         }
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let titles = Selector::parse("h2").unwrap();
+//! let scraper = HtmlPageTurner::new(Site, Selector::parse("a[rel=next]").unwrap(), move |html| {
+//!     html.select(&titles)
+//!         .map(|title| title.text().collect::<String>())
+//!         .collect::<Vec<_>>()
+//! });
+//!
+//! let titles: Vec<_> = scraper
+//!     .pages("https://example.com/posts".to_owned())
+//!     .items()
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//!
+//! assert_eq!(titles, ["First", "Second"]);
+//! # }
+//! ```
+
+use super::resolve_url;
+use crate::mt::{PageTurner, TurnedPageResult};
+use crate::TurnedPage;
+use scraper::{Html, Selector};
+use std::future::Future;
+
+/// An HTTP client capable of downloading HTML documents
+pub trait FetchHtml: Send + Sync {
+    type Error: Send;
+
+    fn fetch_html(&self, url: &str) -> impl Send + Future<Output = Result<String, Self::Error>>;
+}
+
+/// Implements [`PageTurner`] for `String` URL requests. The `href` of the first element matched by
+/// the `next_link` selector becomes the URL of the next page, relative links are resolved against
+/// the URL of the current page.
+#[derive(Debug, Clone)]
+pub struct HtmlPageTurner<C, F> {
+    client: C,
+    next_link: Selector,
+    extract_items: F,
+}
+
+impl<C, F> HtmlPageTurner<C, F> {
+    pub fn new<I>(client: C, next_link: Selector, extract_items: F) -> Self
+    where
+        F: Fn(&Html) -> I,
+    {
+        Self {
+            client,
+            next_link,
+            extract_items,
+        }
+    }
+}
+
+impl<C, F, I> PageTurner<String> for HtmlPageTurner<C, F>
+where
+    C: FetchHtml,
+    F: Send + Sync + Fn(&Html) -> I,
+    I: Send,
+{
+    type PageItems = I;
+    type PageError = C::Error;
+
+    async fn turn_page(&self, url: String) -> TurnedPageResult<Self, String> {
+        let body = self.client.fetch_html(&url).await?;
+
+        // `Html` is not `Send` so it must not live across await points
+        let html = Html::parse_document(&body);
+        let items = (self.extract_items)(&html);
+        let next_url = html
+            .select(&self.next_link)
+            .find_map(|link| link.value().attr("href"))
+            .map(|href| resolve_url(&url, href));
+
+        Ok(TurnedPage::new(items, next_url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+
+    struct Pages(Vec<&'static str>);
+
+    impl FetchHtml for Pages {
+        type Error = String;
+
+        async fn fetch_html(&self, url: &str) -> Result<String, Self::Error> {
+            let ix: usize = url
+                .rsplit('/')
+                .next()
+                .and_then(|page| page.parse().ok())
+                .ok_or_else(|| format!("bad url {url}"))?;
+
+            self.0
+                .get(ix)
+                .map(|page| page.to_string())
+                .ok_or_else(|| format!("{url} not found"))
+        }
+    }
+
+    #[tokio::test]
+    async fn follows_next_links() {
+        let pages = Pages(vec![
+            r#"<li>a</li><li>b</li><a class="next" href="1">Next</a>"#,
+            r#"<li>c</li><a class="next" href="/list/2">Next</a>"#,
+            r#"<li>d</li>"#,
+        ]);
+
+        let item = Selector::parse("li").unwrap();
+        let turner = HtmlPageTurner::new(pages, Selector::parse("a.next").unwrap(), move |html| {
+            html.select(&item)
+                .map(|li| li.inner_html())
+                .collect::<Vec<_>>()
+        });
+
+        let items: Vec<_> = turner
+            .pages("https://example.com/list/0".to_owned())
+            .items()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(items, ["a", "b", "c", "d"]);
+    }
+}
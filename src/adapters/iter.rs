@@ -0,0 +1,210 @@
+//! Turns a blocking `Iterator<Item = Result<T, E>>` into a page turner that yields batches of
+//! items, bridging legacy synchronous data sources into async pagination pipelines.
+//!
+//! Like with [`StreamPageTurner`](super::stream::StreamPageTurner) requests are just `()` tokens.
+//! [`IterPageTurner`] advances the iterator right inside of `turn_page` which blocks the executor,
+//! with the `tokio` feature [`BlockingIterPageTurner`] advances it on a blocking thread instead.
+//!
+//! ```
+//! use page_turner::adapters::iter::IterPageTurner;
+//! use page_turner::prelude::*;
+//! use std::convert::Infallible;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::StreamExt;
+//!
+//! let lines = "a\nb\nc".lines().map(Ok::<_, Infallible>);
+//! let batches: Vec<_> = IterPageTurner::new(lines, 2)
+//!     .pages(())
+//!     .pages_infallible()
+//!     .collect()
+//!     .await;
+//!
+//! assert_eq!(batches, [vec!["a", "b"], vec!["c"]]);
+//! # }
+//! ```
+
+use crate::internal::lock_unpoisoned;
+use crate::TurnedPage;
+use std::{iter::Peekable, sync::Mutex};
+
+struct BatchState<I>
+where
+    I: Iterator,
+{
+    iter: Peekable<I>,
+    pending_error: Option<I::Item>,
+}
+
+impl<I, T, E> BatchState<I>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    fn new(iter: I) -> Mutex<Self> {
+        Mutex::new(Self {
+            iter: iter.peekable(),
+            pending_error: None,
+        })
+    }
+
+    /// If the iterator fails in the middle of a batch the collected items are returned first and
+    /// the error is returned on the next call.
+    fn next_batch(&mut self, batch_size: usize) -> Result<TurnedPage<Vec<T>, ()>, E> {
+        if let Some(Err(e)) = self.pending_error.take() {
+            return Err(e);
+        }
+
+        let mut batch = Vec::with_capacity(batch_size);
+
+        while batch.len() < batch_size {
+            match self.iter.next() {
+                Some(Ok(item)) => batch.push(item),
+                Some(Err(e)) if batch.is_empty() => return Err(e),
+                Some(Err(e)) => {
+                    self.pending_error = Some(Err(e));
+                    return Ok(TurnedPage::next(batch, ()));
+                }
+                None => return Ok(TurnedPage::last(batch)),
+            }
+        }
+
+        let has_more = self.iter.peek().is_some();
+        Ok(TurnedPage::new(batch, has_more.then_some(())))
+    }
+}
+
+/// Implements `PageTurner<()>` for an iterator yielding pages of up to `batch_size` items
+pub struct IterPageTurner<I>
+where
+    I: Iterator,
+{
+    state: Mutex<BatchState<I>>,
+    batch_size: usize,
+}
+
+impl<I, T, E> IterPageTurner<I>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is 0
+    pub fn new(iter: I, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch_size must be positive");
+
+        Self {
+            state: BatchState::new(iter),
+            batch_size,
+        }
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<I, T, E> crate::mt::PageTurner<()> for IterPageTurner<I>
+where
+    I: Send + Iterator<Item = Result<T, E>>,
+    T: Send,
+    E: Send,
+{
+    type PageItems = Vec<T>;
+    type PageError = E;
+
+    async fn turn_page(&self, _: ()) -> crate::mt::TurnedPageResult<Self, ()> {
+        lock_unpoisoned(&self.state).next_batch(self.batch_size)
+    }
+}
+
+#[cfg(feature = "local")]
+impl<I, T, E> crate::local::PageTurner<()> for IterPageTurner<I>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    type PageItems = Vec<T>;
+    type PageError = E;
+
+    async fn turn_page(&self, _: ()) -> crate::local::TurnedPageResult<Self, ()> {
+        lock_unpoisoned(&self.state).next_batch(self.batch_size)
+    }
+}
+
+/// Like [`IterPageTurner`] but advances the iterator with [`tokio::task::spawn_blocking`]
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub struct BlockingIterPageTurner<I>
+where
+    I: Iterator,
+{
+    state: std::sync::Arc<Mutex<BatchState<I>>>,
+    batch_size: usize,
+}
+
+#[cfg(feature = "tokio")]
+impl<I, T, E> BlockingIterPageTurner<I>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is 0
+    pub fn new(iter: I, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch_size must be positive");
+
+        Self {
+            state: std::sync::Arc::new(BatchState::new(iter)),
+            batch_size,
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<I, T, E> crate::mt::PageTurner<()> for BlockingIterPageTurner<I>
+where
+    I: 'static + Send + Iterator<Item = Result<T, E>>,
+    T: 'static + Send,
+    E: 'static + Send,
+{
+    type PageItems = Vec<T>;
+    type PageError = E;
+
+    async fn turn_page(&self, _: ()) -> crate::mt::TurnedPageResult<Self, ()> {
+        let state = self.state.clone();
+        let batch_size = self.batch_size;
+
+        let result =
+            tokio::task::spawn_blocking(move || lock_unpoisoned(&state).next_batch(batch_size))
+                .await;
+
+        match result {
+            Ok(result) => result,
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(e) => panic!("BUG(page-turner): The runtime is shutting down: {e}"),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::{StreamExt, TryStreamExt};
+
+    #[tokio::test]
+    async fn batches() {
+        let numbers = IterPageTurner::new((0..5).map(Ok::<_, ()>), 5);
+        let pages: Vec<_> = numbers.pages(()).try_collect().await.unwrap();
+        assert_eq!(pages, [vec![0, 1, 2, 3, 4]]);
+
+        let failing = IterPageTurner::new([Ok(1), Err("boom"), Ok(2)].into_iter(), 2);
+        let pages: Vec<_> = failing.pages(()).collect().await;
+        assert_eq!(pages, [Ok(vec![1]), Err("boom")]);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn batches_on_blocking_threads() {
+        let numbers = BlockingIterPageTurner::new((0..5).map(Ok::<_, ()>), 2);
+        let numbers: Vec<_> = numbers.pages(()).items().try_collect().await.unwrap();
+        assert_eq!(numbers, [0, 1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,130 @@
+//! Extracts page items and the next page cursor out of raw [`serde_json::Value`] responses. This
+//! covers the most common case of APIs that return something like
+//! `{ "data": [...], "meta": { "next": "cursor" } }` without requiring you to declare response
+//! types.
+//!
+//! ```
+//! use page_turner::adapters::json::JsonPointers;
+//! use serde_json::json;
+//!
+//! struct GetUsers {
+//!     cursor: String,
+//! }
+//!
+//! let pointers = JsonPointers::new("/data", "/meta/next");
+//! let response = json!({ "data": [{ "id": 1 }, { "id": 2 }], "meta": { "next": "abc" } });
+//!
+//! let turned_page = pointers
+//!     .turn_page(response, |cursor| GetUsers {
+//!         cursor: cursor.as_str().unwrap().to_owned(),
+//!     })
+//!     .unwrap();
+//!
+//! assert_eq!(turned_page.items.len(), 2);
+//! assert_eq!(turned_page.next_request.unwrap().cursor, "abc");
+//! ```
+
+use crate::TurnedPage;
+use serde_json::Value;
+
+/// A pair of [JSON pointers](https://datatracker.ietf.org/doc/html/rfc6901) to the array of page
+/// items and to the cursor of the next page inside of a response.
+///
+/// A cursor that is missing, `null` or an empty string marks the last page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPointers {
+    items: String,
+    next_cursor: String,
+}
+
+impl JsonPointers {
+    pub fn new(items: impl Into<String>, next_cursor: impl Into<String>) -> Self {
+        Self {
+            items: items.into(),
+            next_cursor: next_cursor.into(),
+        }
+    }
+
+    /// Takes the items array and the next cursor out of the `response`. The cursor is turned into
+    /// the request for the next page with the `next_request` closure.
+    pub fn turn_page<R>(
+        &self,
+        mut response: Value,
+        next_request: impl FnOnce(Value) -> R,
+    ) -> Result<TurnedPage<Vec<Value>, R>, JsonPageError> {
+        let cursor = response
+            .pointer_mut(&self.next_cursor)
+            .map(Value::take)
+            .filter(|cursor| !is_end_of_pages(cursor));
+
+        let items = match response.pointer_mut(&self.items).map(Value::take) {
+            Some(Value::Array(items)) => items,
+            Some(_) => return Err(JsonPageError::ItemsNotArray(self.items.clone())),
+            None => return Err(JsonPageError::MissingItems(self.items.clone())),
+        };
+
+        Ok(TurnedPage::new(items, cursor.map(next_request)))
+    }
+}
+
+fn is_end_of_pages(cursor: &Value) -> bool {
+    match cursor {
+        Value::Null => true,
+        Value::String(s) => s.is_empty(),
+        _ => false,
+    }
+}
+
+/// Errors that may occur during the items extraction. All variants hold the pointer that failed
+/// to resolve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonPageError {
+    MissingItems(String),
+    ItemsNotArray(String),
+}
+
+impl std::fmt::Display for JsonPageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingItems(pointer) => write!(f, "no page items found at `{pointer}`"),
+            Self::ItemsNotArray(pointer) => write!(f, "page items at `{pointer}` are not an array"),
+        }
+    }
+}
+
+impl std::error::Error for JsonPageError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn turn_page() {
+        let pointers = JsonPointers::new("/items", "/next");
+
+        let page = pointers
+            .turn_page(json!({ "items": [1, 2, 3], "next": 4 }), |cursor| {
+                cursor.as_u64().unwrap()
+            })
+            .unwrap();
+
+        assert_eq!(page.items, vec![json!(1), json!(2), json!(3)]);
+        assert_eq!(page.next_request, Some(4));
+
+        for last in [
+            json!({ "items": [] }),
+            json!({ "items": [], "next": null }),
+            json!({ "items": [], "next": "" }),
+        ] {
+            let page = pointers.turn_page(last, |_| ()).unwrap();
+            assert!(page.next_request.is_none());
+        }
+
+        let err = pointers.turn_page(json!({ "next": 1 }), |_| ()).err();
+        assert_eq!(err, Some(JsonPageError::MissingItems("/items".to_owned())));
+
+        let err = pointers.turn_page(json!({ "items": {} }), |_| ()).err();
+        assert_eq!(err, Some(JsonPageError::ItemsNotArray("/items".to_owned())));
+    }
+}
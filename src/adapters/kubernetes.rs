@@ -0,0 +1,266 @@
+//! A page turner for the [Kubernetes API list
+//! semantics](https://kubernetes.io/docs/reference/using-api/api-concepts/#retrieving-large-results-sets-in-chunks)
+//! where the `continue` token of a response requests the next chunk of a large list.
+//!
+//! Continue tokens expire after a while and the API responds with `410 Gone` to them. The
+//! [`ExpiredPolicy`] decides what to do then. This adapter doesn't depend on any Kubernetes client,
+//! implement [`ListResources`] for the one you use.
+//!
+//! ```
+//! use page_turner::adapters::kubernetes::{
+//!     ExpiredPolicy, KubeListPageTurner, ListError, ListPage, ListRequest, ListResources,
+//! };
+//! use page_turner::prelude::*;
+//!
+//! struct Pods(Vec<&'static str>);
+//!
+//! impl ListResources for Pods {
+//!     type Item = &'static str;
+//!     type Error = String;
+//!
+//!     async fn list(&self, request: &ListRequest) -> Result<ListPage<Self::Item>, ListError<Self::Error>> {
+//!         // A real client would send `?limit=..&continue=..` query parameters
+//!         let start: usize = request.continue_token.as_deref().map_or(0, |t| t.parse().unwrap());
+//!         let end = (start + request.limit as usize).min(self.0.len());
+//!         let continue_token = (end < self.0.len()).then(|| end.to_string());
+//!
+//!         Ok(ListPage { items: self.0[start..end].to_vec(), continue_token })
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let pods = KubeListPageTurner::new(Pods(vec!["api", "db", "cache"]), ExpiredPolicy::Fail);
+//! let names: Vec<_> = pods.pages(ListRequest::new(2)).items().try_collect().await.unwrap();
+//!
+//! assert_eq!(names, ["api", "db", "cache"]);
+//! # }
+//! ```
+
+use crate::mt::{PageTurner, TurnedPageResult};
+use crate::TurnedPage;
+use std::future::Future;
+
+/// Parameters of a list request
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ListRequest {
+    /// The `limit` query parameter, the maximum amount of items in a chunk
+    pub limit: u32,
+    /// The `continue` query parameter, `None` for the first chunk
+    pub continue_token: Option<String>,
+}
+
+impl ListRequest {
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            continue_token: None,
+        }
+    }
+}
+
+/// Items of a list response and its `metadata.continue` field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListPage<T> {
+    pub items: Vec<T>,
+    /// `None` or an empty string means the last chunk
+    pub continue_token: Option<String>,
+}
+
+/// A Kubernetes client capable of listing resources of a single kind
+pub trait ListResources: Send + Sync {
+    type Item: Send;
+    type Error: Send;
+
+    /// Sends a list request. A `410 Gone` response must be reported as [`ListError::Expired`].
+    fn list(
+        &self,
+        request: &ListRequest,
+    ) -> impl Send + Future<Output = Result<ListPage<Self::Item>, ListError<Self::Error>>>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListError<E> {
+    /// The continue token has expired. Kubernetes includes a new continue token into this response
+    /// which allows to proceed without consistency guarantees.
+    Expired { continue_token: Option<String> },
+    /// Any other client error
+    Other(E),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for ListError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Expired { .. } => f.write_str("the continue token has expired"),
+            Self::Other(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ListError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Expired { .. } => None,
+            Self::Other(e) => Some(e),
+        }
+    }
+}
+
+/// What to do when a continue token has expired
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExpiredPolicy {
+    /// Return [`ListError::Expired`]
+    Fail,
+    /// Restart the list from the first chunk at most `max_restarts` times. Items of the chunks
+    /// queried before the restart will be yielded again.
+    Restart { max_restarts: usize },
+    /// Proceed with the continue token from the `410 Gone` response. The resulting list may miss
+    /// or duplicate items changed in the meantime.
+    ContinueInconsistent,
+}
+
+/// Implements [`PageTurner`] for [`ListRequest`] over a [`ListResources`] client
+#[derive(Debug, Clone)]
+pub struct KubeListPageTurner<C> {
+    client: C,
+    policy: ExpiredPolicy,
+}
+
+impl<C> KubeListPageTurner<C> {
+    pub fn new(client: C, policy: ExpiredPolicy) -> Self {
+        Self { client, policy }
+    }
+
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+}
+
+impl<C> PageTurner<ListRequest> for KubeListPageTurner<C>
+where
+    C: ListResources,
+{
+    type PageItems = Vec<C::Item>;
+    type PageError = ListError<C::Error>;
+
+    async fn turn_page(&self, mut request: ListRequest) -> TurnedPageResult<Self, ListRequest> {
+        let mut restarts = 0;
+
+        loop {
+            let continue_token = match self.client.list(&request).await {
+                Ok(ListPage {
+                    items,
+                    continue_token,
+                }) => {
+                    let next_request =
+                        continue_token
+                            .filter(|token| !token.is_empty())
+                            .map(|token| ListRequest {
+                                limit: request.limit,
+                                continue_token: Some(token),
+                            });
+
+                    return Ok(TurnedPage::new(items, next_request));
+                }
+                Err(ListError::Expired { continue_token }) => continue_token,
+                Err(e) => return Err(e),
+            };
+
+            request.continue_token = match self.policy {
+                ExpiredPolicy::Restart { max_restarts } if restarts < max_restarts => {
+                    restarts += 1;
+                    None
+                }
+                ExpiredPolicy::ContinueInconsistent if continue_token.is_some() => continue_token,
+                _ => return Err(ListError::Expired { continue_token }),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+    use std::sync::Mutex;
+
+    /// Lists numbers and expires the continue token of the given chunk once
+    struct Numbers {
+        total: usize,
+        expire_at: Mutex<Option<usize>>,
+    }
+
+    impl Numbers {
+        fn new(total: usize, expire_at: usize) -> Self {
+            Self {
+                total,
+                expire_at: Mutex::new(Some(expire_at)),
+            }
+        }
+    }
+
+    impl ListResources for Numbers {
+        type Item = usize;
+        type Error = ();
+
+        async fn list(&self, request: &ListRequest) -> Result<ListPage<usize>, ListError<()>> {
+            let start: usize = request
+                .continue_token
+                .as_deref()
+                .map_or(0, |t| t.parse().unwrap());
+
+            let mut expire_at = self.expire_at.lock().unwrap();
+            if *expire_at == Some(start) {
+                *expire_at = None;
+                return Err(ListError::Expired {
+                    continue_token: Some((start + 1).to_string()),
+                });
+            }
+
+            let end = (start + request.limit as usize).min(self.total);
+            let continue_token = if end < self.total {
+                end.to_string()
+            } else {
+                String::new()
+            };
+
+            Ok(ListPage {
+                items: (start..end).collect(),
+                continue_token: Some(continue_token),
+            })
+        }
+    }
+
+    async fn list(policy: ExpiredPolicy) -> Result<Vec<usize>, ListError<()>> {
+        KubeListPageTurner::new(Numbers::new(6, 4), policy)
+            .pages(ListRequest::new(2))
+            .items()
+            .try_collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn expired_policies() {
+        assert_eq!(
+            list(ExpiredPolicy::Fail).await,
+            Err(ListError::Expired {
+                continue_token: Some("5".to_owned())
+            })
+        );
+
+        assert_eq!(
+            list(ExpiredPolicy::Restart { max_restarts: 1 }).await,
+            Ok(vec![0, 1, 2, 3, 0, 1, 2, 3, 4, 5])
+        );
+
+        assert_eq!(
+            list(ExpiredPolicy::ContinueInconsistent).await,
+            Ok(vec![0, 1, 2, 3, 5])
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! A page turner for HTTP APIs that point at the next page with an
+//! [RFC 8288](https://datatracker.ietf.org/doc/html/rfc8288) `Link` header, like GitHub and GitLab
+//! REST APIs do:
+//!
+//! ```text
+//! Link: <https://api.github.com/repositories/1/issues?page=2>; rel="next", <...>; rel="last"
+//! ```
+//!
+//! Implement [`FetchLinked`] for your HTTP client or pass a closure and [`LinkHeaderTurner`]
+//! follows `rel="next"` links until a response doesn't have one. Relative links are resolved
+//! against the URL of the current page.
+//!
+//! ```
+//! use page_turner::adapters::link_header::{LinkHeaderTurner, LinkedResponse};
+//! use page_turner::prelude::*;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let api = LinkHeaderTurner::new(|url: String| async move {
+//!     // A real client would send a GET request and take the body and the `Link` header
+//!     let (issues, link) = match url.as_str() {
+//!         "https://api.example.com/issues" => (
+//!             vec![1, 2],
+//!             Some(r#"</issues?page=2>; rel="next", </issues?page=2>; rel="last""#),
+//!         ),
+//!         "https://api.example.com/issues?page=2" => (vec![3], None),
+//!         _ => return Err(format!("{url} not found")),
+//!     };
+//!
+//!     Ok(LinkedResponse {
+//!         items: issues,
+//!         link: link.map(String::from),
+//!     })
+//! });
+//!
+//! let issues: Vec<_> = api
+//!     .pages("https://api.example.com/issues".to_owned())
+//!     .items()
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//!
+//! assert_eq!(issues, [1, 2, 3]);
+//! # }
+//! ```
+
+use super::{next_link, resolve_url};
+use crate::mt::{PageTurner, TurnedPageResult};
+use crate::TurnedPage;
+use std::future::Future;
+
+/// Items of a response along with its `Link` header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkedResponse<I> {
+    pub items: I,
+    /// The raw value of the `Link` header
+    pub link: Option<String>,
+}
+
+/// An HTTP client capable of sending GET requests. Implemented for
+/// `Fn(String) -> impl Future<Output = Result<LinkedResponse<I>, E>>` closures.
+pub trait FetchLinked: Send + Sync {
+    type Items: Send;
+    type Error: Send;
+
+    fn fetch_linked(
+        &self,
+        url: String,
+    ) -> impl Send + Future<Output = Result<LinkedResponse<Self::Items>, Self::Error>>;
+}
+
+impl<F, Fut, I, E> FetchLinked for F
+where
+    F: Send + Sync + Fn(String) -> Fut,
+    Fut: Send + Future<Output = Result<LinkedResponse<I>, E>>,
+    I: Send,
+    E: Send,
+{
+    type Items = I;
+    type Error = E;
+
+    fn fetch_linked(
+        &self,
+        url: String,
+    ) -> impl Send + Future<Output = Result<LinkedResponse<I>, E>> {
+        self(url)
+    }
+}
+
+/// Implements [`PageTurner`] for `String` URL requests following `rel="next"` links of the `Link`
+/// header. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct LinkHeaderTurner<C> {
+    client: C,
+}
+
+impl<C> LinkHeaderTurner<C> {
+    pub fn new(client: C) -> Self {
+        Self { client }
+    }
+
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+}
+
+impl<C> PageTurner<String> for LinkHeaderTurner<C>
+where
+    C: FetchLinked,
+{
+    type PageItems = C::Items;
+    type PageError = C::Error;
+
+    async fn turn_page(&self, url: String) -> TurnedPageResult<Self, String> {
+        let LinkedResponse { items, link } = self.client.fetch_linked(url.clone()).await?;
+        let next_url = link
+            .as_deref()
+            .and_then(next_link)
+            .map(|next| resolve_url(&url, next));
+
+        Ok(TurnedPage::new(items, next_url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::TryStreamExt;
+
+    /// Serves `per_page` numbers per page with absolute `Link` headers like GitHub does
+    struct Numbers {
+        total: usize,
+        per_page: usize,
+    }
+
+    impl FetchLinked for Numbers {
+        type Items = Vec<usize>;
+        type Error = String;
+
+        async fn fetch_linked(&self, url: String) -> Result<LinkedResponse<Vec<usize>>, String> {
+            let page: usize = url
+                .split_once("?page=")
+                .map_or(Ok(1), |(_, page)| page.parse())
+                .map_err(|_| format!("bad url {url}"))?;
+
+            let start = (page - 1) * self.per_page;
+            let items = (start..self.total.min(start + self.per_page)).collect();
+            let last_page = self.total.div_ceil(self.per_page);
+
+            let mut links = vec![format!(
+                r#"<https://api.io/n?page={last_page}>; rel="last""#
+            )];
+            if page < last_page {
+                links.push(format!(
+                    r#"<https://api.io/n?page={}>; rel="next""#,
+                    page + 1
+                ));
+            }
+
+            Ok(LinkedResponse {
+                items,
+                link: Some(links.join(", ")),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn follows_link_headers() {
+        let turner = LinkHeaderTurner::new(Numbers {
+            total: 7,
+            per_page: 3,
+        });
+
+        let pages: Vec<_> = turner
+            .pages("https://api.io/n".to_owned())
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(pages, [vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+    }
+}
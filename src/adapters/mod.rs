@@ -0,0 +1,135 @@
+//! Ready to use building blocks for common pagination schemes. Each adapter is available behind
+//! the feature flag named after the dependency it requires, adapters without dependencies only
+//! require the `mt` feature.
+
+#[cfg(feature = "serde_json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+pub mod json;
+
+#[cfg(feature = "scraper")]
+#[cfg_attr(docsrs, doc(cfg(feature = "scraper")))]
+pub mod html;
+
+#[cfg(feature = "mt")]
+pub mod file;
+
+#[cfg(any(feature = "mt", feature = "local"))]
+pub mod iter;
+
+#[cfg(feature = "mt")]
+pub mod kubernetes;
+
+#[cfg(feature = "mt")]
+pub mod link_header;
+
+#[cfg(feature = "mt")]
+pub mod registry;
+
+#[cfg(any(feature = "mt", feature = "local"))]
+pub mod slice;
+
+#[cfg(any(feature = "mt", feature = "local"))]
+pub mod stream;
+
+#[cfg(feature = "feed-rs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "feed-rs")))]
+pub mod feed;
+
+/// Resolves a possibly relative `href` against the URL of the document it was found in
+#[cfg(feature = "mt")]
+pub(crate) fn resolve_url(base: &str, href: &str) -> String {
+    let href = href.trim();
+    let scheme_end = base.find("://").map(|pos| pos + 3);
+
+    if href.contains("://") {
+        return href.to_owned();
+    }
+
+    if let Some(rest) = href.strip_prefix("//") {
+        let scheme = base.split("://").next().unwrap_or("https");
+        return format!("{scheme}://{rest}");
+    }
+
+    let path_start = scheme_end
+        .and_then(|start| base[start..].find('/').map(|pos| start + pos))
+        .unwrap_or(base.len());
+    let path_end = base.find(['?', '#']).unwrap_or(base.len()).max(path_start);
+
+    if href.starts_with('/') {
+        format!("{}{href}", &base[..path_start])
+    } else if href.starts_with('?') {
+        format!("{}{href}", &base[..path_end])
+    } else {
+        let dir_end = base[path_start..path_end]
+            .rfind('/')
+            .map(|pos| path_start + pos + 1);
+
+        match dir_end {
+            Some(end) => format!("{}{href}", &base[..end]),
+            None => format!("{}/{href}", &base[..path_start]),
+        }
+    }
+}
+
+/// Returns the target of the `rel="next"` link of an [RFC 8288](https://datatracker.ietf.org/doc/html/rfc8288)
+/// `Link` header value
+#[cfg(feature = "mt")]
+pub(crate) fn next_link(header: &str) -> Option<&str> {
+    header.split(',').find_map(|link| {
+        let (target, params) = link.split_once(';')?;
+        let is_next = params.split(';').any(|param| {
+            let Some((name, value)) = param.split_once('=') else {
+                return false;
+            };
+
+            name.trim().eq_ignore_ascii_case("rel")
+                && value
+                    .trim()
+                    .trim_matches('"')
+                    .split_ascii_whitespace()
+                    .any(|rel| rel.eq_ignore_ascii_case("next"))
+        });
+
+        is_next.then(|| target.trim().trim_start_matches('<').trim_end_matches('>'))
+    })
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    #[test]
+    fn resolve_url() {
+        let base = "https://example.com/a/b?page=1";
+
+        assert_eq!(
+            super::resolve_url(base, "http://other.org/"),
+            "http://other.org/"
+        );
+        assert_eq!(super::resolve_url(base, "//cdn.com/x"), "https://cdn.com/x");
+        assert_eq!(super::resolve_url(base, "/c"), "https://example.com/c");
+        assert_eq!(
+            super::resolve_url(base, "?page=2"),
+            "https://example.com/a/b?page=2"
+        );
+        assert_eq!(super::resolve_url(base, "c"), "https://example.com/a/c");
+        assert_eq!(
+            super::resolve_url("https://example.com", "c"),
+            "https://example.com/c"
+        );
+    }
+
+    #[test]
+    fn next_link() {
+        assert_eq!(
+            super::next_link(r#"</v2/_catalog?n=2&last=b>; rel="next""#),
+            Some("/v2/_catalog?n=2&last=b")
+        );
+        assert_eq!(
+            super::next_link(
+                r#"<https://a.com/1>; rel="prev", <https://a.com/3>; title="x"; rel="last next""#
+            ),
+            Some("https://a.com/3")
+        );
+        assert_eq!(super::next_link(r#"<https://a.com/1>; rel=prev"#), None);
+        assert_eq!(super::next_link(""), None);
+    }
+}
@@ -0,0 +1,209 @@
+//! A page turner for repository catalogs and tag lists of OCI/Docker registries described in the
+//! [distribution spec](https://github.com/opencontainers/distribution-spec/blob/main/spec.md#listing-tags).
+//!
+//! The URL of the next page is taken from the `Link` header. Registries that don't send it are
+//! paginated by the `n` and `last` query parameters: if a page is full the next one starts after
+//! its last name.
+//!
+//! ```
+//! use page_turner::adapters::registry::{self, FetchRegistry, Listing, RegistryPageTurner};
+//! use page_turner::prelude::*;
+//!
+//! struct Registry;
+//!
+//! impl FetchRegistry for Registry {
+//!     type Error = String;
+//!
+//!     async fn fetch_listing(&self, url: &str) -> Result<Listing, Self::Error> {
+//!         // A real client would send a GET request and deserialize the `tags` field of the body
+//!         let (tags, link) = match url {
+//!             "https://registry.io/v2/library/alpine/tags/list?n=2" => (
+//!                 vec!["3.18", "3.19"],
+//!                 Some(r#"</v2/library/alpine/tags/list?n=2&last=3.19>; rel="next""#),
+//!             ),
+//!             "https://registry.io/v2/library/alpine/tags/list?n=2&last=3.19" => {
+//!                 (vec!["3.20"], None)
+//!             }
+//!             _ => return Err(format!("{url} not found")),
+//!         };
+//!
+//!         Ok(Listing {
+//!             names: tags.into_iter().map(String::from).collect(),
+//!             link: link.map(String::from),
+//!         })
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let tags: Vec<_> = RegistryPageTurner::new(Registry)
+//!     .pages(registry::tags_url("https://registry.io", "library/alpine", Some(2)))
+//!     .items()
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//!
+//! assert_eq!(tags, ["3.18", "3.19", "3.20"]);
+//! # }
+//! ```
+
+use super::{next_link, resolve_url};
+use crate::mt::{PageTurner, TurnedPageResult};
+use crate::TurnedPage;
+use std::future::Future;
+
+/// Names listed in a registry response along with its `Link` header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Listing {
+    /// `repositories` of a catalog or `tags` of a tag list
+    pub names: Vec<String>,
+    /// The raw value of the `Link` header
+    pub link: Option<String>,
+}
+
+/// A registry client capable of sending authorized GET requests
+pub trait FetchRegistry: Send + Sync {
+    type Error: Send;
+
+    fn fetch_listing(&self, url: &str)
+        -> impl Send + Future<Output = Result<Listing, Self::Error>>;
+}
+
+/// The URL of the `/v2/_catalog` endpoint listing repositories of the `registry`
+pub fn catalog_url(registry: &str, n: Option<u32>) -> String {
+    with_page_size(format!("{}/v2/_catalog", registry.trim_end_matches('/')), n)
+}
+
+/// The URL of the `/v2/<name>/tags/list` endpoint listing tags of the `repository`
+pub fn tags_url(registry: &str, repository: &str, n: Option<u32>) -> String {
+    with_page_size(
+        format!(
+            "{}/v2/{repository}/tags/list",
+            registry.trim_end_matches('/')
+        ),
+        n,
+    )
+}
+
+fn with_page_size(url: String, n: Option<u32>) -> String {
+    match n {
+        Some(n) => format!("{url}?n={n}"),
+        None => url,
+    }
+}
+
+fn query_param<'u>(url: &'u str, name: &str) -> Option<&'u str> {
+    let (_, query) = url.split_once('?')?;
+    query
+        .split('&')
+        .filter_map(|param| param.split_once('='))
+        .find_map(|(key, value)| (key == name).then_some(value))
+}
+
+fn with_last(url: &str, last: &str) -> String {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let params = query
+        .split('&')
+        .filter(|param| !param.is_empty() && !param.starts_with("last="))
+        .chain(std::iter::once(format!("last={last}").as_str()))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    format!("{path}?{params}")
+}
+
+/// Implements [`PageTurner`] for `String` URL requests yielding repository or tag names. Use
+/// [`catalog_url`] and [`tags_url`] to make the first request.
+#[derive(Debug, Clone)]
+pub struct RegistryPageTurner<C> {
+    client: C,
+}
+
+impl<C> RegistryPageTurner<C> {
+    pub fn new(client: C) -> Self {
+        Self { client }
+    }
+
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+}
+
+impl<C> PageTurner<String> for RegistryPageTurner<C>
+where
+    C: FetchRegistry,
+{
+    type PageItems = Vec<String>;
+    type PageError = C::Error;
+
+    async fn turn_page(&self, url: String) -> TurnedPageResult<Self, String> {
+        let Listing { names, link } = self.client.fetch_listing(&url).await?;
+
+        let next_url = match link.as_deref().and_then(next_link) {
+            Some(next) => Some(resolve_url(&url, next)),
+            None => {
+                let page_size = query_param(&url, "n").and_then(|n| n.parse::<usize>().ok());
+                match (page_size, names.last()) {
+                    (Some(n), Some(last)) if names.len() >= n => Some(with_last(&url, last)),
+                    _ => None,
+                }
+            }
+        };
+
+        Ok(TurnedPage::new(names, next_url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+
+    /// A registry without `Link` headers
+    struct Repositories(Vec<&'static str>);
+
+    impl FetchRegistry for Repositories {
+        type Error = ();
+
+        async fn fetch_listing(&self, url: &str) -> Result<Listing, ()> {
+            let n: usize = query_param(url, "n").unwrap().parse().unwrap();
+            let last = query_param(url, "last");
+
+            let names = self
+                .0
+                .iter()
+                .filter(|name| last.map_or(true, |last| **name > last))
+                .take(n)
+                .map(|name| name.to_string())
+                .collect();
+
+            Ok(Listing { names, link: None })
+        }
+    }
+
+    #[tokio::test]
+    async fn paginates_by_last() {
+        let repositories = Repositories(vec!["a", "b", "c", "d"]);
+        let turner = RegistryPageTurner::new(repositories);
+
+        let names: Vec<_> = turner
+            .pages(catalog_url("https://registry.io/", Some(2)))
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(names, [vec!["a", "b"], vec!["c", "d"], vec![]]);
+
+        let names: Vec<_> = turner
+            .pages(catalog_url("https://registry.io", Some(3)))
+            .items()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(names, ["a", "b", "c", "d"]);
+    }
+}
@@ -0,0 +1,212 @@
+//! An in-memory page turner over a vector of items. It's handy in tests, examples and for feeding
+//! static data into pages streams. Artificial delays and failures can be injected to simulate a
+//! real API.
+//!
+//! ```
+//! use page_turner::adapters::slice::{SlicePage, SlicePageTurner, SliceError};
+//! use page_turner::prelude::*;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let letters = SlicePageTurner::new(vec!['a', 'b', 'c', 'd', 'e'], 2);
+//! let pages: Vec<_> = letters
+//!     .pages_ahead(2, Limit::None, SlicePage(0))
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//!
+//! assert_eq!(pages, [vec!['a', 'b'], vec!['c', 'd'], vec!['e']]);
+//!
+//! let letters = letters.fail_at(1, 1);
+//! let result: Result<Vec<_>, _> = letters.pages(SlicePage(0)).items().try_collect().await;
+//! assert_eq!(result, Err(SliceError::Injected(1)));
+//!
+//! let output: String = letters.pages(SlicePage(0)).items().try_collect().await.unwrap();
+//! assert_eq!(output, "abcde", "The injected failure happens only once");
+//! # }
+//! ```
+
+use crate::internal::lock_unpoisoned;
+use crate::{RequestAhead, TurnedPage};
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+/// A request of the page with the given index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlicePage(pub usize);
+
+impl RequestAhead for SlicePage {
+    fn next_request(&self) -> Self {
+        Self(self.0 + 1)
+    }
+
+    fn nth_request(&self, n: usize) -> Self {
+        Self(self.0 + n + 1)
+    }
+}
+
+/// Implements `PageTurner` for [`SlicePage`] requests yielding pages of `page_size` cloned items
+#[derive(Debug)]
+pub struct SlicePageTurner<T> {
+    items: Vec<T>,
+    page_size: usize,
+    delay: Duration,
+    failures: Mutex<HashMap<usize, usize>>,
+}
+
+impl<T> SlicePageTurner<T>
+where
+    T: Clone,
+{
+    /// # Panics
+    ///
+    /// Panics if `page_size` is 0
+    pub fn new(items: Vec<T>, page_size: usize) -> Self {
+        assert!(page_size > 0, "page_size must be positive");
+
+        Self {
+            items,
+            page_size,
+            delay: Duration::ZERO,
+            failures: Mutex::default(),
+        }
+    }
+
+    /// Delays every response
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Makes the next `times` requests of the `page` fail with [`SliceError::Injected`]. Use
+    /// `usize::MAX` to make the page always fail.
+    pub fn fail_at(self, page: usize, times: usize) -> Self {
+        lock_unpoisoned(&self.failures).insert(page, times);
+        self
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.items
+    }
+
+    async fn slice_page(&self, SlicePage(page): SlicePage) -> SliceResult<T> {
+        if !self.delay.is_zero() {
+            futures_timer::Delay::new(self.delay).await;
+        }
+
+        if let Some(times) = lock_unpoisoned(&self.failures).get_mut(&page) {
+            if *times > 0 {
+                *times -= 1;
+                return Err(SliceError::Injected(page));
+            }
+        }
+
+        let start = page.saturating_mul(self.page_size);
+        if start >= self.items.len() && !(page == 0 && self.items.is_empty()) {
+            return Err(SliceError::OutOfBounds(page));
+        }
+
+        let end = start.saturating_add(self.page_size).min(self.items.len());
+        let next = (end < self.items.len()).then_some(SlicePage(page + 1));
+
+        Ok(TurnedPage::new(self.items[start..end].to_vec(), next))
+    }
+}
+
+type SliceResult<T> = Result<TurnedPage<Vec<T>, SlicePage>, SliceError>;
+
+#[cfg(feature = "mt")]
+impl<T> crate::mt::PageTurner<SlicePage> for SlicePageTurner<T>
+where
+    T: Clone + Send + Sync,
+{
+    type PageItems = Vec<T>;
+    type PageError = SliceError;
+
+    async fn turn_page(&self, request: SlicePage) -> crate::mt::TurnedPageResult<Self, SlicePage> {
+        self.slice_page(request).await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<T> crate::local::PageTurner<SlicePage> for SlicePageTurner<T>
+where
+    T: Clone,
+{
+    type PageItems = Vec<T>;
+    type PageError = SliceError;
+
+    async fn turn_page(
+        &self,
+        request: SlicePage,
+    ) -> crate::local::TurnedPageResult<Self, SlicePage> {
+        self.slice_page(request).await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SliceError {
+    /// The page is past the end of items
+    OutOfBounds(usize),
+    /// A failure injected with [`SlicePageTurner::fail_at`]
+    Injected(usize),
+}
+
+impl std::fmt::Display for SliceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutOfBounds(page) => write!(f, "page {page} is out of bounds"),
+            Self::Injected(page) => write!(f, "injected failure of page {page}"),
+        }
+    }
+}
+
+impl std::error::Error for SliceError {}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+
+    #[tokio::test]
+    async fn pages() {
+        let empty = SlicePageTurner::<u8>::new(Vec::new(), 3);
+        let pages: Vec<_> = empty.pages(SlicePage(0)).try_collect().await.unwrap();
+        assert_eq!(pages, [Vec::<u8>::new()]);
+
+        let numbers = SlicePageTurner::new((0..9).collect(), 3).delay(Duration::from_millis(1));
+        let pages: Vec<Vec<u32>> = numbers.pages(SlicePage(1)).try_collect().await.unwrap();
+        assert_eq!(pages, [vec![3, 4, 5], vec![6, 7, 8]]);
+
+        let result = numbers
+            .pages_ahead(4, Limit::None, SlicePage(0))
+            .try_collect::<Vec<_>>()
+            .await;
+        assert_eq!(result.unwrap().len(), 3);
+
+        assert_eq!(
+            numbers.turn_page(SlicePage(2)).await,
+            Ok(TurnedPage::last(vec![6, 7, 8]))
+        );
+
+        let result = numbers.pages(SlicePage(3)).try_collect::<Vec<_>>().await;
+        assert_eq!(result, Err(SliceError::OutOfBounds(3)));
+
+        let slow_numbers =
+            SlicePageTurner::new((0..9).collect::<Vec<u8>>(), 3).delay(Duration::from_millis(50));
+        let result = slow_numbers
+            .pages_ahead(1, Limit::Duration(Duration::from_millis(30)), SlicePage(0))
+            .try_collect::<Vec<_>>()
+            .await;
+        assert_eq!(result.unwrap(), [vec![0, 1, 2]]);
+
+        let numbers = numbers.fail_at(2, usize::MAX);
+        for _ in 0..2 {
+            let result = numbers.pages(SlicePage(0)).try_collect::<Vec<_>>().await;
+            assert_eq!(result, Err(SliceError::Injected(2)));
+        }
+    }
+}
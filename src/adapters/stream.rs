@@ -0,0 +1,146 @@
+//! Turns an arbitrary `Stream<Item = Result<T, E>>` into a page turner that yields batches of
+//! items, so existing streaming sources gain page level processing, limits and the pages/items
+//! duality of pages streams.
+//!
+//! A stream can only be consumed sequentially, therefore, requests are just `()` tokens and the
+//! stream is locked while a batch is being collected.
+//!
+//! ```
+//! use page_turner::adapters::stream::StreamPageTurner;
+//! use page_turner::prelude::*;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::{stream, TryStreamExt};
+//!
+//! let numbers = stream::iter((1..=5).map(Ok::<_, ()>));
+//! let batches: Vec<_> = StreamPageTurner::new(numbers, 2)
+//!     .pages(())
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//!
+//! assert_eq!(batches, [vec![1, 2], vec![3, 4], vec![5]]);
+//! # }
+//! ```
+
+use crate::TurnedPage;
+use futures::{
+    lock::Mutex,
+    stream::{Peekable, Stream, StreamExt},
+};
+use std::pin::Pin;
+
+/// Implements `PageTurner<()>` for a stream yielding pages of up to `batch_size` items
+pub struct StreamPageTurner<S>
+where
+    S: Stream,
+{
+    state: Mutex<BatchState<S>>,
+    batch_size: usize,
+}
+
+struct BatchState<S>
+where
+    S: Stream,
+{
+    stream: Pin<Box<Peekable<S>>>,
+    pending_error: Option<S::Item>,
+}
+
+impl<S, T, E> StreamPageTurner<S>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is 0
+    pub fn new(stream: S, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch_size must be positive");
+
+        Self {
+            state: Mutex::new(BatchState {
+                stream: Box::pin(stream.peekable()),
+                pending_error: None,
+            }),
+            batch_size,
+        }
+    }
+
+    /// Collects the next batch. If the stream fails in the middle of a batch the collected items
+    /// are returned first and the error is returned on the next call.
+    async fn next_batch(&self) -> Result<TurnedPage<Vec<T>, ()>, E> {
+        let mut state = self.state.lock().await;
+
+        if let Some(Err(e)) = state.pending_error.take() {
+            return Err(e);
+        }
+
+        let mut batch = Vec::with_capacity(self.batch_size);
+
+        while batch.len() < self.batch_size {
+            match state.stream.next().await {
+                Some(Ok(item)) => batch.push(item),
+                Some(Err(e)) if batch.is_empty() => return Err(e),
+                Some(Err(e)) => {
+                    state.pending_error = Some(Err(e));
+                    return Ok(TurnedPage::next(batch, ()));
+                }
+                None => return Ok(TurnedPage::last(batch)),
+            }
+        }
+
+        let has_more = state.stream.as_mut().peek().await.is_some();
+        Ok(TurnedPage::new(batch, has_more.then_some(())))
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<S, T, E> crate::mt::PageTurner<()> for StreamPageTurner<S>
+where
+    S: Send + Stream<Item = Result<T, E>>,
+    T: Send,
+    E: Send,
+{
+    type PageItems = Vec<T>;
+    type PageError = E;
+
+    async fn turn_page(&self, _: ()) -> crate::mt::TurnedPageResult<Self, ()> {
+        self.next_batch().await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<S, T, E> crate::local::PageTurner<()> for StreamPageTurner<S>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    type PageItems = Vec<T>;
+    type PageError = E;
+
+    async fn turn_page(&self, _: ()) -> crate::local::TurnedPageResult<Self, ()> {
+        self.next_batch().await
+    }
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::{stream, TryStreamExt};
+
+    #[tokio::test]
+    async fn rebatches() {
+        let even = StreamPageTurner::new(stream::iter((0..4).map(Ok::<_, ()>)), 2);
+        let pages: Vec<_> = even.pages(()).try_collect().await.unwrap();
+        assert_eq!(pages, [vec![0, 1], vec![2, 3]]);
+
+        let empty = StreamPageTurner::new(stream::empty::<Result<u8, ()>>(), 2);
+        let pages: Vec<_> = empty.pages(()).try_collect().await.unwrap();
+        assert_eq!(pages, [Vec::<u8>::new()]);
+
+        let failing = StreamPageTurner::new(stream::iter([Ok(1), Ok(2), Ok(3), Err("boom")]), 2);
+        let pages: Vec<_> = failing.pages(()).collect().await;
+        assert_eq!(pages, [Ok(vec![1, 2]), Ok(vec![3]), Err("boom")]);
+    }
+}
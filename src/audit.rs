@@ -0,0 +1,356 @@
+//! Opt-in recording of pagination runs for audit trails and bug reports against flaky APIs.
+//!
+//! Wrap a page turner into [`Audited`] and keep its [`AuditLog`] handle, once the stream completes
+//! or fails [`AuditLog::summary`] returns every issued request with its timing and outcome along
+//! with the final cursor to resume the pagination from. Put [`Audited`] inside of
+//! [`crate::retry::Retry`] to record every retry attempt.
+//!
+//! ```
+//! use page_turner::audit::{Audited, PageOutcome};
+//! use page_turner::prelude::*;
+//!
+//! struct Api;
+//!
+//! impl PageTurner<usize> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         match page {
+//!             0..=1 => Ok(TurnedPage::next(vec![page], page + 1)),
+//!             _ => Err(()),
+//!         }
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let api = Audited::new(Api);
+//! let log = api.log();
+//!
+//! assert!(api.pages(0).try_collect::<Vec<_>>().await.is_err());
+//!
+//! let summary = log.summary();
+//! assert_eq!(summary.requests_issued(), 3);
+//! assert_eq!(summary.pages[2].outcome, PageOutcome::Error);
+//! assert_eq!(summary.cursor, Some(2));
+//! # }
+//! ```
+
+use crate::internal::lock_unpoisoned;
+use crate::TurnedPage;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A page turner that records every request into an [`AuditLog`]. See the [module docs](self).
+#[derive(Debug)]
+pub struct Audited<P, R> {
+    page_turner: P,
+    log: AuditLog<R>,
+}
+
+impl<P, R> Audited<P, R> {
+    pub fn new(page_turner: P) -> Self {
+        Self {
+            page_turner,
+            log: AuditLog::default(),
+        }
+    }
+
+    /// Returns a handle to the log which remains valid after the page turner is dropped
+    pub fn log(&self) -> AuditLog<R> {
+        self.log.clone()
+    }
+
+    pub fn into_inner(self) -> P {
+        self.page_turner
+    }
+
+    async fn turn_page_with<I, E, Fut>(
+        &self,
+        request: R,
+        turn_page: impl FnOnce(R) -> Fut,
+    ) -> Result<TurnedPage<I, R>, E>
+    where
+        R: Clone,
+        Fut: std::future::Future<Output = Result<TurnedPage<I, R>, E>>,
+    {
+        let started_after = self.log.start();
+        let start = Instant::now();
+        let result = turn_page(request.clone()).await;
+        self.log
+            .record(request, started_after, start.elapsed(), &result);
+
+        result
+    }
+}
+
+impl<P, R> Clone for Audited<P, R>
+where
+    P: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            page_turner: self.page_turner.clone(),
+            log: self.log.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, R> crate::mt::PageTurner<R> for Audited<P, R>
+where
+    P: crate::mt::PageTurner<R>,
+    R: Send + Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |request| self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, R> crate::local::PageTurner<R> for Audited<P, R>
+where
+    P: crate::local::PageTurner<R>,
+    R: Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |request| self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+/// A handle to records of an [`Audited`] page turner. Cloning is cheap and all clones observe the
+/// same log.
+#[derive(Debug)]
+pub struct AuditLog<R>(Arc<Mutex<AuditState<R>>>);
+
+#[derive(Debug)]
+struct AuditState<R> {
+    started: Option<Instant>,
+    pages: Vec<PageAudit<R>>,
+    cursor: Option<R>,
+}
+
+impl<R> AuditLog<R> {
+    /// Returns a snapshot of the log, it can be taken at any point of the run
+    pub fn summary(&self) -> AuditSummary<R>
+    where
+        R: Clone,
+    {
+        let state = self.lock();
+
+        AuditSummary {
+            pages: state.pages.clone(),
+            cursor: state.cursor.clone(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, AuditState<R>> {
+        lock_unpoisoned(&self.0)
+    }
+
+    /// Returns the time passed since the first request of the log
+    fn start(&self) -> Duration {
+        self.lock()
+            .started
+            .get_or_insert_with(Instant::now)
+            .elapsed()
+    }
+
+    fn record<I, E>(
+        &self,
+        request: R,
+        started_after: Duration,
+        latency: Duration,
+        result: &Result<TurnedPage<I, R>, E>,
+    ) where
+        R: Clone,
+    {
+        let (outcome, cursor) = match result {
+            Ok(TurnedPage {
+                next_request: Some(next_request),
+                ..
+            }) => (PageOutcome::Ok, Some(next_request.clone())),
+            Ok(TurnedPage {
+                next_request: None, ..
+            }) => (PageOutcome::Last, None),
+            Err(_) => (PageOutcome::Error, Some(request.clone())),
+        };
+
+        let mut state = self.lock();
+        state.cursor = cursor;
+        state.pages.push(PageAudit {
+            request,
+            started_after,
+            latency,
+            outcome,
+        });
+    }
+}
+
+impl<R> Clone for AuditLog<R> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<R> Default for AuditLog<R> {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(AuditState {
+            started: None,
+            pages: Vec::new(),
+            cursor: None,
+        })))
+    }
+}
+
+/// A record of a single `turn_page` call
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageAudit<R> {
+    pub request: R,
+    /// The time passed between the first request of the log and this one
+    pub started_after: Duration,
+    pub latency: Duration,
+    pub outcome: PageOutcome,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PageOutcome {
+    /// The page has the next request
+    Ok,
+    /// The page is the last one
+    Last,
+    Error,
+}
+
+/// A snapshot of an [`AuditLog`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuditSummary<R> {
+    /// Records of all requests in the order of their completion
+    pub pages: Vec<PageAudit<R>>,
+    /// The request to resume the pagination from. It's the next request of the last completed
+    /// page or the request of the last failed one, `None` after the last page. With concurrent
+    /// streams it only reflects the request completed last.
+    pub cursor: Option<R>,
+}
+
+impl<R> AuditSummary<R> {
+    pub fn requests_issued(&self) -> usize {
+        self.pages.len()
+    }
+
+    pub fn errors(&self) -> usize {
+        self.pages
+            .iter()
+            .filter(|page| page.outcome == PageOutcome::Error)
+            .count()
+    }
+
+    /// The amount of requests that repeated an earlier failed request
+    pub fn retries(&self) -> usize
+    where
+        R: PartialEq,
+    {
+        self.pages
+            .iter()
+            .enumerate()
+            .filter(|(i, page)| {
+                self.pages[..*i].iter().any(|earlier| {
+                    earlier.outcome == PageOutcome::Error && earlier.request == page.request
+                })
+            })
+            .count()
+    }
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use crate::retry::{Retries, Retry};
+    use crate::test_utils::*;
+    use futures::TryStreamExt;
+
+    #[tokio::test]
+    async fn records_retries_and_cursor() {
+        let mut blog = BlogClient::new(4);
+        blog.set_error(2);
+
+        let audited = Audited::new(blog);
+        let log = audited.log();
+        let blog = Retry::new(audited, Retries::new(2));
+
+        let result = blog
+            .pages(GetContentRequest { page: 0 })
+            .try_collect::<Vec<_>>()
+            .await;
+        assert!(result.is_err());
+
+        let summary = log.summary();
+        let outcomes: Vec<_> = summary
+            .pages
+            .iter()
+            .map(|page| (page.request.page, page.outcome))
+            .collect();
+
+        assert_eq!(
+            outcomes,
+            [
+                (0, PageOutcome::Ok),
+                (1, PageOutcome::Ok),
+                (2, PageOutcome::Error),
+                (2, PageOutcome::Error),
+                (2, PageOutcome::Error),
+            ]
+        );
+        assert_eq!(summary.requests_issued(), 5);
+        assert_eq!(summary.errors(), 3);
+        assert_eq!(summary.retries(), 2);
+        assert_eq!(summary.cursor, Some(GetContentRequest { page: 2 }));
+        assert!(summary
+            .pages
+            .windows(2)
+            .all(|pages| pages[0].started_after <= pages[1].started_after));
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    #[test]
+    fn summary_roundtrips_through_serde() {
+        let summary = AuditSummary {
+            pages: vec![PageAudit {
+                request: 3,
+                started_after: Duration::from_millis(5),
+                latency: Duration::from_millis(20),
+                outcome: PageOutcome::Error,
+            }],
+            cursor: Some(3),
+        };
+
+        let json = serde_json::to_string(&summary).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AuditSummary<usize>>(&json).unwrap(),
+            summary
+        );
+
+        let json = serde_json::to_string(&Limit::Pages(3)).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Limit>(&json).unwrap(),
+            Limit::Pages(3)
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! Best-effort pagination that doesn't stop on page errors.
+//!
+//! By default any page error ends a pages stream. Batch jobs that would rather query everything
+//! they can may wrap a page turner into [`BestEffort`]. It records each failed request along with
+//! its error into an [`ErrorLog`], yields an empty page in place of the failed one and proceeds
+//! with [`RequestAhead::next_request`]. Take the [`ErrorReport`] from the log once the stream
+//! completes.
+//!
+//! Because pages after a failed one are queried blindly ensure that the stream ends at some point,
+//! i.e. with [`BestEffort::max_errors`] or [`crate::Limit::Pages`].
+//!
+//! ```
+//! use page_turner::best_effort::BestEffort;
+//! use page_turner::prelude::*;
+//!
+//! #[derive(Debug, Clone, PartialEq)]
+//! struct Page(usize);
+//!
+//! impl RequestAhead for Page {
+//!     fn next_request(&self) -> Self {
+//!         Page(self.0 + 1)
+//!     }
+//! }
+//!
+//! struct Api;
+//!
+//! impl PageTurner<Page> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = String;
+//!
+//!     async fn turn_page(&self, Page(page): Page) -> TurnedPageResult<Self, Page> {
+//!         match page {
+//!             2 => Err(format!("page {page} is corrupted")),
+//!             _ => Ok(TurnedPage::new(vec![page], (page < 4).then_some(Page(page + 1)))),
+//!         }
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let api = BestEffort::new(Api);
+//! let log = api.log();
+//!
+//! let items: Vec<_> = api.pages(Page(0)).items().try_collect().await.unwrap();
+//! assert_eq!(items, [0, 1, 3, 4]);
+//!
+//! let report = log.take_report();
+//! assert_eq!(report.failures.len(), 1);
+//! assert_eq!(report.failures[0].request, Page(2));
+//! # }
+//! ```
+
+use crate::internal::lock_unpoisoned;
+use crate::{RequestAhead, TurnedPage};
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+/// A page turner that records page errors instead of returning them. See the [module
+/// docs](self).
+#[derive(Debug)]
+pub struct BestEffort<P, R, E> {
+    page_turner: P,
+    log: ErrorLog<R, E>,
+    max_errors: Option<usize>,
+}
+
+impl<P, R, E> BestEffort<P, R, E> {
+    pub fn new(page_turner: P) -> Self {
+        Self {
+            page_turner,
+            log: ErrorLog::default(),
+            max_errors: None,
+        }
+    }
+
+    /// Returns errors as usual once `max_errors` are recorded, unlimited by default
+    pub fn max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = Some(max_errors);
+        self
+    }
+
+    /// Returns a handle to the log which remains valid after the page turner is dropped
+    pub fn log(&self) -> ErrorLog<R, E> {
+        self.log.clone()
+    }
+
+    pub fn into_inner(self) -> P {
+        self.page_turner
+    }
+
+    async fn turn_page_with<I, Fut>(
+        &self,
+        request: R,
+        turn_page: impl FnOnce(R) -> Fut,
+    ) -> Result<TurnedPage<I, R>, E>
+    where
+        R: RequestAhead + Clone,
+        I: Default,
+        Fut: Future<Output = Result<TurnedPage<I, R>, E>>,
+    {
+        let error = match turn_page(request.clone()).await {
+            Ok(page) => return Ok(page),
+            Err(e) => e,
+        };
+
+        let mut failures = self.log.lock();
+        if self
+            .max_errors
+            .is_some_and(|max_errors| failures.len() >= max_errors)
+        {
+            return Err(error);
+        }
+
+        let next_request = request.next_request();
+        failures.push(PageFailure { request, error });
+
+        Ok(TurnedPage::next(I::default(), next_request))
+    }
+}
+
+impl<P, R, E> Clone for BestEffort<P, R, E>
+where
+    P: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            page_turner: self.page_turner.clone(),
+            log: self.log.clone(),
+            max_errors: self.max_errors,
+        }
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, R> crate::mt::PageTurner<R> for BestEffort<P, R, P::PageError>
+where
+    P: crate::mt::PageTurner<R>,
+    P::PageItems: Default,
+    R: Send + RequestAhead + Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |request| self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, R> crate::local::PageTurner<R> for BestEffort<P, R, P::PageError>
+where
+    P: crate::local::PageTurner<R>,
+    P::PageItems: Default,
+    R: RequestAhead + Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |request| self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+/// A handle to failures recorded by a [`BestEffort`] page turner. Cloning is cheap and all clones
+/// observe the same log.
+#[derive(Debug)]
+pub struct ErrorLog<R, E>(Arc<Mutex<Vec<PageFailure<R, E>>>>);
+
+impl<R, E> ErrorLog<R, E> {
+    /// Takes all failures recorded so far out of the log
+    pub fn take_report(&self) -> ErrorReport<R, E> {
+        ErrorReport {
+            failures: std::mem::take(&mut *self.lock()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<PageFailure<R, E>>> {
+        lock_unpoisoned(&self.0)
+    }
+}
+
+impl<R, E> Clone for ErrorLog<R, E> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<R, E> Default for ErrorLog<R, E> {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(Vec::new())))
+    }
+}
+
+/// A failed request with its error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageFailure<R, E> {
+    pub request: R,
+    pub error: E,
+}
+
+/// Failures of a best-effort pagination run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorReport<R, E> {
+    /// Failures in the order of their completion
+    pub failures: Vec<PageFailure<R, E>>,
+}
+
+impl<R, E> ErrorReport<R, E> {
+    pub fn is_empty(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use crate::test_utils::*;
+    use futures::TryStreamExt;
+
+    #[tokio::test]
+    async fn records_errors_and_continues() {
+        let mut blog = BlogClient::new(6);
+        blog.set_error(1);
+        blog.set_error_with_msg(4, "Another error");
+
+        let blog = BestEffort::new(blog);
+        let log = blog.log();
+
+        let records: Vec<_> = blog
+            .pages(GetContentRequest { page: 0 })
+            .items()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(
+            records,
+            [BlogRecord(0), BlogRecord(2), BlogRecord(3), BlogRecord(5)]
+        );
+
+        let report = log.take_report();
+        let failures: Vec<_> = report
+            .failures
+            .iter()
+            .map(|failure| (failure.request.page, failure.error.as_str()))
+            .collect();
+        assert_eq!(failures, [(1, "Custom error"), (4, "Another error")]);
+        assert!(log.take_report().is_empty());
+
+        let blog = blog.max_errors(1);
+        let result = blog
+            .pages(GetContentRequest { page: 0 })
+            .try_collect::<Vec<_>>()
+            .await;
+        assert_eq!(result, Err("Another error".to_owned()));
+        assert_eq!(log.take_report().failures.len(), 1);
+    }
+}
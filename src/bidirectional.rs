@@ -0,0 +1,138 @@
+//! Backward and bidirectional pagination.
+//!
+//! Some APIs, i.e. chat histories, return a cursor to the previous page along with the next one
+//! and expect clients to page backwards from "now". Return pages built with
+//! [`TurnedPage::with_prev`] from `turn_page` and:
+//!
+//! - `PageTurner::pages` queries pages forward as usual,
+//! - `PageTurner::pages_backward` follows previous requests instead of next ones,
+//! - `PageTurner::pages_bidirectional` allows to switch the direction at any time, e.g. to load
+//!   older messages on scroll up and newer messages on scroll down.
+//!
+//! ```
+//! use page_turner::bidirectional::WithPrev;
+//! use page_turner::prelude::*;
+//!
+//! /// Returns 3 messages per page, the request is the id of the first message
+//! struct Chat;
+//!
+//! impl PageTurner<usize> for Chat {
+//!     type PageItems = WithPrev<Vec<usize>, usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, first: usize) -> TurnedPageResult<Self, usize> {
+//!         let messages = (first..10.min(first + 3)).collect();
+//!         let next = (first + 3 < 10).then_some(first + 3);
+//!         Ok(TurnedPage::with_prev(messages, next, first.checked_sub(3)))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let history: Vec<_> = Chat.pages_backward(6).try_collect().await.unwrap();
+//! assert_eq!(history, [vec![6, 7, 8], vec![3, 4, 5], vec![0, 1, 2]]);
+//!
+//! let mut pages = Chat.pages_bidirectional(3);
+//! assert_eq!(pages.next_page().await, Some(Ok(vec![3, 4, 5])));
+//! assert_eq!(pages.prev_page().await, Some(Ok(vec![0, 1, 2])));
+//! assert_eq!(pages.prev_page().await, None);
+//! assert_eq!(pages.next_page().await, Some(Ok(vec![6, 7, 8])));
+//! # }
+//! ```
+
+use crate::TurnedPage;
+use std::future::Future;
+
+/// Page items along with an optional request to query the previous page. See the [module
+/// docs](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WithPrev<I, R> {
+    pub items: I,
+    pub prev_request: Option<R>,
+}
+
+impl<I, R> TurnedPage<WithPrev<I, R>, R> {
+    /// Creates a page that can be paginated in both directions
+    pub fn with_prev(items: I, next_request: Option<R>, prev_request: Option<R>) -> Self {
+        Self::new(
+            WithPrev {
+                items,
+                prev_request,
+            },
+            next_request,
+        )
+    }
+
+    /// Splits the page into items, the next request and the previous request
+    pub fn into_parts(self) -> (I, Option<R>, Option<R>) {
+        (self.items.items, self.next_request, self.items.prev_request)
+    }
+}
+
+impl<I, R> IntoIterator for WithPrev<I, R>
+where
+    I: IntoIterator,
+{
+    type Item = I::Item;
+    type IntoIter = I::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+/// A page turner that follows previous requests of the inner page turner instead of next ones.
+/// Returned pages don't have previous requests so they're plain items. See
+/// `PageTurner::pages_backward`.
+#[derive(Debug, Clone)]
+pub struct Backward<P> {
+    page_turner: P,
+}
+
+impl<P> Backward<P> {
+    pub fn new(page_turner: P) -> Self {
+        Self { page_turner }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.page_turner
+    }
+}
+
+async fn turn_page_backward<I, R, E>(
+    page: impl Future<Output = Result<TurnedPage<WithPrev<I, R>, R>, E>>,
+) -> Result<TurnedPage<I, R>, E> {
+    let (items, _, prev_request) = page.await?.into_parts();
+    Ok(TurnedPage::new(items, prev_request))
+}
+
+#[cfg(feature = "mt")]
+impl<P, R, I> crate::mt::PageTurner<R> for Backward<P>
+where
+    P: crate::mt::PageTurner<R, PageItems = WithPrev<I, R>>,
+    R: Send,
+    I: Send,
+{
+    type PageItems = I;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        turn_page_backward(self.page_turner.turn_page(request)).await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, R, I> crate::local::PageTurner<R> for Backward<P>
+where
+    P: crate::local::PageTurner<R, PageItems = WithPrev<I, R>>,
+{
+    type PageItems = I;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        turn_page_backward(self.page_turner.turn_page(request)).await
+    }
+}
@@ -0,0 +1,152 @@
+//! Synchronous iteration over pages streams. See `PagesStream::blocking`.
+//!
+//! CLI tools and scripts around paginated APIs are often synchronous and shouldn't need an async
+//! runtime just to loop over pages. [`BlockingPages`] turns any pages stream into an
+//! [`Iterator`] that blocks the current thread on each page with the [`BlockOn`] executor of your
+//! choice. [`ParkThread`] is a minimal executor that parks the thread until the page is ready, it
+//! works for page turners that don't rely on a runtime, i.e. built on a blocking HTTP client or
+//! on runtime agnostic timers. Implement [`BlockOn`] to drive pages on another executor, e.g. for
+//! `tokio::runtime::Runtime`:
+//!
+//! ```ignore
+//! impl BlockOn for MyRuntime {
+//!     fn block_on<F: Future>(&self, future: F) -> F::Output {
+//!         self.0.block_on(future)
+//!     }
+//! }
+//! ```
+//!
+//! ```
+//! use page_turner::prelude::*;
+//!
+//! struct Api;
+//!
+//! impl PageTurner<usize> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         Ok(TurnedPage::new(vec![page], (page < 2).then_some(page + 1)))
+//!     }
+//! }
+//!
+//! let mut items = Vec::new();
+//! for page in Api.pages(0).blocking() {
+//!     items.extend(page.unwrap());
+//! }
+//!
+//! assert_eq!(items, [0, 1, 2]);
+//! ```
+
+use futures::{Stream, StreamExt};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+/// An executor that runs a future to completion on the current thread
+pub trait BlockOn {
+    fn block_on<F: Future>(&self, future: F) -> F::Output;
+}
+
+/// A [`BlockOn`] executor that parks the current thread until the future is woken up
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParkThread;
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+impl BlockOn for ParkThread {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                // Spurious wakeups only cause an extra poll
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+}
+
+/// An iterator over pages of the inner stream driven by a [`BlockOn`] executor, see
+/// `PagesStream::blocking` and `PagesStream::blocking_on`
+pub struct BlockingPages<S, B> {
+    stream: Pin<Box<S>>,
+    executor: B,
+}
+
+impl<S, B> BlockingPages<S, B> {
+    pub fn new(stream: S, executor: B) -> Self {
+        Self {
+            stream: Box::pin(stream),
+            executor,
+        }
+    }
+}
+
+impl<S, B> Iterator for BlockingPages<S, B>
+where
+    S: Stream,
+    B: BlockOn,
+{
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.executor.block_on(self.stream.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::adapters::slice::{SlicePage, SlicePageTurner};
+    use crate::mt::prelude::*;
+    use std::time::Duration;
+
+    #[test]
+    fn iterates_pages() {
+        let numbers = SlicePageTurner::new((0..10).collect::<Vec<u8>>(), 3)
+            .delay(Duration::from_millis(5))
+            .fail_at(2, 1);
+
+        let mut pages = numbers.pages_ahead(2, Limit::None, SlicePage(0)).blocking();
+        assert_eq!(pages.next(), Some(Ok(vec![0, 1, 2])));
+        assert_eq!(pages.next(), Some(Ok(vec![3, 4, 5])));
+        assert!(pages.next().unwrap().is_err());
+
+        let items: Result<Vec<_>, _> = numbers.pages(SlicePage(0)).items().blocking().collect();
+        assert_eq!(items, Ok((0..10).collect()));
+    }
+
+    #[test]
+    fn parks_until_woken_up() {
+        let (tx, rx) = futures::channel::oneshot::channel();
+        let sender = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            tx.send(42).unwrap();
+        });
+
+        assert_eq!(ParkThread.block_on(rx), Ok(42));
+        sender.join().unwrap();
+    }
+}
@@ -0,0 +1,368 @@
+//! Caching of turned pages.
+//!
+//! Wrap a page turner into [`CachedPageTurner`] to look up pages in a [`PageCache`] before
+//! querying them, i.e. to re-run an analysis over mostly static paginated data without fetching
+//! everything again. Only successfully turned pages are cached. Pages older than the TTL set with
+//! [`CachedPageTurner::with_ttl`] are queried again and replace the cached ones.
+//!
+//! Implement [`PageCache`] to keep pages in a storage of your choice, i.e. Redis or a database
+//! behind an async client, or use the in-memory [`MemoryCache`]. Caches used only with the `local`
+//! flavor can implement [`LocalPageCache`] instead, which doesn't require `Send` futures.
+//!
+//! ```
+//! use page_turner::cache::{CachedPageTurner, MemoryCache};
+//! use page_turner::prelude::*;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use std::time::Duration;
+//!
+//! struct Api {
+//!     calls: AtomicUsize,
+//! }
+//!
+//! impl PageTurner<usize> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         self.calls.fetch_add(1, Ordering::Relaxed);
+//!         Ok(TurnedPage::new(vec![page], (page < 2).then_some(page + 1)))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let api = CachedPageTurner::new(Api { calls: AtomicUsize::new(0) }, MemoryCache::new())
+//!     .with_ttl(Duration::from_secs(60));
+//!
+//! for _ in 0..3 {
+//!     let items: Vec<_> = api.pages(0).items().try_collect().await.unwrap();
+//!     assert_eq!(items, [0, 1, 2]);
+//! }
+//!
+//! assert_eq!(api.into_inner().calls.into_inner(), 3);
+//! # }
+//! ```
+
+use crate::internal::lock_unpoisoned;
+use crate::TurnedPage;
+use futures::future;
+use std::{
+    collections::HashMap,
+    future::Future,
+    hash::Hash,
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+/// A storage of turned pages keyed by their requests. Lookups and writes are awaited by the page
+/// request that needs them, so a remote storage doesn't block the executor.
+pub trait PageCache<R, I> {
+    fn get(&self, request: &R) -> impl Send + Future<Output = Option<CachedPage<I, R>>>;
+
+    fn put(&self, request: &R, page: CachedPage<I, R>) -> impl Send + Future<Output = ()>;
+}
+
+/// A [`PageCache`] for the `local` flavor whose futures don't have to be `Send`, i.e. to keep
+/// pages in an `Rc<RefCell<_>>`. Every [`PageCache`] is also a `LocalPageCache`.
+pub trait LocalPageCache<R, I> {
+    fn get(&self, request: &R) -> impl Future<Output = Option<CachedPage<I, R>>>;
+
+    fn put(&self, request: &R, page: CachedPage<I, R>) -> impl Future<Output = ()>;
+}
+
+impl<R, I, C> LocalPageCache<R, I> for C
+where
+    C: PageCache<R, I>,
+{
+    fn get(&self, request: &R) -> impl Future<Output = Option<CachedPage<I, R>>> {
+        PageCache::get(self, request)
+    }
+
+    fn put(&self, request: &R, page: CachedPage<I, R>) -> impl Future<Output = ()> {
+        PageCache::put(self, request, page)
+    }
+}
+
+/// A turned page along with the time it was cached at
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CachedPage<I, R> {
+    pub page: TurnedPage<I, R>,
+    pub cached_at: SystemTime,
+}
+
+impl<I, R> CachedPage<I, R> {
+    /// Whether the page was cached longer than `ttl` ago
+    pub fn is_expired(&self, ttl: Duration) -> bool {
+        // A page cached "in the future" because of a clock adjustment is considered fresh
+        self.cached_at.elapsed().is_ok_and(|elapsed| elapsed > ttl)
+    }
+}
+
+/// An in-memory [`PageCache`] that never evicts pages
+#[derive(Debug)]
+pub struct MemoryCache<R, I> {
+    pages: Mutex<HashMap<R, CachedPage<I, R>>>,
+}
+
+impl<R, I> MemoryCache<R, I> {
+    pub fn new() -> Self {
+        Self {
+            pages: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Removes all cached pages
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<R, CachedPage<I, R>>> {
+        lock_unpoisoned(&self.pages)
+    }
+}
+
+impl<R, I> Default for MemoryCache<R, I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R, I> PageCache<R, I> for MemoryCache<R, I>
+where
+    R: Send + Eq + Hash + Clone,
+    I: Send + Clone,
+{
+    fn get(&self, request: &R) -> impl Send + Future<Output = Option<CachedPage<I, R>>> {
+        future::ready(self.lock().get(request).cloned())
+    }
+
+    fn put(&self, request: &R, page: CachedPage<I, R>) -> impl Send + Future<Output = ()> {
+        self.lock().insert(request.clone(), page);
+        future::ready(())
+    }
+}
+
+/// A page turner that serves pages from a [`PageCache`] and caches pages turned by the inner page
+/// turner. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct CachedPageTurner<P, C> {
+    page_turner: P,
+    cache: C,
+    ttl: Option<Duration>,
+}
+
+impl<P, C> CachedPageTurner<P, C> {
+    /// Creates a page turner with cached pages that never expire
+    pub fn new(page_turner: P, cache: C) -> Self {
+        Self {
+            page_turner,
+            cache,
+            ttl: None,
+        }
+    }
+
+    /// Queries pages cached longer than `ttl` ago again
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    pub fn cache(&self) -> &C {
+        &self.cache
+    }
+
+    pub fn into_inner(self) -> P {
+        self.page_turner
+    }
+
+    /// Returns the cached page unless it's expired
+    fn fresh<R, I>(&self, cached: Option<CachedPage<I, R>>) -> Option<TurnedPage<I, R>> {
+        cached
+            .filter(|cached| match self.ttl {
+                Some(ttl) => !cached.is_expired(ttl),
+                None => true,
+            })
+            .map(|cached| cached.page)
+    }
+}
+
+// The flavors differ only in the cache trait, the `mt` one requires `Send` futures
+#[cfg(feature = "mt")]
+impl<P, C, R> crate::mt::PageTurner<R> for CachedPageTurner<P, C>
+where
+    P: crate::mt::PageTurner<R>,
+    C: Send + Sync + PageCache<R, P::PageItems>,
+    R: Send + Clone,
+    P::PageItems: Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        if let Some(page) = self.fresh(PageCache::get(&self.cache, &request).await) {
+            return Ok(page);
+        }
+
+        let page = self.page_turner.turn_page(request.clone()).await?;
+        let cached = CachedPage {
+            page: page.clone(),
+            cached_at: SystemTime::now(),
+        };
+        PageCache::put(&self.cache, &request, cached).await;
+
+        Ok(page)
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, C, R> crate::local::PageTurner<R> for CachedPageTurner<P, C>
+where
+    P: crate::local::PageTurner<R>,
+    C: LocalPageCache<R, P::PageItems>,
+    R: Clone,
+    P::PageItems: Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        if let Some(page) = self.fresh(LocalPageCache::get(&self.cache, &request).await) {
+            return Ok(page);
+        }
+
+        let page = self.page_turner.turn_page(request.clone()).await?;
+        let cached = CachedPage {
+            page: page.clone(),
+            cached_at: SystemTime::now(),
+        };
+        LocalPageCache::put(&self.cache, &request, cached).await;
+
+        Ok(page)
+    }
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Counted {
+        calls: AtomicUsize,
+    }
+
+    impl PageTurner<usize> for Counted {
+        type PageItems = Vec<usize>;
+        type PageError = String;
+
+        async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+
+            match page {
+                3 => Err("Page 3 is broken".to_owned()),
+                _ => Ok(TurnedPage::new(vec![page], (page < 4).then_some(page + 1))),
+            }
+        }
+    }
+
+    fn calls<C>(cached: &CachedPageTurner<Counted, C>) -> usize {
+        cached.page_turner.calls.load(Ordering::Relaxed)
+    }
+
+    #[tokio::test]
+    async fn serves_cached_pages() {
+        let cached = CachedPageTurner::new(
+            Counted {
+                calls: AtomicUsize::new(0),
+            },
+            MemoryCache::new(),
+        );
+
+        for _ in 0..2 {
+            let result: Result<Vec<_>, _> = cached.pages(0).items().try_collect().await;
+            assert_eq!(result, Err("Page 3 is broken".to_owned()));
+        }
+        assert_eq!(calls(&cached), 5, "Errors aren't cached");
+
+        let pages: Vec<_> = cached.pages(4).try_collect().await.unwrap();
+        assert_eq!(pages, [vec![4]]);
+        assert_eq!(calls(&cached), 6);
+
+        cached.cache().clear();
+        let _ = cached.turn_page(0).await;
+        assert_eq!(calls(&cached), 7);
+    }
+
+    #[tokio::test]
+    async fn queries_expired_pages() {
+        let cache = MemoryCache::new();
+        let expired = CachedPage {
+            page: TurnedPage::last(vec![100]),
+            cached_at: SystemTime::now() - Duration::from_secs(120),
+        };
+        PageCache::put(&cache, &1, expired).await;
+
+        let cached = CachedPageTurner::new(
+            Counted {
+                calls: AtomicUsize::new(0),
+            },
+            cache,
+        );
+
+        let page = cached.turn_page(1).await.unwrap();
+        assert_eq!(page, TurnedPage::last(vec![100]));
+        assert_eq!(calls(&cached), 0);
+
+        let cached = cached.with_ttl(Duration::from_secs(60));
+        let page = cached.turn_page(1).await.unwrap();
+        assert_eq!(page, TurnedPage::next(vec![1], 2));
+        assert_eq!(calls(&cached), 1);
+
+        let page = cached.turn_page(1).await.unwrap();
+        assert_eq!(page, TurnedPage::next(vec![1], 2));
+        assert_eq!(calls(&cached), 1, "The expired page is replaced");
+    }
+
+    #[cfg(feature = "local")]
+    #[tokio::test]
+    async fn accepts_local_caches() {
+        use std::{cell::RefCell, rc::Rc};
+
+        struct Numbers;
+
+        impl crate::local::PageTurner<usize> for Numbers {
+            type PageItems = Vec<usize>;
+            type PageError = ();
+
+            async fn turn_page(&self, page: usize) -> crate::local::TurnedPageResult<Self, usize> {
+                Ok(TurnedPage::new(vec![page], (page < 2).then_some(page + 1)))
+            }
+        }
+
+        type Page = CachedPage<Vec<usize>, usize>;
+
+        #[derive(Default)]
+        struct RcCache(Rc<RefCell<HashMap<usize, Page>>>);
+
+        impl LocalPageCache<usize, Vec<usize>> for RcCache {
+            async fn get(&self, request: &usize) -> Option<Page> {
+                self.0.borrow().get(request).cloned()
+            }
+
+            async fn put(&self, request: &usize, page: Page) {
+                self.0.borrow_mut().insert(*request, page);
+            }
+        }
+
+        let cached = CachedPageTurner::new(Numbers, RcCache::default());
+        let page = crate::local::PageTurner::turn_page(&cached, 1)
+            .await
+            .unwrap();
+        assert_eq!(page, TurnedPage::next(vec![1], 2));
+        assert_eq!(cached.cache().0.borrow().len(), 1);
+    }
+}
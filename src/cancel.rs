@@ -0,0 +1,234 @@
+//! Graceful cancellation of pages streams. See `PagesStream::until_cancelled`.
+//!
+//! Dropping a stream in the middle of a `pages_ahead` window abandons in-flight requests and
+//! leaves no trace whether it was a shutdown or a bug. A stream wrapped with
+//! `PagesStream::until_cancelled` checks the token before yielding every page and once the token
+//! is cancelled it stops scheduling new requests and ends with `None`. Check
+//! [`Cancellation::is_cancelled`] afterwards to tell a graceful shutdown from the end of the
+//! pagination.
+//!
+//! The crate provides its own [`CancelToken`] but any token can be used by implementing
+//! [`Cancellation`], e.g. for `tokio_util::sync::CancellationToken`:
+//!
+//! ```ignore
+//! impl Cancellation for MyToken {
+//!     type Cancelled = WaitForCancellationFutureOwned;
+//!
+//!     fn cancelled(&self) -> Self::Cancelled {
+//!         self.0.clone().cancelled_owned()
+//!     }
+//!
+//!     fn is_cancelled(&self) -> bool {
+//!         self.0.is_cancelled()
+//!     }
+//! }
+//! ```
+//!
+//! ```
+//! use page_turner::cancel::{CancelToken, Cancellation};
+//! use page_turner::prelude::*;
+//!
+//! struct Api;
+//!
+//! impl PageTurner<usize> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         Ok(TurnedPage::next(vec![page], page + 1))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let token = CancelToken::new();
+//! let mut pages = std::pin::pin!(Api.pages(0).until_cancelled(token.clone()));
+//!
+//! assert_eq!(pages.try_next().await, Ok(Some(vec![0])));
+//! token.cancel();
+//! assert_eq!(pages.try_next().await, Ok(None));
+//! assert!(token.is_cancelled());
+//! # }
+//! ```
+
+use crate::internal::{async_iterator_impl, lock_unpoisoned};
+use futures::Stream;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// A token signalling that a stream must end. Implement it for a token of your runtime to cancel
+/// streams with it.
+pub trait Cancellation {
+    type Cancelled: Future<Output = ()>;
+
+    /// Returns a future that completes once the token is cancelled
+    fn cancelled(&self) -> Self::Cancelled;
+
+    fn is_cancelled(&self) -> bool;
+}
+
+/// A runtime agnostic cancellation token. Cloning is cheap and cancelling any clone cancels all
+/// of them.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<Mutex<CancelState>>);
+
+#[derive(Debug, Default)]
+struct CancelState {
+    cancelled: bool,
+    wakers: Vec<Waker>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token and wakes up all streams waiting for it
+    pub fn cancel(&self) {
+        let wakers = {
+            let mut state = self.lock();
+            state.cancelled = true;
+            std::mem::take(&mut state.wakers)
+        };
+
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CancelState> {
+        lock_unpoisoned(&self.0)
+    }
+}
+
+impl Cancellation for CancelToken {
+    type Cancelled = Cancelled;
+
+    fn cancelled(&self) -> Cancelled {
+        Cancelled(self.clone())
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.lock().cancelled
+    }
+}
+
+/// A future returned by [`CancelToken::cancelled`](Cancellation::cancelled)
+#[derive(Debug)]
+pub struct Cancelled(CancelToken);
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.0.lock();
+        if state.cancelled {
+            return Poll::Ready(());
+        }
+
+        if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            state.wakers.push(cx.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
+
+/// A stream returned by `PagesStream::until_cancelled`
+pub struct Cancellable<S, C: Cancellation> {
+    stream: Option<Pin<Box<S>>>,
+    cancelled: Pin<Box<C::Cancelled>>,
+}
+
+impl<S, C: Cancellation> Cancellable<S, C> {
+    #[cfg(any(feature = "mt", feature = "local"))]
+    pub(crate) fn new(stream: S, token: &C) -> Self {
+        Self {
+            stream: Some(Box::pin(stream)),
+            cancelled: Box::pin(token.cancelled()),
+        }
+    }
+}
+
+// The inner stream and the future are boxed
+impl<S, C: Cancellation> Unpin for Cancellable<S, C> {}
+
+impl<S, C> Stream for Cancellable<S, C>
+where
+    S: Stream,
+    C: Cancellation,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let Some(stream) = this.stream.as_mut() else {
+            return Poll::Ready(None);
+        };
+
+        if this.cancelled.as_mut().poll(cx).is_ready() {
+            // Dropping the stream stops scheduling of new requests
+            this.stream = None;
+            return Poll::Ready(None);
+        }
+
+        let item = std::task::ready!(stream.as_mut().poll_next(cx));
+        if item.is_none() {
+            this.stream = None;
+        }
+
+        Poll::Ready(item)
+    }
+}
+
+async_iterator_impl!([S, C: Cancellation] Cancellable<S, C>);
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::adapters::slice::{SlicePage, SlicePageTurner};
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn cancels_pages_ahead() {
+        let token = CancelToken::new();
+        let numbers = SlicePageTurner::new((0..100).collect::<Vec<u8>>(), 3);
+
+        let mut pages = std::pin::pin!(numbers
+            .pages_ahead(4, Limit::None, SlicePage(0))
+            .until_cancelled(token.clone()));
+
+        assert!(pages.try_next().await.unwrap().is_some());
+        assert!(pages.try_next().await.unwrap().is_some());
+
+        token.cancel();
+        assert_eq!(pages.try_next().await, Ok(None));
+        assert_eq!(pages.try_next().await, Ok(None));
+        assert!(token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn wakes_up_pending_stream() {
+        let token = CancelToken::new();
+        let canceller = token.clone();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            canceller.cancel();
+        });
+
+        let pages: Vec<_> = futures::stream::pending::<Result<Vec<usize>, ()>>()
+            .until_cancelled(token)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert!(pages.is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+//! Pagination of dependent queries as one stream. See `PageTurner::pages_chain`.
+//!
+//! Sync workflows often paginate one query and then another one that depends on how the first one
+//! ended, i.e. list pages of active projects and then continue with archived ones starting after
+//! the last active project. `PageTurner::pages_chain` queries pages of the first request and once
+//! they end passes a [`ChainSummary`] of them to a closure that produces the next request to
+//! paginate, possibly of another type. Pages of both queries are yielded by the same stream.
+//!
+//! ```
+//! use page_turner::chain::ChainSummary;
+//! use page_turner::prelude::*;
+//!
+//! /// Lists active projects by page
+//! struct Projects;
+//!
+//! struct ListArchived {
+//!     after: usize,
+//! }
+//!
+//! impl PageTurner<usize> for Projects {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         Ok(TurnedPage::new(vec![page], (page < 1).then_some(page + 1)))
+//!     }
+//! }
+//!
+//! impl PageTurner<ListArchived> for Projects {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, request: ListArchived) -> TurnedPageResult<Self, ListArchived> {
+//!         Ok(TurnedPage::last(vec![request.after + 100]))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let projects: Vec<_> = Projects
+//!     .pages_chain(0, |summary: ChainSummary<usize>| {
+//!         Some(ListArchived {
+//!             after: summary.last_request,
+//!         })
+//!     })
+//!     .items()
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//!
+//! assert_eq!(projects, [0, 1, 101]);
+//! # }
+//! ```
+
+/// A summary of the first query of a chain passed to the closure producing the next query
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChainSummary<R> {
+    /// The amount of pages of the first query
+    pub pages: usize,
+    /// The amount of items in pages of the first query
+    pub items: usize,
+    /// The request of the last page of the first query
+    pub last_request: R,
+}
+
+pub(crate) enum ChainState<R, R2, F> {
+    First {
+        request: R,
+        pages: usize,
+        items: usize,
+        next_query: F,
+    },
+    Second(R2),
+    Done,
+}
+
+impl<R, R2, F> ChainState<R, R2, F> {
+    pub(crate) fn new(request: R, next_query: F) -> Self {
+        Self::First {
+            request,
+            pages: 0,
+            items: 0,
+            next_query,
+        }
+    }
+}
@@ -0,0 +1,309 @@
+//! Page turners that transform pages and errors of other page turners, see
+//! `PageTurner::map_items`, `PageTurner::map_err` and `PageTurner::and_then_page`, or fall back
+//! to another page turner, see `PageTurner::or_else`.
+//!
+//! An SDK can expose a domain typed page turner built from a raw transport level one without
+//! writing a new trait impl for each request type.
+//!
+//! ```
+//! use page_turner::prelude::*;
+//!
+//! /// Returns raw JSON lines
+//! struct Transport;
+//!
+//! impl PageTurner<usize> for Transport {
+//!     type PageItems = Vec<String>;
+//!     type PageError = std::io::Error;
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         let lines = vec![format!("{page}"), format!("{}", page * 10)];
+//!         Ok(TurnedPage::new(lines, (page < 2).then_some(page + 1)))
+//!     }
+//! }
+//!
+//! #[derive(Debug)]
+//! enum SdkError {
+//!     Io(std::io::Error),
+//!     Parse(std::num::ParseIntError),
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let sdk = Transport.map_err(SdkError::Io).and_then_page(|lines: Vec<String>| {
+//!     lines
+//!         .iter()
+//!         .map(|line| line.parse::<u32>())
+//!         .collect::<Result<Vec<_>, _>>()
+//!         .map_err(SdkError::Parse)
+//! });
+//!
+//! let numbers: Vec<_> = sdk.pages(0).items().try_collect().await.unwrap();
+//! assert_eq!(numbers, [0, 0, 1, 10, 2, 20]);
+//! # }
+//! ```
+
+use crate::TurnedPage;
+use std::future::Future;
+
+macro_rules! wrapper_def {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone)]
+        pub struct $name<P, F> {
+            page_turner: P,
+            f: F,
+        }
+
+        impl<P, F> $name<P, F> {
+            pub fn new(page_turner: P, f: F) -> Self {
+                Self { page_turner, f }
+            }
+
+            pub fn into_inner(self) -> P {
+                self.page_turner
+            }
+        }
+    };
+}
+
+wrapper_def!(
+    /// A page turner that maps page items of the inner page turner, see `PageTurner::map_items`
+    MapItems
+);
+
+wrapper_def!(
+    /// A page turner that maps errors of the inner page turner, see `PageTurner::map_err`
+    MapErr
+);
+
+wrapper_def!(
+    /// A page turner that maps page items of the inner page turner with a fallible function, see
+    /// `PageTurner::and_then_page`
+    AndThenPage
+);
+
+impl<P, F> MapItems<P, F> {
+    async fn turn_page_with<R, I, O, E>(
+        &self,
+        page: impl Future<Output = Result<TurnedPage<I, R>, E>>,
+    ) -> Result<TurnedPage<O, R>, E>
+    where
+        F: Fn(I) -> O,
+    {
+        let TurnedPage {
+            items,
+            next_request,
+        } = page.await?;
+
+        Ok(TurnedPage::new((self.f)(items), next_request))
+    }
+}
+
+impl<P, F> MapErr<P, F> {
+    async fn turn_page_with<R, I, E, O>(
+        &self,
+        page: impl Future<Output = Result<TurnedPage<I, R>, E>>,
+    ) -> Result<TurnedPage<I, R>, O>
+    where
+        F: Fn(E) -> O,
+    {
+        page.await.map_err(&self.f)
+    }
+}
+
+impl<P, F> AndThenPage<P, F> {
+    async fn turn_page_with<R, I, O, E>(
+        &self,
+        page: impl Future<Output = Result<TurnedPage<I, R>, E>>,
+    ) -> Result<TurnedPage<O, R>, E>
+    where
+        F: Fn(I) -> Result<O, E>,
+    {
+        let TurnedPage {
+            items,
+            next_request,
+        } = page.await?;
+
+        Ok(TurnedPage::new((self.f)(items)?, next_request))
+    }
+}
+
+/// A page turner that sends failed requests of the inner page turner to the fallback page turner,
+/// see `PageTurner::or_else`
+#[derive(Debug, Clone)]
+pub struct OrElse<P, Q> {
+    page_turner: P,
+    fallback: Q,
+}
+
+impl<P, Q> OrElse<P, Q> {
+    pub fn new(page_turner: P, fallback: Q) -> Self {
+        Self {
+            page_turner,
+            fallback,
+        }
+    }
+
+    pub fn fallback(&self) -> &Q {
+        &self.fallback
+    }
+
+    pub fn into_inner(self) -> (P, Q) {
+        (self.page_turner, self.fallback)
+    }
+
+    async fn turn_page_with<R, T, E, Fut, FallbackFut>(
+        &self,
+        request: R,
+        turn_page: impl FnOnce(R) -> Fut,
+        turn_fallback_page: impl FnOnce(R) -> FallbackFut,
+    ) -> Result<T, E>
+    where
+        R: Clone,
+        Fut: Future<Output = Result<T, E>>,
+        FallbackFut: Future<Output = Result<T, E>>,
+    {
+        match turn_page(request.clone()).await {
+            Ok(page) => Ok(page),
+            Err(_) => turn_fallback_page(request).await,
+        }
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, F, R, O> crate::mt::PageTurner<R> for MapItems<P, F>
+where
+    P: crate::mt::PageTurner<R>,
+    F: Send + Sync + Fn(P::PageItems) -> O,
+    R: Send,
+    O: Send,
+{
+    type PageItems = O;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        self.turn_page_with(self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, F, R, O> crate::local::PageTurner<R> for MapItems<P, F>
+where
+    P: crate::local::PageTurner<R>,
+    F: Fn(P::PageItems) -> O,
+{
+    type PageItems = O;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        self.turn_page_with(self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, F, R, O> crate::mt::PageTurner<R> for MapErr<P, F>
+where
+    P: crate::mt::PageTurner<R>,
+    F: Send + Sync + Fn(P::PageError) -> O,
+    R: Send,
+    O: Send,
+{
+    type PageItems = P::PageItems;
+    type PageError = O;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        self.turn_page_with(self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, F, R, O> crate::local::PageTurner<R> for MapErr<P, F>
+where
+    P: crate::local::PageTurner<R>,
+    F: Fn(P::PageError) -> O,
+{
+    type PageItems = P::PageItems;
+    type PageError = O;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        self.turn_page_with(self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, F, R, O> crate::mt::PageTurner<R> for AndThenPage<P, F>
+where
+    P: crate::mt::PageTurner<R>,
+    F: Send + Sync + Fn(P::PageItems) -> Result<O, P::PageError>,
+    R: Send,
+    O: Send,
+{
+    type PageItems = O;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        self.turn_page_with(self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, F, R, O> crate::local::PageTurner<R> for AndThenPage<P, F>
+where
+    P: crate::local::PageTurner<R>,
+    F: Fn(P::PageItems) -> Result<O, P::PageError>,
+{
+    type PageItems = O;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        self.turn_page_with(self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, Q, R> crate::mt::PageTurner<R> for OrElse<P, Q>
+where
+    P: crate::mt::PageTurner<R>,
+    Q: crate::mt::PageTurner<R, PageItems = P::PageItems, PageError = P::PageError>,
+    R: Send + Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        self.turn_page_with(
+            request,
+            |request| self.page_turner.turn_page(request),
+            |request| self.fallback.turn_page(request),
+        )
+        .await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, Q, R> crate::local::PageTurner<R> for OrElse<P, Q>
+where
+    P: crate::local::PageTurner<R>,
+    Q: crate::local::PageTurner<R, PageItems = P::PageItems, PageError = P::PageError>,
+    R: Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        self.turn_page_with(
+            request,
+            |request| self.page_turner.turn_page(request),
+            |request| self.fallback.turn_page(request),
+        )
+        .await
+    }
+}
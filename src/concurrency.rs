@@ -0,0 +1,325 @@
+//! Per-class concurrency limits for streams that mix endpoints with different costs.
+//!
+//! [`ConcurrencyClasses`] tags every request with a class and lets at most the configured amount
+//! of requests of each class be in flight, i.e. 2 "heavy" export pages but any amount of "light"
+//! listing pages allowed by `pages_ahead*` methods. Requests that wait for their class occupy
+//! lookahead slots of the stream, so pick `requests_ahead_count` with the limits in mind. Limits
+//! are shared by all clones of the page turner and by all streams using them.
+//!
+//! ```
+//! use page_turner::concurrency::ConcurrencyClasses;
+//! use page_turner::prelude::*;
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+//! enum Cost {
+//!     Heavy,
+//!     Light,
+//! }
+//!
+//! #[derive(Clone)]
+//! struct Page(usize);
+//!
+//! impl RequestAhead for Page {
+//!     fn next_request(&self) -> Self {
+//!         Page(self.0 + 1)
+//!     }
+//! }
+//!
+//! struct Api;
+//!
+//! impl PageTurner<Page> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, Page(page): Page) -> TurnedPageResult<Self, Page> {
+//!         Ok(TurnedPage::new(vec![page], (page < 9).then_some(Page(page + 1))))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let api = ConcurrencyClasses::new(Api, |Page(page): &Page| match page % 5 {
+//!     0 => Cost::Heavy,
+//!     _ => Cost::Light,
+//! })
+//! .limit(Cost::Heavy, 1);
+//!
+//! let items: Vec<_> = api
+//!     .pages_ahead(4, Limit::None, Page(0))
+//!     .items()
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//!
+//! assert_eq!(items, (0..10).collect::<Vec<_>>());
+//! # }
+//! ```
+
+use crate::internal::lock_unpoisoned;
+use std::{
+    collections::HashMap,
+    future::Future,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    task::{Poll, Waker},
+};
+
+/// A page turner that limits the amount of in-flight requests per class. See the [module
+/// docs](self).
+#[derive(Debug)]
+pub struct ConcurrencyClasses<P, K, F> {
+    page_turner: P,
+    classify: F,
+    limits: Arc<Mutex<ClassLimits<K>>>,
+}
+
+#[derive(Debug)]
+struct ClassLimits<K> {
+    max_in_flight: HashMap<K, usize>,
+    in_flight: HashMap<K, usize>,
+    waiters: HashMap<K, Vec<Waker>>,
+}
+
+impl<P, K, F> ConcurrencyClasses<P, K, F> {
+    /// Wraps the page turner. `classify` returns the class of a request, classes without a
+    /// [`ConcurrencyClasses::limit`] are unlimited.
+    pub fn new(page_turner: P, classify: F) -> Self {
+        Self {
+            page_turner,
+            classify,
+            limits: Arc::new(Mutex::new(ClassLimits {
+                max_in_flight: HashMap::new(),
+                in_flight: HashMap::new(),
+                waiters: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Allows at most `max_in_flight` requests of the `class` at once. `0` is treated as `1`.
+    pub fn limit(self, class: K, max_in_flight: usize) -> Self
+    where
+        K: Eq + Hash,
+    {
+        self.lock()
+            .max_in_flight
+            .insert(class, max_in_flight.max(1));
+        self
+    }
+
+    /// The amount of requests of the `class` being queried right now
+    pub fn in_flight(&self, class: &K) -> usize
+    where
+        K: Eq + Hash,
+    {
+        self.lock().in_flight.get(class).copied().unwrap_or(0)
+    }
+
+    pub fn into_inner(self) -> P {
+        self.page_turner
+    }
+
+    async fn turn_page_with<R, Fut>(
+        &self,
+        request: R,
+        turn_page: impl FnOnce(R) -> Fut,
+    ) -> Fut::Output
+    where
+        K: Clone + Eq + Hash,
+        F: Fn(&R) -> K,
+        Fut: Future,
+    {
+        let class = (self.classify)(&request);
+        let _permit = self.acquire(class).await;
+        turn_page(request).await
+    }
+
+    async fn acquire(&self, class: K) -> Permit<K>
+    where
+        K: Clone + Eq + Hash,
+    {
+        futures::future::poll_fn(|cx| {
+            let mut limits = self.lock();
+            let in_flight = limits.in_flight.get(&class).copied().unwrap_or(0);
+            let max_in_flight = limits.max_in_flight.get(&class).copied();
+
+            if max_in_flight.is_some_and(|max_in_flight| in_flight >= max_in_flight) {
+                // A request polled again before its class was released is already registered
+                let waiters = limits.waiters.entry(class.clone()).or_default();
+                if !waiters.iter().any(|waker| waker.will_wake(cx.waker())) {
+                    waiters.push(cx.waker().clone());
+                }
+
+                return Poll::Pending;
+            }
+
+            *limits.in_flight.entry(class.clone()).or_default() += 1;
+            Poll::Ready(())
+        })
+        .await;
+
+        Permit {
+            class,
+            limits: Arc::clone(&self.limits),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ClassLimits<K>> {
+        lock_unpoisoned(&self.limits)
+    }
+}
+
+impl<P, K, F> Clone for ConcurrencyClasses<P, K, F>
+where
+    P: Clone,
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            page_turner: self.page_turner.clone(),
+            classify: self.classify.clone(),
+            limits: Arc::clone(&self.limits),
+        }
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, R, K, F> crate::mt::PageTurner<R> for ConcurrencyClasses<P, K, F>
+where
+    P: crate::mt::PageTurner<R>,
+    R: Send,
+    K: Send + Sync + Clone + Eq + Hash,
+    F: Send + Sync + Fn(&R) -> K,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |request| self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, R, K, F> crate::local::PageTurner<R> for ConcurrencyClasses<P, K, F>
+where
+    P: crate::local::PageTurner<R>,
+    K: Clone + Eq + Hash,
+    F: Fn(&R) -> K,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |request| self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+/// Releases a slot of the class on drop, including when the page future is cancelled
+struct Permit<K: Eq + Hash> {
+    class: K,
+    limits: Arc<Mutex<ClassLimits<K>>>,
+}
+
+impl<K: Eq + Hash> Drop for Permit<K> {
+    fn drop(&mut self) {
+        let mut limits = lock_unpoisoned(&self.limits);
+
+        if let Some(in_flight) = limits.in_flight.get_mut(&self.class) {
+            *in_flight = in_flight.saturating_sub(1);
+        }
+
+        // Waiters of the class race for the slot, the ones that still can't proceed register again
+        for waker in limits.waiters.remove(&self.class).unwrap_or_default() {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use crate::test_utils::*;
+    use futures::TryStreamExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct Tracker {
+        heavy_in_flight: AtomicUsize,
+        max_heavy_in_flight: AtomicUsize,
+    }
+
+    impl PageTurner<GetContentRequest> for Tracker {
+        type PageItems = Vec<usize>;
+        type PageError = ();
+
+        async fn turn_page(
+            &self,
+            request: GetContentRequest,
+        ) -> TurnedPageResult<Self, GetContentRequest> {
+            let heavy = request.page % 2 == 0;
+            if heavy {
+                let in_flight = self.heavy_in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max_heavy_in_flight
+                    .fetch_max(in_flight, Ordering::SeqCst);
+            }
+
+            futures_timer::Delay::new(std::time::Duration::from_millis(5)).await;
+
+            if heavy {
+                self.heavy_in_flight.fetch_sub(1, Ordering::SeqCst);
+            }
+
+            let next_page = request.page + 1;
+            Ok(TurnedPage::new(
+                vec![request.page],
+                (next_page < 20).then_some(GetContentRequest { page: next_page }),
+            ))
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn limits_classes() {
+        let classes = ConcurrencyClasses::new(Tracker::default(), |req: &GetContentRequest| {
+            req.page % 2 == 0
+        })
+        .limit(true, 2);
+
+        let items: Vec<_> = classes
+            .pages_ahead(8, Limit::None, GetContentRequest { page: 0 })
+            .items()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+        assert_eq!(classes.in_flight(&true), 0);
+
+        let tracker = classes.into_inner();
+        assert_eq!(tracker.max_heavy_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn registers_waiters_once_per_task() {
+        let classes = ConcurrencyClasses::new((), |class: &u8| *class).limit(0, 1);
+        let mut cx = std::task::Context::from_waker(futures::task::noop_waker_ref());
+
+        let permit = std::pin::pin!(classes.acquire(0)).as_mut().poll(&mut cx);
+        assert!(permit.is_ready());
+
+        let mut waiting = std::pin::pin!(classes.acquire(0));
+        assert!(waiting.as_mut().poll(&mut cx).is_pending());
+        assert!(waiting.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(classes.lock().waiters[&0].len(), 1);
+
+        // Other classes don't wait for the limited one
+        assert!(std::pin::pin!(classes.acquire(1)).poll(&mut cx).is_ready());
+
+        drop(permit);
+        assert!(classes.lock().waiters.is_empty());
+        assert!(waiting.poll(&mut cx).is_ready());
+    }
+}
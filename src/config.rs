@@ -0,0 +1,337 @@
+//! Organization wide defaults of page turners.
+//!
+//! [`PageTurnerConfig`] gathers parameters that are usually repeated at every call site: the
+//! default [`Limit`] and lookahead of streams, a [`RetryPolicy`], a timeout of a single request
+//! and an observer of completed requests. Attach it to a client with [`Configured`] once and every
+//! request sent through the client is timed out with [`Timeout`], retried with [`Retry`] and
+//! observed accordingly.
+//!
+//! Streams get the default limit and lookahead when they're made by `PagesBuilder::from(&client)`
+//! or by a builder with `PagesBuilder::with_config`. The `pages*` methods of `PageTurner` keep
+//! taking their arguments explicitly, so they don't silently change behavior of a wrapped client.
+//!
+//! ```
+//! use page_turner::config::{Configured, PageTurnerConfig};
+//! use page_turner::position::PageNumber;
+//! use page_turner::prelude::*;
+//! use page_turner::retry::Retries;
+//! use page_turner::timeout::TimeoutError;
+//! use page_turner::PagesBuilder;
+//! use std::time::Duration;
+//!
+//! struct Api;
+//!
+//! impl PageTurner<PageNumber> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: PageNumber) -> TurnedPageResult<Self, PageNumber> {
+//!         if page.get() == 7 {
+//!             std::future::pending::<()>().await;
+//!         }
+//!
+//!         Ok(TurnedPage::next(vec![page.get()], page.next_request()))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let config = PageTurnerConfig::new()
+//!     .ahead(4)
+//!     .limit(Limit::Pages(5))
+//!     .retry(Retries::new(1))
+//!     .request_timeout(Duration::from_millis(10));
+//!
+//! let api = Configured::new(Api, config);
+//!
+//! let items: Vec<_> = PagesBuilder::from(&api)
+//!     .build(PageNumber::FIRST)
+//!     .items()
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//! assert_eq!(items, [1, 2, 3, 4, 5]);
+//!
+//! let result = api.pages(PageNumber::new(5)).try_collect::<Vec<_>>().await;
+//! assert!(matches!(result, Err(TimeoutError::Timeout(_))));
+//! # }
+//! ```
+
+use crate::audit::PageOutcome;
+use crate::retry::{Retries, Retry, RetryPolicy};
+use crate::timeout::{Timeout, TimeoutError};
+use crate::{Limit, TurnedPage};
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// A completed attempt of a request reported to the observer of a [`Configured`] page turner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageEvent {
+    /// The attempt of the request starting from 1
+    pub attempt: usize,
+    pub latency: Duration,
+    pub outcome: PageOutcome,
+}
+
+type Observer = Arc<dyn Fn(PageEvent) + Send + Sync>;
+
+/// Defaults shared by all streams of a [`Configured`] page turner. See the [module docs](self).
+#[derive(Clone)]
+pub struct PageTurnerConfig<Policy = Retries> {
+    pub(crate) limit: Limit,
+    pub(crate) requests_ahead_count: usize,
+    retry: Policy,
+    request_timeout: Option<Duration>,
+    observer: Option<Observer>,
+}
+
+impl PageTurnerConfig {
+    /// Creates a config of streams that query pages one by one without a limit, retries and
+    /// timeouts
+    pub fn new() -> Self {
+        Self {
+            limit: Limit::None,
+            requests_ahead_count: 0,
+            retry: Retries::new(0),
+            request_timeout: None,
+            observer: None,
+        }
+    }
+}
+
+impl<Policy> PageTurnerConfig<Policy> {
+    /// The default limit of streams made by `PagesBuilder::with_config`
+    pub fn limit(mut self, limit: Limit) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// The default amount of concurrent requests of streams made by `PagesBuilder::with_config`.
+    /// `0` means querying pages one by one.
+    pub fn ahead(mut self, requests_ahead_count: usize) -> Self {
+        self.requests_ahead_count = requests_ahead_count;
+        self
+    }
+
+    /// Retries failed and timed out requests according to the `policy`
+    pub fn retry<Q>(self, policy: Q) -> PageTurnerConfig<Q> {
+        PageTurnerConfig {
+            limit: self.limit,
+            requests_ahead_count: self.requests_ahead_count,
+            retry: policy,
+            request_timeout: self.request_timeout,
+            observer: self.observer,
+        }
+    }
+
+    /// Fails an attempt of a request with [`TimeoutError::Timeout`] if it takes longer than
+    /// `timeout`
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Calls `observer` after every attempt of every request
+    pub fn observer<F>(mut self, observer: F) -> Self
+    where
+        F: 'static + Send + Sync + Fn(PageEvent),
+    {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+}
+
+impl Default for PageTurnerConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Policy: std::fmt::Debug> std::fmt::Debug for PageTurnerConfig<Policy> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PageTurnerConfig")
+            .field("limit", &self.limit)
+            .field("requests_ahead_count", &self.requests_ahead_count)
+            .field("retry", &self.retry)
+            .field("request_timeout", &self.request_timeout)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
+}
+
+/// A page turner that applies a [`PageTurnerConfig`] to every request of the inner page turner by
+/// wrapping it into [`Timeout`] and [`Retry`]. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Configured<P, Policy = Retries> {
+    retry: Retry<Timeout<P>, Policy>,
+    config: PageTurnerConfig<()>,
+}
+
+impl<P, Policy> Configured<P, Policy> {
+    pub fn new(page_turner: P, config: PageTurnerConfig<Policy>) -> Self {
+        let PageTurnerConfig {
+            limit,
+            requests_ahead_count,
+            retry,
+            request_timeout,
+            observer,
+        } = config;
+
+        // Without a timeout requests bypass the `Timeout` wrapper, the duration is never used
+        let timeout = Timeout::new(page_turner, request_timeout.unwrap_or(Duration::MAX));
+
+        Self {
+            retry: Retry::new(timeout, retry),
+            config: PageTurnerConfig {
+                limit,
+                requests_ahead_count,
+                retry: (),
+                request_timeout,
+                observer,
+            },
+        }
+    }
+
+    /// The config without its retry policy which is owned by the [`Retry`] wrapper
+    pub fn config(&self) -> &PageTurnerConfig<()> {
+        &self.config
+    }
+
+    pub fn into_inner(self) -> P {
+        self.retry.into_inner().into_inner()
+    }
+
+    async fn observe<I, R, E>(
+        &self,
+        attempt: usize,
+        page: impl Future<Output = Result<TurnedPage<I, R>, E>>,
+    ) -> Result<TurnedPage<I, R>, E> {
+        let Some(observer) = &self.config.observer else {
+            return page.await;
+        };
+
+        let start = Instant::now();
+        let result = page.await;
+        let outcome = match &result {
+            Ok(TurnedPage {
+                next_request: Some(_),
+                ..
+            }) => PageOutcome::Ok,
+            Ok(TurnedPage {
+                next_request: None, ..
+            }) => PageOutcome::Last,
+            Err(_) => PageOutcome::Error,
+        };
+
+        observer(PageEvent {
+            attempt,
+            latency: start.elapsed(),
+            outcome,
+        });
+
+        result
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, Policy, R> crate::mt::PageTurner<R> for Configured<P, Policy>
+where
+    P: crate::mt::PageTurner<R>,
+    Policy: Send + Sync + RetryPolicy<TimeoutError<P::PageError>>,
+    R: Send + Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = TimeoutError<P::PageError>;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        self.retry
+            .turn_page_with(request, |timeout, request, attempt| {
+                self.observe(attempt, async move {
+                    match self.config.request_timeout {
+                        Some(_) => timeout.turn_page(request).await,
+                        None => {
+                            (timeout.get_ref().turn_page(request).await).map_err(TimeoutError::Page)
+                        }
+                    }
+                })
+            })
+            .await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, Policy, R> crate::local::PageTurner<R> for Configured<P, Policy>
+where
+    P: crate::local::PageTurner<R>,
+    Policy: RetryPolicy<TimeoutError<P::PageError>>,
+    R: Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = TimeoutError<P::PageError>;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        self.retry
+            .turn_page_with(request, |timeout, request, attempt| {
+                self.observe(attempt, async move {
+                    match self.config.request_timeout {
+                        Some(_) => timeout.turn_page(request).await,
+                        None => {
+                            (timeout.get_ref().turn_page(request).await).map_err(TimeoutError::Page)
+                        }
+                    }
+                })
+            })
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::mt::{prelude::*, PagesBuilder};
+    use crate::test_utils::*;
+    use futures::TryStreamExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn applies_config() {
+        let mut blog = BlogClient::new(8);
+        blog.set_error(6);
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let config = PageTurnerConfig::new()
+            .ahead(3)
+            .limit(Limit::Pages(6))
+            .retry(|_: &TimeoutError<String>, retry| (retry < 2).then_some(Duration::ZERO))
+            .observer({
+                let attempts = Arc::clone(&attempts);
+                move |event| {
+                    assert!(event.attempt <= 2);
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+
+        let blog = Configured::new(blog, config);
+
+        let records: Vec<_> = PagesBuilder::from(&blog)
+            .build(GetContentRequest { page: 0 })
+            .items()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(records, (0..6).map(BlogRecord).collect::<Vec<_>>());
+
+        let attempts_before = attempts.load(Ordering::Relaxed);
+        let result = blog
+            .pages(GetContentRequest { page: 6 })
+            .try_collect::<Vec<_>>()
+            .await;
+        assert!(matches!(result, Err(TimeoutError::Page(_))));
+        assert_eq!(attempts.load(Ordering::Relaxed) - attempts_before, 2);
+    }
+}
@@ -0,0 +1,56 @@
+//! Injection of additional requests into a running pages stream. See
+//! `PagesBuilder::build_with_injector`.
+
+use crate::internal::lock_unpoisoned;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+/// A handle to push requests into a stream built with `PagesBuilder::build_with_injector`, i.e.
+/// when processing a page reveals extra resources to fetch. Injected requests share the
+/// concurrency of the stream and take free slots before its own requests. Cloning is cheap and
+/// all clones feed the same stream.
+#[derive(Debug)]
+pub struct RequestInjector<R>(Arc<Mutex<VecDeque<R>>>);
+
+impl<R> RequestInjector<R> {
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(VecDeque::new())))
+    }
+
+    /// Queues the request. The stream picks it up the next time it's polled.
+    pub fn push(&self, request: R) {
+        self.lock().push_back(request);
+    }
+
+    /// The amount of queued requests that the stream hasn't picked up yet
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    #[cfg(any(feature = "mt", feature = "local"))]
+    pub(crate) fn pop(&self) -> Option<R> {
+        self.lock().pop_front()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<R>> {
+        lock_unpoisoned(&self.0)
+    }
+}
+
+impl<R> Clone for RequestInjector<R> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<R> Default for RequestInjector<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
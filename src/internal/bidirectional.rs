@@ -0,0 +1,93 @@
+macro_rules! bidirectional_pages_def {
+    ($($extra_bounds:tt)*) => {
+        /// Pages around the initial request returned by [`PageTurner::pages_bidirectional`].
+        ///
+        /// The first call of either method queries the initial page. After that
+        /// [`BidirectionalPages::next_page`] queries the page after the newest queried one and
+        /// [`BidirectionalPages::prev_page`] queries the page before the oldest queried one.
+        pub struct BidirectionalPages<'p, P, R> {
+            page_turner: &'p P,
+            initial_request: Option<R>,
+            next_request: Option<R>,
+            prev_request: Option<R>,
+        }
+
+        impl<'p, P, R> BidirectionalPages<'p, P, R> {
+            fn new(page_turner: &'p P, request: R) -> Self {
+                Self {
+                    page_turner,
+                    initial_request: Some(request),
+                    next_request: None,
+                    prev_request: None,
+                }
+            }
+
+            /// Returns the request the next call of [`BidirectionalPages::next_page`] will send
+            pub fn peek_next_request(&self) -> Option<&R> {
+                self.initial_request.as_ref().or(self.next_request.as_ref())
+            }
+
+            /// Returns the request the next call of [`BidirectionalPages::prev_page`] will send
+            pub fn peek_prev_request(&self) -> Option<&R> {
+                self.initial_request.as_ref().or(self.prev_request.as_ref())
+            }
+        }
+
+        impl<'p, P, R, I> BidirectionalPages<'p, P, R>
+        where
+            P: PageTurner<R, PageItems = WithPrev<I, R>>,
+            R: Clone,
+            $($extra_bounds)*
+        {
+
+            /// Queries the next page. Returns `None` if there are no more pages in this
+            /// direction. The request of a failed page is kept so the call can be repeated.
+            pub async fn next_page(&mut self) -> Option<Result<I, PageError<P, R>>> {
+                if self.initial_request.is_some() {
+                    return self.initial_page().await;
+                }
+
+                let request = self.next_request.clone()?;
+                let result = self.page_turner.turn_page(request).await;
+
+                Some(result.map(|page| {
+                    let (items, next_request, _) = page.into_parts();
+                    self.next_request = next_request;
+                    items
+                }))
+            }
+
+            /// Queries the previous page. Returns `None` if there are no more pages in this
+            /// direction. The request of a failed page is kept so the call can be repeated.
+            pub async fn prev_page(&mut self) -> Option<Result<I, PageError<P, R>>> {
+                if self.initial_request.is_some() {
+                    return self.initial_page().await;
+                }
+
+                let request = self.prev_request.clone()?;
+                let result = self.page_turner.turn_page(request).await;
+
+                Some(result.map(|page| {
+                    let (items, _, prev_request) = page.into_parts();
+                    self.prev_request = prev_request;
+                    items
+                }))
+            }
+
+            async fn initial_page(&mut self) -> Option<Result<I, PageError<P, R>>> {
+                let request = self.initial_request.clone()?;
+                let result = self.page_turner.turn_page(request).await;
+
+                Some(result.map(|page| {
+                    let (items, next_request, prev_request) = page.into_parts();
+                    self.initial_request = None;
+                    self.next_request = next_request;
+                    self.prev_request = prev_request;
+                    items
+                }))
+            }
+        }
+    };
+}
+
+pub(crate) use bidirectional_pages_def;
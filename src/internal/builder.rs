@@ -0,0 +1,355 @@
+macro_rules! pages_builder_def {
+    ($($extra_bounds:tt)*) => {
+        /// Configures a pages stream with a fluent interface as an alternative to choosing between
+        /// `pages*` methods and their arguments. By default the builder makes a stream that
+        /// queries pages one by one like [`PageTurner::pages`].
+        ///
+        /// ```text
+        /// PagesBuilder::new(&client)
+        ///     .ahead(8)
+        ///     .start_at_page(50)
+        ///     .limit(Limit::Pages(25))
+        ///     .timeout(Duration::from_secs(30))
+        ///     .retry(Retries::new(3))
+        ///     .build(request)
+        /// ```
+        ///
+        /// Because of [`PagesBuilder::start_at_page`] and [`PagesBuilder::ahead`] all streams made
+        /// by the builder require requests to implement [`RequestAhead`].
+        #[derive(Debug, Clone)]
+        pub struct PagesBuilder<P> {
+            page_turner: P,
+            requests_ahead_count: usize,
+            auto: bool,
+            adaptive: Option<AdaptiveAhead>,
+            unordered: bool,
+            limit: Limit,
+            start_at_page: usize,
+        }
+
+        impl<P> PagesBuilder<P> {
+            pub fn new(page_turner: P) -> Self {
+                Self {
+                    page_turner,
+                    requests_ahead_count: 0,
+                    auto: false,
+                    adaptive: None,
+                    unordered: false,
+                    limit: Limit::None,
+                    start_at_page: 0,
+                }
+            }
+
+            /// Queries `requests_ahead_count` pages concurrently like
+            /// [`PageTurner::pages_ahead`]. `0` means querying pages one by one.
+            pub fn ahead(mut self, requests_ahead_count: usize) -> Self {
+                self.requests_ahead_count = requests_ahead_count;
+                self.auto = false;
+                self.adaptive = None;
+                self
+            }
+
+            /// Like [`PagesBuilder::ahead`] but adjusts the amount of concurrent requests up to
+            /// `max_requests_ahead_count` to how fast pages are consumed compared to how fast they
+            /// are fetched. The amount grows while the consumer waits for pages and shrinks while
+            /// fetched pages wait for the consumer, so the pipeline stays full without buffering
+            /// many pages. Doesn't apply to [`PagesBuilder::unordered`] streams which always use
+            /// the maximum.
+            pub fn ahead_auto(mut self, max_requests_ahead_count: usize) -> Self {
+                self.requests_ahead_count = max_requests_ahead_count;
+                self.auto = true;
+                self.adaptive = None;
+                self
+            }
+
+            /// Like [`PagesBuilder::ahead`] but grows the amount of concurrent requests while
+            /// responses stay fast and halves it when they slow down, within bounds of the
+            /// [`AdaptiveAhead`]. Lets large backfills find a sustainable concurrency for an API
+            /// on their own. Errors still end the stream, wrap the page turner into
+            /// [`crate::retry::Retry`] to make retried requests count as slow ones instead.
+            /// Doesn't apply to [`PagesBuilder::unordered`] streams which always use the maximum.
+            pub fn ahead_adaptive(mut self, adaptive: AdaptiveAhead) -> Self {
+                self.requests_ahead_count = adaptive.max;
+                self.auto = true;
+                self.adaptive = Some(adaptive);
+                self
+            }
+
+            /// Yields pages concurrently queried with [`PagesBuilder::ahead`] as soon as they
+            /// become available like [`PageTurner::pages_ahead_unordered`]
+            pub fn unordered(mut self) -> Self {
+                self.unordered = true;
+                self
+            }
+
+            /// Stops the stream after the amount of pages or the time budget of the `limit`
+            pub fn limit(mut self, limit: Limit) -> Self {
+                self.limit = limit;
+                self
+            }
+
+            /// Sends failed requests again according to the `policy` by wrapping the page turner
+            /// into [`crate::retry::Retry`]. Call it after [`PagesBuilder::timeout`] to retry
+            /// timed out requests as well.
+            pub fn retry<Policy>(self, policy: Policy) -> PagesBuilder<crate::retry::Retry<P, Policy>> {
+                self.map_page_turner(|page_turner| crate::retry::Retry::new(page_turner, policy))
+            }
+
+            /// Fails requests that don't complete within the `timeout` by wrapping the page turner
+            /// into [`crate::timeout::Timeout`]
+            pub fn timeout(self, timeout: std::time::Duration) -> PagesBuilder<crate::timeout::Timeout<P>> {
+                self.map_page_turner(|page_turner| crate::timeout::Timeout::new(page_turner, timeout))
+            }
+
+            /// Takes the default lookahead and limit of a [`crate::config::PageTurnerConfig`]
+            pub fn with_config<Policy>(self, config: &crate::config::PageTurnerConfig<Policy>) -> Self {
+                self.ahead(config.requests_ahead_count).limit(config.limit)
+            }
+
+            /// Skips the first `page` pages without querying them with
+            /// [`RequestAhead::nth_request`]. Combined with [`PagesBuilder::limit`] it selects a
+            /// range of pages, i.e. `.start_at_page(50).limit(Limit::Pages(25))` yields pages
+            /// `50..75`.
+            pub fn start_at_page(mut self, page: usize) -> Self {
+                self.start_at_page = page;
+                self
+            }
+
+            fn map_page_turner<Q>(self, f: impl FnOnce(P) -> Q) -> PagesBuilder<Q> {
+                PagesBuilder {
+                    page_turner: f(self.page_turner),
+                    requests_ahead_count: self.requests_ahead_count,
+                    auto: self.auto,
+                    adaptive: self.adaptive,
+                    unordered: self.unordered,
+                    limit: self.limit,
+                    start_at_page: self.start_at_page,
+                }
+            }
+
+            pub fn build<'p, R>(
+                self,
+                request: R,
+            ) -> impl 'p + PagesStream<'p, PageItems<P, R>, PageError<P, R>>
+            where
+                P: 'p + Clone + PageTurner<R>,
+                R: 'p + RequestAhead,
+                $($extra_bounds)*
+            {
+                let request = self.first_request(request);
+
+                let stream: PagesBoxStream<'p, Result<PageItems<P, R>, PageError<P, R>>> =
+                    match (self.requests_ahead_count, self.unordered, self.limit) {
+                        (0, _, Limit::None) => Box::pin(Pages::new(self.page_turner, request)),
+                        (0, _, Limit::Pages(pages)) => {
+                            Box::pin(Pages::new(self.page_turner, request).take(pages))
+                        }
+                        (0, _, limit @ (Limit::Duration(_) | Limit::All { .. })) => Box::pin(stream::try_unfold(
+                            Box::new(PagesAheadState::new(self.page_turner, request, 1, limit)),
+                            request_pages_ahead,
+                        )),
+                        (requests_ahead_count, false, limit) if self.auto => {
+                            Box::pin(stream::try_unfold(
+                                Box::new(PagesAheadAutoState::new(
+                                    self.page_turner,
+                                    request,
+                                    requests_ahead_count,
+                                    limit,
+                                    self.adaptive,
+                                )),
+                                request_pages_ahead_auto,
+                            ))
+                        }
+                        (requests_ahead_count, false, limit) => Box::pin(stream::try_unfold(
+                            Box::new(PagesAheadState::new(
+                                self.page_turner,
+                                request,
+                                requests_ahead_count,
+                                limit,
+                            )),
+                            request_pages_ahead,
+                        )),
+                        (requests_ahead_count, true, limit) => Box::pin(
+                            stream::try_unfold(
+                                Box::new(PagesAheadUnorderedState::new(
+                                    self.page_turner,
+                                    request,
+                                    requests_ahead_count,
+                                    limit,
+                                )),
+                                request_pages_ahead_unordered,
+                            )
+                            .map_ok(|(_, items)| items),
+                        ),
+                    };
+
+                stream
+            }
+
+            /// Builds an unordered stream of `pages` pages like [`PagesBuilder::unordered`] that
+            /// launches requests in the order of descending `priority`, so that consumers stopping
+            /// early get the most valuable pages first, i.e. pages with the most recent dates. The
+            /// whole range is still covered and equal priorities keep the order of pages. The last
+            /// page or the first error still end the stream but only after all prioritized pages
+            /// before them are yielded.
+            ///
+            /// Priorities are computed for all requests before the stream starts, hence the amount
+            /// of pages is required and replaces the limit of the builder. The stream is always
+            /// unordered and sends [`PagesBuilder::ahead`] requests concurrently, but at least one.
+            pub fn build_prioritized<'p, R, K, F>(
+                self,
+                request: R,
+                pages: usize,
+                priority: F,
+            ) -> impl 'p + PagesStream<'p, PageItems<P, R>, PageError<P, R>>
+            where
+                P: 'p + Clone + PageTurner<R>,
+                R: 'p + RequestAhead,
+                K: Ord,
+                F: FnMut(&R) -> K,
+                $($extra_bounds)*
+            {
+                let requests_ahead_count = self.requests_ahead_count.max(1);
+                let request = self.first_request(request);
+
+                stream::try_unfold(
+                    Box::new(PagesAheadUnorderedState::with_requests(
+                        self.page_turner,
+                        NumberedRequests::prioritized(request, pages, priority),
+                        requests_ahead_count,
+                        None,
+                    )),
+                    request_pages_ahead_unordered,
+                )
+                .map_ok(|(_, items)| items)
+            }
+
+            /// Queries the starting page and the page with the `last_page` index concurrently
+            /// before streaming pages between them, so the boundary information is available
+            /// right away while the bulk of pages is still being loaded. The middle stream
+            /// respects [`PagesBuilder::ahead`] and [`PagesBuilder::unordered`] while the limit is
+            /// replaced by the range between the boundary pages.
+            ///
+            /// `last_page` is an index of the page counted from the initial request as in
+            /// [`PagesBuilder::start_at_page`], usually it's derived from the total amount of
+            /// items known in advance. If it's not past the starting page only the head is
+            /// queried.
+            pub async fn build_head_and_tail<'p, R>(
+                self,
+                request: R,
+                last_page: usize,
+            ) -> Result<HeadAndTail<'p, PageItems<P, R>, PageError<P, R>>, PageError<P, R>>
+            where
+                P: 'p + Clone + PageTurner<R>,
+                R: 'p + RequestAhead,
+                $($extra_bounds)*
+            {
+                let start = self.start_at_page;
+
+                if last_page <= start {
+                    let head_request = match start {
+                        0 => request,
+                        page => request.nth_request(page - 1),
+                    };
+                    let head = self.page_turner.turn_page(head_request).await?;
+
+                    return Ok(HeadAndTail {
+                        head: head.items,
+                        tail: None,
+                        middle: Box::pin(stream::empty()),
+                    });
+                }
+
+                let tail_request = request.nth_request(last_page - 1);
+                let middle_request = request.nth_request(start);
+                let head_request = match start {
+                    0 => request,
+                    page => request.nth_request(page - 1),
+                };
+
+                let (head, tail) = futures::future::try_join(
+                    self.page_turner.turn_page(head_request),
+                    self.page_turner.turn_page(tail_request),
+                )
+                .await?;
+
+                let middle = Self {
+                    start_at_page: 0,
+                    limit: Limit::Pages(last_page - start - 1),
+                    ..self
+                }
+                .build(middle_request);
+
+                Ok(HeadAndTail {
+                    head: head.items,
+                    tail: Some(tail.items),
+                    middle: Box::pin(middle),
+                })
+            }
+
+            /// Builds an unordered stream like [`PagesBuilder::unordered`] whose consumer can push
+            /// additional requests with the `injector`, i.e. when processing a page reveals extra
+            /// resources to fetch. Injected requests share the concurrency of the stream set with
+            /// [`PagesBuilder::ahead`], take free slots before requests generated by the stream
+            /// and are paginated until they return no next request. They don't count towards the
+            /// [`PagesBuilder::limit`].
+            ///
+            /// Requests are picked up when the stream is polled, so push them while processing
+            /// yielded pages. The stream ends once all of its pages and all injected requests are
+            /// queried, requests pushed after that are ignored. An error of an injected request
+            /// ends the stream right away.
+            pub fn build_with_injector<'p, R>(
+                self,
+                request: R,
+                injector: RequestInjector<R>,
+            ) -> impl 'p + PagesStream<'p, PageItems<P, R>, PageError<P, R>>
+            where
+                P: 'p + Clone + PageTurner<R>,
+                R: 'p + RequestAhead,
+                $($extra_bounds)*
+            {
+                let requests = RequestIter::new(self.first_request(request), self.limit);
+                let latency = requests.latency();
+                let state = PagesAheadUnorderedState::with_requests(
+                    self.page_turner,
+                    NumberedRequests::sequential(requests),
+                    self.requests_ahead_count.max(1),
+                    latency,
+                )
+                .with_injector(injector);
+
+                stream::try_unfold(Box::new(state), request_pages_ahead_unordered)
+                    .map_ok(|(_, items)| items)
+            }
+
+            fn first_request<R: RequestAhead>(&self, request: R) -> R {
+                match self.start_at_page {
+                    0 => request,
+                    page => request.nth_request(page - 1),
+                }
+            }
+        }
+
+        /// Makes a builder that starts with the default lookahead and limit of the
+        /// [`crate::config::Configured`] page turner, so its streams don't repeat them at call
+        /// sites
+        impl<'p, P, Policy> From<&'p crate::config::Configured<P, Policy>>
+            for PagesBuilder<&'p crate::config::Configured<P, Policy>>
+        {
+            fn from(page_turner: &'p crate::config::Configured<P, Policy>) -> Self {
+                PagesBuilder::new(page_turner).with_config(page_turner.config())
+            }
+        }
+
+        /// Boundary pages and a stream of pages between them returned by
+        /// [`PagesBuilder::build_head_and_tail`]
+        pub struct HeadAndTail<'p, T, E> {
+            pub head: T,
+            /// `None` when the head is the only requested page
+            pub tail: Option<T>,
+            pub middle: PagesBoxStream<'p, Result<T, E>>,
+        }
+    };
+}
+
+pub(crate) use pages_builder_def;
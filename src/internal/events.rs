@@ -0,0 +1,329 @@
+//! Structured events emitted by pages streams when the `log-json` or the `tracing` feature is
+//! enabled. Without them [`StreamEvents`] only carries the [`StreamId`] and its methods simply await
+//! page futures.
+
+use crate::{StreamId, TurnedPage};
+use std::future::Future;
+
+/// Identifies a stream in emitted events
+#[derive(Debug, Clone, Copy)]
+pub struct StreamEvents {
+    id: StreamId,
+    #[cfg(any(feature = "log-json", feature = "tracing"))]
+    mode: &'static str,
+}
+
+impl StreamEvents {
+    #[cfg_attr(
+        not(any(feature = "log-json", feature = "tracing")),
+        allow(unused_variables)
+    )]
+    pub fn new(mode: &'static str) -> Self {
+        Self {
+            id: StreamId::next(),
+            #[cfg(any(feature = "log-json", feature = "tracing"))]
+            mode,
+        }
+    }
+
+    pub fn id(&self) -> StreamId {
+        self.id
+    }
+
+    /// Awaits the future of the page with the `page` index counted from the initial request and
+    /// emits an event with its latency and outcome. `window` is the amount of requests that were
+    /// in flight when this one was scheduled.
+    #[cfg_attr(
+        not(any(feature = "log-json", feature = "tracing")),
+        allow(unused_variables)
+    )]
+    pub async fn turn_page<F, T, R, E>(self, page: usize, window: usize, fut: F) -> F::Output
+    where
+        F: Future<Output = Result<TurnedPage<T, R>, E>>,
+    {
+        #[cfg(feature = "tracing")]
+        let fut = self.traced(page, window, fut);
+
+        #[cfg(feature = "log-json")]
+        {
+            let start = std::time::Instant::now();
+            let result = fut.await;
+            self.emit(page, start.elapsed(), &result);
+            result
+        }
+
+        #[cfg(not(feature = "log-json"))]
+        fut.await
+    }
+
+    #[cfg(feature = "tracing")]
+    fn traced<F, T, R, E>(
+        self,
+        page: usize,
+        window: usize,
+        fut: F,
+    ) -> impl Future<Output = F::Output>
+    where
+        F: Future<Output = Result<TurnedPage<T, R>, E>>,
+    {
+        use tracing::Instrument;
+
+        // `attempt` is recorded by `Retry` wrappers polled within the span
+        let span = tracing::debug_span!(
+            target: "page_turner",
+            "turn_page",
+            stream_id = self.id.0,
+            mode = self.mode,
+            page,
+            window,
+            attempt = tracing::field::Empty,
+        );
+
+        async move {
+            tracing::trace!(target: "page_turner", "scheduled");
+
+            let start = std::time::Instant::now();
+            let result = fut.await;
+            let latency_us = start.elapsed().as_micros() as u64;
+
+            match &result {
+                Ok(page) => tracing::debug!(
+                    target: "page_turner",
+                    latency_us,
+                    last = page.next_request.is_none(),
+                    "completed"
+                ),
+                Err(_) => tracing::warn!(target: "page_turner", latency_us, "failed"),
+            }
+
+            result
+        }
+        .instrument(span)
+    }
+
+    #[cfg(feature = "log-json")]
+    fn emit<T, R, E>(
+        &self,
+        page: usize,
+        latency: std::time::Duration,
+        result: &Result<TurnedPage<T, R>, E>,
+    ) {
+        let outcome = match result {
+            Ok(TurnedPage {
+                next_request: Some(_),
+                ..
+            }) => "ok",
+            Ok(TurnedPage {
+                next_request: None, ..
+            }) => "last",
+            Err(_) => "error",
+        };
+
+        let level = match result {
+            Ok(_) => log::Level::Debug,
+            Err(_) => log::Level::Warn,
+        };
+
+        let event = serde_json::json!({
+            "event": "page",
+            "stream_id": self.id.0,
+            "mode": self.mode,
+            "page": page,
+            "latency_us": latency.as_micros() as u64,
+            "outcome": outcome,
+        });
+
+        log::log!(target: "page_turner", level, "{event}");
+    }
+}
+
+#[cfg(all(test, feature = "log-json"))]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    static EVENTS: Mutex<Vec<(log::Level, serde_json::Value)>> = Mutex::new(Vec::new());
+
+    struct Recorder;
+
+    impl log::Log for Recorder {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            if record.target() == "page_turner" {
+                let event = serde_json::from_str(&record.args().to_string()).unwrap();
+                EVENTS.lock().unwrap().push((record.level(), event));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[tokio::test]
+    async fn events() {
+        log::set_logger(&Recorder).unwrap();
+        log::set_max_level(log::LevelFilter::Debug);
+
+        let events = StreamEvents::new("pages");
+        let page = |next: Option<usize>| async move { Ok::<_, ()>(TurnedPage::new((), next)) };
+
+        events.turn_page(0, 0, page(Some(1))).await.unwrap();
+        events.turn_page(1, 0, page(None)).await.unwrap();
+        let result = events.turn_page(2, 0, async { Err::<TurnedPage<(), ()>, _>(()) });
+        assert!(result.await.is_err());
+
+        let recorded: Vec<_> = EVENTS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, event)| event["stream_id"] == events.id.0)
+            .map(|(level, event)| {
+                assert_eq!(event["event"], "page");
+                assert_eq!(event["mode"], "pages");
+                assert!(event["latency_us"].is_u64());
+
+                (*level, event["page"].clone(), event["outcome"].clone())
+            })
+            .collect();
+
+        assert_eq!(
+            recorded,
+            [
+                (log::Level::Debug, 0.into(), "ok".into()),
+                (log::Level::Debug, 1.into(), "last".into()),
+                (log::Level::Warn, 2.into(), "error".into()),
+            ]
+        );
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tracing_tests {
+    use super::*;
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    type Fields = HashMap<&'static str, String>;
+
+    struct FieldsVisitor<'f>(&'f mut Fields);
+
+    impl Visit for FieldsVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name(), format!("{value:?}"));
+        }
+    }
+
+    /// Records events along with fields of the span they were emitted in
+    #[derive(Default)]
+    struct Recorder {
+        spans: Mutex<Vec<(&'static Metadata<'static>, Fields)>>,
+        entered: Mutex<Vec<usize>>,
+        events: Mutex<Vec<(Fields, Fields)>>,
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attrs: &span::Attributes<'_>) -> span::Id {
+            let mut fields = Fields::new();
+            attrs.record(&mut FieldsVisitor(&mut fields));
+
+            let mut spans = self.spans.lock().unwrap();
+            spans.push((attrs.metadata(), fields));
+            span::Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &span::Id, values: &span::Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            values.record(&mut FieldsVisitor(
+                &mut spans[span.into_u64() as usize - 1].1,
+            ));
+        }
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields::new();
+            event.record(&mut FieldsVisitor(&mut fields));
+
+            let span = match self.entered.lock().unwrap().last() {
+                Some(ix) => self.spans.lock().unwrap()[ix - 1].1.clone(),
+                None => Fields::new(),
+            };
+
+            self.events.lock().unwrap().push((span, fields));
+        }
+
+        fn enter(&self, span: &span::Id) {
+            self.entered.lock().unwrap().push(span.into_u64() as usize);
+        }
+
+        fn exit(&self, _: &span::Id) {
+            self.entered.lock().unwrap().pop();
+        }
+
+        fn current_span(&self) -> tracing_core::span::Current {
+            match self.entered.lock().unwrap().last() {
+                Some(&ix) => {
+                    let metadata = self.spans.lock().unwrap()[ix - 1].0;
+                    tracing_core::span::Current::new(span::Id::from_u64(ix as u64), metadata)
+                }
+                None => tracing_core::span::Current::none(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn spans() {
+        let recorder = Arc::new(Recorder::default());
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+
+        let events = StreamEvents::new("pages_ahead");
+        let result = events.turn_page(4, 2, async {
+            tracing::Span::current().record("attempt", 1);
+            Ok::<_, ()>(TurnedPage::new((), None::<()>))
+        });
+        assert!(result.await.is_ok());
+
+        let result = events.turn_page(5, 3, async { Err::<TurnedPage<(), ()>, _>(()) });
+        assert!(result.await.is_err());
+
+        let recorded = recorder.events.lock().unwrap();
+        let messages: Vec<_> = recorded
+            .iter()
+            .map(|(span, event)| {
+                assert_eq!(span["stream_id"], events.id.0.to_string());
+                assert_eq!(span["mode"], "\"pages_ahead\"");
+                assert!(event.contains_key("latency_us") || event["message"] == "scheduled");
+
+                (
+                    span["page"].as_str(),
+                    span["window"].as_str(),
+                    span.get("attempt").map(String::as_str),
+                    event["message"].as_str(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            messages,
+            [
+                ("4", "2", None, "scheduled"),
+                ("4", "2", Some("1"), "completed"),
+                ("5", "3", None, "scheduled"),
+                ("5", "3", None, "failed"),
+            ]
+        );
+        assert_eq!(recorded[1].1["last"], "true");
+    }
+}
@@ -1,22 +1,35 @@
+use super::SharedAverage;
 use crate::{Limit, RequestAhead};
-
-pub type RequestChunks<R> = Chunks<RequestIter<R>>;
-pub type EnumerableRequestChunks<R> = Chunks<std::iter::Enumerate<RequestIter<R>>>;
+use std::time::Instant;
 
 pub struct RequestIter<R> {
     cur_request: Option<R>,
     limit: Limit,
     counter: usize,
+    deadline: Option<(Instant, SharedAverage)>,
 }
 
 impl<R> RequestIter<R> {
     pub fn new(req: R, limit: Limit) -> Self {
+        let deadline = limit
+            .duration()
+            .and_then(|duration| Instant::now().checked_add(duration))
+            .map(|deadline| (deadline, SharedAverage::default()));
+
         Self {
             cur_request: Some(req),
             limit,
             counter: 0,
+            deadline,
         }
     }
+
+    /// Returns the average latency of requests which must be recorded by the scheduler if the
+    /// iterator has a deadline. Requests that are expected to complete past the deadline aren't
+    /// yielded.
+    pub fn latency(&self) -> Option<SharedAverage> {
+        self.deadline.as_ref().map(|(_, latency)| latency.clone())
+    }
 }
 
 impl<R> Iterator for RequestIter<R>
@@ -26,12 +39,20 @@ impl<R> Iterator for RequestIter<R>
     type Item = R;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Limit::Pages(pages) = self.limit {
+        if let Some(pages) = self.limit.pages() {
             if self.counter >= pages {
                 return None;
             }
         }
 
+        if let Some((deadline, latency)) = &self.deadline {
+            let expected_latency = latency.get().unwrap_or_default();
+            if Instant::now() + expected_latency > *deadline {
+                self.cur_request = None;
+                return None;
+            }
+        }
+
         let next_request = self
             .cur_request
             .as_ref()
@@ -46,6 +67,66 @@ fn next(&mut self) -> Option<Self::Item> {
     }
 }
 
+/// Requests numbered by their page index, either generated one after another or launched in the
+/// order of their priority
+pub enum NumberedRequests<R, I = RequestIter<R>> {
+    Sequential(std::iter::Enumerate<I>),
+    Prioritized(std::vec::IntoIter<(usize, R)>),
+}
+
+impl<R, I> NumberedRequests<R, I>
+where
+    I: Iterator<Item = R>,
+{
+    pub fn sequential(requests: I) -> Self {
+        Self::Sequential(requests.enumerate())
+    }
+
+    /// Drops requests for pages past the last one. Sequential requests all come after the last
+    /// page, prioritized ones may still contain pages before it.
+    pub fn truncate(&mut self, last_page: usize) {
+        match self {
+            Self::Sequential(_) => *self = Self::Prioritized(Vec::new().into_iter()),
+            Self::Prioritized(requests) => {
+                let remaining: Vec<_> = requests.filter(|(num, _)| *num < last_page).collect();
+                *requests = remaining.into_iter();
+            }
+        }
+    }
+}
+
+impl<R> NumberedRequests<R> {
+    /// Generates requests for `pages` pages and orders them by descending `priority`. Requests
+    /// with equal priorities remain in the order of pages.
+    pub fn prioritized<K, F>(request: R, pages: usize, mut priority: F) -> Self
+    where
+        R: RequestAhead,
+        K: Ord,
+        F: FnMut(&R) -> K,
+    {
+        let mut requests: Vec<_> = RequestIter::new(request, Limit::Pages(pages))
+            .enumerate()
+            .collect();
+        requests.sort_by_key(|(_, request)| std::cmp::Reverse(priority(request)));
+
+        Self::Prioritized(requests.into_iter())
+    }
+}
+
+impl<R, I> Iterator for NumberedRequests<R, I>
+where
+    I: Iterator<Item = R>,
+{
+    type Item = (usize, R);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Sequential(requests) => requests.next(),
+            Self::Prioritized(requests) => requests.next(),
+        }
+    }
+}
+
 pub trait ChunksExt: Sized {
     fn chunks(self, chunk_size: usize) -> Chunks<Self>;
 }
@@ -79,6 +160,10 @@ pub fn next_chunk(&mut self) -> Option<Chunk<'_, I>> {
     pub fn next_item(&mut self) -> Option<I::Item> {
         self.iter.next()
     }
+
+    pub fn iter_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
 }
 
 pub struct Chunk<'c, I: Iterator> {
@@ -136,6 +221,66 @@ fn next_request(&self) -> Self {
         }
     }
 
+    #[test]
+    fn request_iter_deadline() {
+        let mut requests = RequestIter::new(
+            DumbRequest::default(),
+            Limit::Duration(std::time::Duration::from_secs(60)),
+        );
+
+        assert_eq!(requests.next().map(|req| req.page), Some(1));
+
+        let latency = requests.latency().unwrap();
+        latency.record(std::time::Duration::from_secs(30));
+        assert_eq!(requests.next().map(|req| req.page), Some(2));
+
+        // The next request is expected to complete past the deadline
+        latency.record(std::time::Duration::from_secs(300));
+        assert!(requests.next().is_none());
+        assert!(requests.next().is_none());
+
+        let mut requests =
+            RequestIter::new(DumbRequest::default(), Limit::Duration(Default::default()));
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        assert!(requests.next().is_none());
+        assert!(RequestIter::new(DumbRequest::default(), Limit::None)
+            .latency()
+            .is_none());
+    }
+
+    #[test]
+    fn request_iter_composite_limit() {
+        assert_eq!(Limit::Pages(5).and(Limit::Pages(3)), Limit::Pages(3));
+        assert_eq!(Limit::None.and(Limit::Pages(5)), Limit::Pages(5));
+        assert_eq!(Limit::None.and(Limit::None), Limit::None);
+
+        let limit = Limit::Pages(3).and(Limit::Duration(std::time::Duration::from_secs(60)));
+        assert_eq!(RequestIter::new(DumbRequest::default(), limit).count(), 3);
+
+        let limit = Limit::Duration(Default::default()).and(Limit::Pages(3));
+        let mut requests = RequestIter::new(DumbRequest::default(), limit);
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        assert!(requests.next().is_none());
+    }
+
+    #[test]
+    fn prioritized_requests() {
+        let mut requests =
+            NumberedRequests::prioritized(DumbRequest::default(), 6, |req| req.page % 3);
+        let order: Vec<_> = requests.by_ref().take(2).map(|(num, _)| num).collect();
+        assert_eq!(order, [1, 4]);
+
+        requests.truncate(3);
+        let order: Vec<_> = requests.map(|(num, req)| (num, req.page)).collect();
+        assert_eq!(order, [(0, 1), (2, 3)]);
+
+        let mut requests =
+            NumberedRequests::sequential(RequestIter::new(DumbRequest::default(), Limit::None));
+        assert_eq!(requests.next().map(|(num, _)| num), Some(0));
+        requests.truncate(0);
+        assert!(requests.next().is_none());
+    }
+
     #[test]
     fn request_iter() {
         let last = RequestIter::new(DumbRequest::default(), Limit::None)
@@ -14,16 +14,95 @@
 //! It turned out that every page turner requires everything from this module to be fully
 //! implemented so it's ok to abuse glob imports(`use internal::*;`) in page turner modules.
 
+pub mod bidirectional;
+pub mod builder;
+pub mod events;
 pub mod itertools;
 pub mod pages;
 pub mod pages_ahead;
+pub mod pages_ahead_auto;
 pub mod pages_ahead_unordered;
+pub mod pages_while;
+pub mod tail;
+pub mod testing;
 
+pub use crate::watch::TailError;
+pub use crate::{OverqueryStats, Progress, Total};
+pub use events::StreamEvents;
 pub use itertools::*;
-pub use pages::PagesState;
+pub use pages_ahead_auto::{auto_window, record_latency, AimdWindow, SharedAverage};
+pub use pages_ahead_unordered::{Completed, INJECTED_PAGE};
+pub use pages_while::pages_while;
 
-pub(crate) use pages::request_next_page_decl;
+pub(crate) use bidirectional::bidirectional_pages_def;
+pub(crate) use builder::pages_builder_def;
+pub(crate) use pages::pages_stream_def;
 pub(crate) use pages_ahead::{pages_ahead_state_def, request_pages_ahead_decl};
+pub(crate) use pages_ahead_auto::{pages_ahead_auto_state_def, request_pages_ahead_auto_decl};
 pub(crate) use pages_ahead_unordered::{
-    pages_ahead_unordered_state_def, request_pages_ahead_unordered_decl,
+    pages_ahead_unordered_state_def, pages_ahead_unordered_stream_def,
+    request_pages_ahead_unordered_decl,
 };
+pub(crate) use tail::{request_tail_page_decl, tail_state_def};
+pub(crate) use testing::assert_pagination_equivalent_decl;
+
+/// Locks the `mutex` ignoring poisoning. Shared states of the crate are never left half-modified
+/// across a panic, so a panic in another holder of the lock doesn't make the state inconsistent and
+/// there is no reason to propagate it to every stream sharing the state.
+pub(crate) fn lock_unpoisoned<T: ?Sized>(
+    mutex: &std::sync::Mutex<T>,
+) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Implements `core::async_iter::AsyncIterator` for a named stream type by delegating to its
+/// `Stream` impl when the `nightly-async-iter` feature is enabled. Generic parameters of the impl
+/// are passed in brackets and bounds required by the type follow `where`:
+/// `async_iterator_impl!(['p, T, E] Stream<'p, T, E> where T: Send)`.
+macro_rules! async_iterator_impl {
+    ([$($generics:tt)*] $ty:ty $(where $($bounds:tt)*)?) => {
+        #[cfg(feature = "nightly-async-iter")]
+        impl<$($generics)*> core::async_iter::AsyncIterator for $ty
+        where
+            Self: futures::Stream,
+            $($($bounds)*)?
+        {
+            type Item = <Self as futures::Stream>::Item;
+
+            fn poll_next(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<Option<Self::Item>> {
+                futures::Stream::poll_next(self, cx)
+            }
+        }
+    };
+}
+
+pub(crate) use async_iterator_impl;
+
+/// Accounts a yielded page in `done` and returns the updated [`Progress`] towards the `total`
+pub fn update_progress<T>(done: &mut usize, total: Total, items: &T) -> Progress
+where
+    for<'t> &'t T: IntoIterator,
+{
+    match total {
+        Total::Pages(pages) => {
+            *done += 1;
+            Progress::new(*done, pages)
+        }
+        Total::Items(items_count) => {
+            *done += items.into_iter().count();
+            Progress::new(*done, items_count)
+        }
+    }
+}
+
+pub fn unwrap_infallible<T, E>(result: Result<T, E>) -> T
+where
+    E: Into<std::convert::Infallible>,
+{
+    result.unwrap_or_else(|e| match e.into() {})
+}
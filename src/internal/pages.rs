@@ -1,40 +1,256 @@
-pub struct PagesState<P, R> {
-    pub page_turner: P,
-    pub next_request: Option<R>,
-}
+macro_rules! pages_stream_def {
+    ($($extra_bounds:tt)*) => {
+        /// A stream that queries pages one by one returned by [`PageTurner::pages`] and
+        /// [`PageTurner::into_pages`].
+        ///
+        /// The stream allows to look at the request for the next page between polls which is
+        /// useful to log the progress or to persist a cursor in order to resume the pagination
+        /// later.
+        pub struct Pages<'p, P, R>
+        where
+            P: 'p + PageTurner<R>,
+            $($extra_bounds)*
+        {
+            page_turner: Option<P>,
+            next_request: Option<R>,
+            current_request: Option<R>,
+            clone_request: Option<fn(&R) -> R>,
+            in_progress: Option<PagesFuture<'p, P, R>>,
+            events: StreamEvents,
+            pages_queried: usize,
+        }
+
+        impl<'p, P, R> Pages<'p, P, R>
+        where
+            P: 'p + PageTurner<R>,
+            $($extra_bounds)*
+        {
+            fn new(page_turner: P, request: R) -> Self {
+                Self {
+                    page_turner: Some(page_turner),
+                    next_request: Some(request),
+                    current_request: None,
+                    clone_request: None,
+                    in_progress: None,
+                    events: StreamEvents::new("pages"),
+                    pages_queried: 0,
+                }
+            }
+
+            /// Returns the identifier of this stream
+            pub fn stream_id(&self) -> StreamId {
+                self.events.id()
+            }
+
+            /// Returns the request that will be sent on the next poll. Returns `None` while
+            /// a page is being queried or if the stream has ended.
+            pub fn peek_next_request(&self) -> Option<&R> {
+                self.next_request.as_ref()
+            }
+
+            /// Consumes the stream and returns the request for the first page that hasn't been
+            /// successfully queried yet. A request that was in progress or failed can be recovered
+            /// only if [`Pages::track_current_request`] was enabled.
+            pub fn into_next_request(self) -> Option<R> {
+                self.next_request.or(self.current_request)
+            }
+
+            /// Returns the request of the page that is currently being queried. Always returns
+            /// `None` unless [`Pages::track_current_request`] was called.
+            pub fn current_request(&self) -> Option<&R> {
+                self.current_request.as_ref()
+            }
+
+            /// Skips `n` pages starting from the next one without querying them. Does nothing if the
+            /// next request is unknown because a page is being queried or the stream has ended.
+            pub fn skip_pages(mut self, n: usize) -> Self
+            where
+                R: RequestAhead,
+            {
+                self.next_request = match (self.next_request.take(), n) {
+                    (Some(request), 0) => Some(request),
+                    (Some(request), n) => {
+                        self.pages_queried += n;
+                        Some(request.nth_request(n - 1))
+                    }
+                    (None, _) => None,
+                };
 
-impl<P, R> PagesState<P, R> {
-    pub fn new(page_turner: P, request: R) -> Self {
-        Self {
-            page_turner,
-            next_request: Some(request),
+                self
+            }
+
+            /// Makes the stream yield pages along with requests that produced them, see
+            /// [`ResumablePages`]
+            pub fn resumable(self) -> ResumablePages<'p, P, R>
+            where
+                R: Clone,
+            {
+                ResumablePages {
+                    pages: self,
+                    sent_request: None,
+                }
+            }
+
+            /// Makes the stream keep a clone of each request being sent so it can be observed
+            /// with [`Pages::current_request`].
+            pub fn track_current_request(mut self) -> Self
+            where
+                R: Clone,
+            {
+                self.clone_request = Some(R::clone);
+                self
+            }
         }
-    }
-}
 
-macro_rules! request_next_page_decl {
-    ($($extra_bounds:tt)*) => {
-        async fn request_next_page<P, R>(
-            mut state: crate::internal::pages::PagesState<P, R>,
-        ) -> Result<Option<(PageItems<P, R>, crate::internal::pages::PagesState<P, R>)>, PageError<P, R>>
+        // Neither page turner nor requests are ever pinned, they're only moved into a boxed future
+        impl<'p, P, R> Unpin for Pages<'p, P, R>
+        where
+            P: 'p + PageTurner<R>,
+            $($extra_bounds)*
+        {
+        }
+
+        impl<'p, P, R> Stream for Pages<'p, P, R>
         where
-            P: PageTurner<R>,
+            P: 'p + PageTurner<R>,
+            R: 'p,
             $($extra_bounds)*
         {
-            let request = match state.next_request {
-                Some(request) => request,
-                None => return Ok(None),
-            };
+            type Item = Result<PageItems<P, R>, PageError<P, R>>;
+
+            fn poll_next(
+                self: Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<Option<Self::Item>> {
+                let this = self.get_mut();
+
+                if this.in_progress.is_none() {
+                    match (this.page_turner.take(), this.next_request.take()) {
+                        #[allow(unused_mut)]
+                        (Some(mut page_turner), Some(request)) => {
+                            this.current_request = this.clone_request.map(|clone| clone(&request));
+
+                            let events = this.events;
+                            let page = this.pages_queried;
+                            this.pages_queried += 1;
+
+                            this.in_progress = Some(Box::pin(async move {
+                                let result = events.turn_page(page, 0, page_turner.turn_page(request)).await;
+                                (page_turner, result)
+                            }));
+                        }
+                        (page_turner, _) => {
+                            this.page_turner = page_turner;
+                            return std::task::Poll::Ready(None);
+                        }
+                    }
+                }
+
+                let (page_turner, result) = match this.in_progress.as_mut() {
+                    Some(fut) => std::task::ready!(fut.as_mut().poll(cx)),
+                    None => unreachable!("BUG(page-turner): The future was scheduled right above"),
+                };
 
-            let TurnedPage {
-                items,
-                next_request,
-            } = state.page_turner.turn_page(request).await?;
+                this.in_progress = None;
+                this.page_turner = Some(page_turner);
 
-            state.next_request = next_request;
-            Ok(Some((items, state)))
+                // After an error `next_request` remains `None` which ends the stream
+                let result = result.map(|TurnedPage { items, next_request }| {
+                    this.current_request = None;
+                    this.next_request = next_request;
+                    items
+                });
+
+                std::task::Poll::Ready(Some(result))
+            }
+        }
+
+        crate::internal::async_iterator_impl!(
+            ['p, P, R] Pages<'p, P, R>
+            where
+                P: 'p + PageTurner<R>,
+                $($extra_bounds)*
+        );
+
+        /// A stream returned by [`Pages::resumable`] and [`PageTurner::pages_resumable`] that
+        /// yields pages along with requests that produced them. Persist the request of the last
+        /// processed page or [`ResumablePages::into_next_request`] to resume the pagination after
+        /// a restart without querying processed pages again.
+        pub struct ResumablePages<'p, P, R>
+        where
+            P: 'p + PageTurner<R>,
+            $($extra_bounds)*
+        {
+            pages: Pages<'p, P, R>,
+            sent_request: Option<R>,
+        }
+
+        impl<'p, P, R> ResumablePages<'p, P, R>
+        where
+            P: 'p + PageTurner<R>,
+            $($extra_bounds)*
+        {
+            /// Returns the request that will be sent on the next poll. Returns `None` while
+            /// a page is being queried or if the stream has ended.
+            pub fn peek_next_request(&self) -> Option<&R> {
+                self.pages.peek_next_request()
+            }
+
+            /// Consumes the stream and returns the request for the first page that hasn't been
+            /// yielded yet, including the one that was in progress or failed. Returns `None` if
+            /// all pages were yielded.
+            pub fn into_next_request(self) -> Option<R> {
+                self.pages.into_next_request().or(self.sent_request)
+            }
+        }
+
+        // Neither page turner nor requests are ever pinned
+        impl<'p, P, R> Unpin for ResumablePages<'p, P, R>
+        where
+            P: 'p + PageTurner<R>,
+            $($extra_bounds)*
+        {
+        }
+
+        impl<'p, P, R> Stream for ResumablePages<'p, P, R>
+        where
+            P: 'p + PageTurner<R>,
+            R: 'p + Clone,
+            $($extra_bounds)*
+        {
+            type Item = Result<(R, PageItems<P, R>), PageError<P, R>>;
+
+            fn poll_next(
+                self: Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<Option<Self::Item>> {
+                let this = self.get_mut();
+
+                // The request is going to be sent by this poll
+                if this.sent_request.is_none() {
+                    this.sent_request = this.pages.peek_next_request().cloned();
+                }
+
+                let page = std::task::ready!(Pin::new(&mut this.pages).poll_next(cx));
+                std::task::Poll::Ready(page.map(|result| {
+                    result.map(|items| {
+                        let request = this.sent_request.take().expect(
+                            "BUG(page-turner): A page can't be yielded before its request is sent",
+                        );
+
+                        (request, items)
+                    })
+                }))
+            }
         }
+
+        crate::internal::async_iterator_impl!(
+            ['p, P, R] ResumablePages<'p, P, R>
+            where
+                P: 'p + PageTurner<R>,
+                $($extra_bounds)*
+        );
     };
 }
 
-pub(crate) use request_next_page_decl;
+pub(crate) use pages_stream_def;
@@ -1,14 +1,17 @@
 macro_rules! pages_ahead_state_def {
     ($($extra_bounds:tt)*) => {
-        struct PagesAheadState<'p, P, R>
+        struct PagesAheadState<'p, P, R, I = RequestIter<R>>
         where
             P: 'p + PageTurner<R>,
             $($extra_bounds)*
         {
             page_turner: P,
-            requests: RequestChunks<R>,
+            requests: Chunks<I>,
             in_progress: FuturesOrdered<PageTurnerFuture<'p, P, R>>,
             last_page_queried: bool,
+            events: StreamEvents,
+            pages_queried: usize,
+            latency: Option<SharedAverage>,
         }
 
         impl<'p, P, R> PagesAheadState<'p, P, R>
@@ -18,12 +21,33 @@ impl<'p, P, R> PagesAheadState<'p, P, R>
             $($extra_bounds)*
         {
             pub fn new(page_turner: P, request: R, chunk_size: usize, limit: Limit) -> Self {
-                let requests = RequestIter::new(request, limit).chunks(chunk_size);
+                let requests = RequestIter::new(request, limit);
+                let latency = requests.latency();
+
+                Self::from_requests(page_turner, requests, chunk_size, latency)
+            }
+        }
+
+        impl<'p, P, R, I> PagesAheadState<'p, P, R, I>
+        where
+            P: 'p + PageTurner<R>,
+            I: Iterator<Item = R>,
+            $($extra_bounds)*
+        {
+            pub fn from_requests(
+                page_turner: P,
+                requests: I,
+                chunk_size: usize,
+                latency: Option<SharedAverage>,
+            ) -> Self {
                 Self {
                     page_turner,
-                    requests,
+                    requests: requests.chunks(chunk_size),
                     in_progress: FuturesOrdered::new(),
                     last_page_queried: false,
+                    events: StreamEvents::new("pages_ahead"),
+                    pages_queried: 0,
+                    latency,
                 }
             }
         }
@@ -32,12 +56,13 @@ pub fn new(page_turner: P, request: R, chunk_size: usize, limit: Limit) -> Self
 
 macro_rules! request_pages_ahead_decl {
     ($($extra_bounds:tt)*) => {
-        async fn request_pages_ahead<'p, P, R>(
-            mut state: Box<PagesAheadState<'p, P, R>>,
-        ) -> Result<Option<(PageItems<P, R>, Box<PagesAheadState<'p, P, R>>)>, PageError<P, R>>
+        async fn request_pages_ahead<'p, P, R, I>(
+            mut state: Box<PagesAheadState<'p, P, R, I>>,
+        ) -> Result<Option<(PageItems<P, R>, Box<PagesAheadState<'p, P, R, I>>)>, PageError<P, R>>
         where
             P: 'p + Clone + PageTurner<R>,
-            R: 'p + RequestAhead,
+            R: 'p,
+            I: Iterator<Item = R>,
             $($extra_bounds)*
         {
             if state.last_page_queried {
@@ -50,8 +75,15 @@ async fn request_pages_ahead<'p, P, R>(
                     Some(chunk) => {
                         for req in chunk {
                             let local_page_turner = state.page_turner.clone();
+                            let events = state.events;
+                            let latency = state.latency.clone();
+                            let page = state.pages_queried;
+                            let in_flight = state.in_progress.len();
+                            state.pages_queried += 1;
+
                             state.in_progress.push_back(Box::pin(async move {
-                                local_page_turner.turn_page(req).await
+                                let page_future = events.turn_page(page, in_flight, local_page_turner.turn_page(req));
+                                record_latency(latency, page_future).await
                             }));
                         }
                     }
@@ -64,9 +96,16 @@ async fn request_pages_ahead<'p, P, R>(
                 // a sliding window maner.
                 if let Some(req) = state.requests.next_item() {
                     let local_page_turner = state.page_turner.clone();
-                    state.in_progress.push_back(Box::pin(
-                        async move { local_page_turner.turn_page(req).await },
-                    ))
+                    let events = state.events;
+                    let latency = state.latency.clone();
+                    let page = state.pages_queried;
+                    let in_flight = state.in_progress.len();
+                    state.pages_queried += 1;
+
+                    state.in_progress.push_back(Box::pin(async move {
+                        let page_future = events.turn_page(page, in_flight, local_page_turner.turn_page(req));
+                        record_latency(latency, page_future).await
+                    }))
                 }
             }
 
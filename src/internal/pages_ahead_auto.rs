@@ -0,0 +1,245 @@
+use super::lock_unpoisoned;
+use crate::AdaptiveAhead;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// An exponential moving average of durations shared between a stream and its page futures
+#[derive(Debug, Clone, Default)]
+pub struct SharedAverage(Arc<Mutex<Option<Duration>>>);
+
+impl SharedAverage {
+    pub fn record(&self, sample: Duration) {
+        let mut average = lock_unpoisoned(&self.0);
+        *average = Some(match *average {
+            Some(average) => (average * 3 + sample) / 4,
+            None => sample,
+        });
+    }
+
+    pub fn get(&self) -> Option<Duration> {
+        *lock_unpoisoned(&self.0)
+    }
+}
+
+/// Records the latency of `fut` if it's requested
+pub async fn record_latency<F>(latency: Option<SharedAverage>, fut: F) -> F::Output
+where
+    F: std::future::Future,
+{
+    match latency {
+        Some(latency) => {
+            let start = std::time::Instant::now();
+            let output = fut.await;
+            latency.record(start.elapsed());
+            output
+        }
+        None => fut.await,
+    }
+}
+
+/// Returns the amount of concurrent requests required to fetch a page per each consumer's
+/// `gap` between polls when a single request takes `latency`
+pub fn auto_window(latency: Option<Duration>, gap: Option<Duration>, max_window: usize) -> usize {
+    match (latency, gap) {
+        (None, _) => 1,
+        (Some(latency), Some(gap)) if !gap.is_zero() => {
+            let window = latency.as_nanos().div_ceil(gap.as_nanos());
+            usize::try_from(window)
+                .unwrap_or(usize::MAX)
+                .clamp(1, max_window)
+        }
+        (Some(_), _) => max_window,
+    }
+}
+
+/// An AIMD window of [`AdaptiveAhead`] shared between a stream and its page futures
+#[derive(Debug, Clone)]
+pub struct AimdWindow(Arc<Mutex<AimdState>>);
+
+#[derive(Debug)]
+struct AimdState {
+    bounds: AdaptiveAhead,
+    window: usize,
+    fast_responses: usize,
+    min_latency: Option<Duration>,
+    // Increments on every decrease, so a burst of slow responses to requests launched with the same
+    // window halves it only once
+    epoch: u64,
+}
+
+impl AimdWindow {
+    pub fn new(bounds: AdaptiveAhead) -> Self {
+        let max = bounds.max.max(bounds.min);
+
+        Self(Arc::new(Mutex::new(AimdState {
+            bounds,
+            window: bounds.initial.clamp(bounds.min, max),
+            fast_responses: 0,
+            min_latency: None,
+            epoch: 0,
+        })))
+    }
+
+    pub fn window(&self) -> usize {
+        self.lock().window
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.lock().epoch
+    }
+
+    /// Accounts a response to a request launched in the `epoch`
+    pub fn record(&self, epoch: u64, latency: Duration, is_ok: bool) {
+        let mut state = self.lock();
+        let min_latency = state.min_latency.map_or(latency, |min| min.min(latency));
+        state.min_latency = Some(min_latency);
+
+        let is_slow =
+            latency.as_secs_f64() > min_latency.as_secs_f64() * state.bounds.latency_tolerance;
+        if is_ok && !is_slow {
+            state.fast_responses += 1;
+            if state.fast_responses >= state.window {
+                state.fast_responses = 0;
+                state.window = (state.window + 1).min(state.bounds.max.max(state.bounds.min));
+            }
+        } else if epoch == state.epoch {
+            state.fast_responses = 0;
+            state.window = (state.window / 2).max(state.bounds.min);
+            state.epoch += 1;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, AimdState> {
+        lock_unpoisoned(&self.0)
+    }
+}
+
+macro_rules! pages_ahead_auto_state_def {
+    ($($extra_bounds:tt)*) => {
+        struct PagesAheadAutoState<'p, P, R>
+        where
+            P: 'p + PageTurner<R>,
+            $($extra_bounds)*
+        {
+            page_turner: P,
+            requests: RequestIter<R>,
+            in_progress: FuturesOrdered<PageTurnerFuture<'p, P, R>>,
+            last_page_queried: bool,
+            events: StreamEvents,
+            pages_queried: usize,
+            max_window: usize,
+            latency: SharedAverage,
+            consumer_gap: SharedAverage,
+            yielded_at: Option<std::time::Instant>,
+            aimd: Option<AimdWindow>,
+        }
+
+        impl<'p, P, R> PagesAheadAutoState<'p, P, R>
+        where
+            P: 'p + Clone + PageTurner<R>,
+            R: 'p + RequestAhead,
+            $($extra_bounds)*
+        {
+            fn new(
+                page_turner: P,
+                request: R,
+                max_window: usize,
+                limit: Limit,
+                adaptive: Option<AdaptiveAhead>,
+            ) -> Self {
+                let requests = RequestIter::new(request, limit);
+                let latency = requests.latency().unwrap_or_default();
+
+                Self {
+                    page_turner,
+                    requests,
+                    in_progress: FuturesOrdered::new(),
+                    last_page_queried: false,
+                    events: StreamEvents::new("pages_ahead_auto"),
+                    pages_queried: 0,
+                    max_window: max_window.max(1),
+                    latency,
+                    consumer_gap: SharedAverage::default(),
+                    yielded_at: None,
+                    aimd: adaptive.map(AimdWindow::new),
+                }
+            }
+
+            /// Schedules requests until the window sized for the measured latency and consumer
+            /// speed or the AIMD window is full
+            fn fill_window(&mut self) {
+                let window = match &self.aimd {
+                    Some(aimd) => aimd.window(),
+                    None => auto_window(self.latency.get(), self.consumer_gap.get(), self.max_window),
+                };
+
+                while self.in_progress.len() < window {
+                    let Some(request) = self.requests.next() else {
+                        break;
+                    };
+
+                    let local_page_turner = self.page_turner.clone();
+                    let events = self.events;
+                    let latency = self.latency.clone();
+                    let aimd = self.aimd.clone();
+                    let page = self.pages_queried;
+                    let in_flight = self.in_progress.len();
+                    self.pages_queried += 1;
+
+                    self.in_progress.push_back(Box::pin(async move {
+                        let page_future = events.turn_page(page, in_flight, local_page_turner.turn_page(request));
+                        let Some(aimd) = aimd else {
+                            return record_latency(Some(latency), page_future).await;
+                        };
+
+                        let epoch = aimd.epoch();
+                        let start = std::time::Instant::now();
+                        let result = record_latency(Some(latency), page_future).await;
+                        aimd.record(epoch, start.elapsed(), result.is_ok());
+                        result
+                    }));
+                }
+            }
+        }
+    };
+}
+
+macro_rules! request_pages_ahead_auto_decl {
+    ($($extra_bounds:tt)*) => {
+        async fn request_pages_ahead_auto<'p, P, R>(
+            mut state: Box<PagesAheadAutoState<'p, P, R>>,
+        ) -> Result<Option<(PageItems<P, R>, Box<PagesAheadAutoState<'p, P, R>>)>, PageError<P, R>>
+        where
+            P: 'p + Clone + PageTurner<R>,
+            R: 'p + RequestAhead,
+            $($extra_bounds)*
+        {
+            if state.last_page_queried {
+                return Ok(None);
+            }
+
+            if let Some(yielded_at) = state.yielded_at.take() {
+                state.consumer_gap.record(yielded_at.elapsed());
+            }
+
+            state.fill_window();
+
+            match state.in_progress.try_next().await? {
+                Some(TurnedPage {
+                    items,
+                    next_request,
+                }) => {
+                    state.last_page_queried = next_request.is_none();
+                    state.yielded_at = Some(std::time::Instant::now());
+                    Ok(Some((items, state)))
+                }
+                None => Ok(None),
+            }
+        }
+    };
+}
+
+pub(crate) use pages_ahead_auto_state_def;
+pub(crate) use request_pages_ahead_auto_decl;
@@ -1,15 +1,32 @@
+/// The index of injected pages which don't belong to the sequence of generated requests
+pub const INJECTED_PAGE: usize = usize::MAX;
+
+/// A page completed by the unordered scheduler
+pub enum Completed<T> {
+    /// The page with the `num` index in the requests generation order
+    Page(usize, T),
+    /// A page requested with a [`crate::inject::RequestInjector`]
+    Injected(T),
+}
+
 macro_rules! pages_ahead_unordered_state_def {
     ($($extra_bounds:tt)*) => {
-        struct PagesAheadUnorderedState<'p, P, R>
+        struct PagesAheadUnorderedState<'p, P, R, I = RequestIter<R>>
         where
             P: 'p + PageTurner<R>,
             $($extra_bounds)*
         {
             page_turner: P,
-            numbered_requests: EnumerableRequestChunks<R>,
+            numbered_requests: Chunks<NumberedRequests<R, I>>,
             in_progress: FuturesUnordered<NumberedRequestFuture<'p, P, R>>,
             first_error: Option<(usize, PageError<P, R>)>,
             last_page: Option<usize>,
+            stats: OverqueryStats,
+            events: StreamEvents,
+            latency: Option<SharedAverage>,
+            requests_ahead_count: usize,
+            injector: Option<RequestInjector<R>>,
+            injected: FuturesUnordered<PageTurnerFuture<'p, P, R>>,
         }
 
         impl<'p, P, R> PagesAheadUnorderedState<'p, P, R>
@@ -19,64 +36,231 @@ impl<'p, P, R> PagesAheadUnorderedState<'p, P, R>
             $($extra_bounds)*
         {
             fn new(page_turner: P, request: R, chunk_size: usize, limit: Limit) -> Self {
-                let numbered_requests = RequestIter::new(request, limit)
-                    .enumerate()
-                    .chunks(chunk_size);
+                let requests = RequestIter::new(request, limit);
+                let latency = requests.latency();
+
+                Self::with_requests(
+                    page_turner,
+                    NumberedRequests::sequential(requests),
+                    chunk_size,
+                    latency,
+                )
+            }
+        }
+
+        impl<'p, P, R, I> PagesAheadUnorderedState<'p, P, R, I>
+        where
+            P: 'p + PageTurner<R>,
+            R: 'p,
+            I: Iterator<Item = R>,
+            $($extra_bounds)*
+        {
 
+            fn with_requests(
+                page_turner: P,
+                numbered_requests: NumberedRequests<R, I>,
+                chunk_size: usize,
+                latency: Option<SharedAverage>,
+            ) -> Self {
                 Self {
                     page_turner,
-                    numbered_requests,
+                    numbered_requests: numbered_requests.chunks(chunk_size),
                     in_progress: FuturesUnordered::new(),
                     first_error: None,
                     last_page: None,
+                    stats: OverqueryStats::default(),
+                    events: StreamEvents::new("pages_ahead_unordered"),
+                    latency,
+                    requests_ahead_count: chunk_size,
+                    injector: None,
+                    injected: FuturesUnordered::new(),
                 }
             }
 
+            fn with_injector(mut self, injector: RequestInjector<R>) -> Self {
+                self.injector = Some(injector);
+                self
+            }
+
+            fn free_slots(&self) -> usize {
+                self.requests_ahead_count
+                    .saturating_sub(self.in_progress.len() + self.injected.len())
+            }
+
+            fn has_injected(&self) -> bool {
+                !self.injected.is_empty()
+                    || self.injector.as_ref().is_some_and(|injector| !injector.is_empty())
+            }
+
+            /// Follows the pagination of an injected request and returns its items
+            fn injected_page(
+                &mut self,
+                result: Result<TurnedPage<PageItems<P, R>, R>, PageError<P, R>>,
+            ) -> Result<PageItems<P, R>, PageError<P, R>> {
+                let TurnedPage { items, next_request } = result?;
+
+                if let (Some(injector), Some(next_request)) = (&self.injector, next_request) {
+                    injector.push(next_request);
+                }
+
+                Ok(items)
+            }
+
             /// Updates the error so that an error with the least `new_err_num` remains while other ones
             /// get discarded
             fn update_err(&mut self, new_err_num: usize, new_err: PageError<P, R>) {
+                self.numbered_requests.iter_mut().truncate(new_err_num);
+
                 match &self.first_error {
                     Some((old_err_num, _)) if new_err_num < *old_err_num => {
+                        self.stats.record_discarded_error();
                         self.first_error = Some((new_err_num, new_err));
                     }
-                    Some(_) => {}
+                    Some(_) => self.stats.record_discarded_error(),
                     None => self.first_error = Some((new_err_num, new_err)),
                 }
             }
+
+            fn set_last_page(&mut self, num: usize) {
+                self.numbered_requests.iter_mut().truncate(num);
+                self.last_page = Some(num);
+            }
+
+            fn schedule(&mut self, (num, request): (usize, R))
+            where
+                P: Clone,
+            {
+                self.stats.record_request();
+                let local_page_turner = self.page_turner.clone();
+                let events = self.events;
+                let latency = self.latency.clone();
+                let in_flight = self.in_progress.len();
+                self.in_progress.push(Box::pin(async move {
+                    let page_future = events.turn_page(num, in_flight, local_page_turner.turn_page(request));
+                    (num, record_latency(latency, page_future).await)
+                }));
+            }
+
+            /// Schedules a remaining request before the last page or the first error. Sequential
+            /// requests are all past them, but prioritized ones may still cover earlier pages.
+            fn schedule_remaining(&mut self)
+            where
+                P: Clone,
+            {
+                if self.free_slots() == 0 {
+                    return;
+                }
+
+                if let Some(req) = self.numbered_requests.next_item() {
+                    self.schedule(req);
+                }
+            }
+
+            /// Injected requests take free slots before the remaining requests
+            fn schedule_injected(&mut self)
+            where
+                P: Clone,
+            {
+                let Some(injector) = self.injector.clone() else {
+                    return;
+                };
+
+                while self.free_slots() > 0 {
+                    let Some(request) = injector.pop() else {
+                        break;
+                    };
+
+                    let local_page_turner = self.page_turner.clone();
+                    self.injected
+                        .push(Box::pin(async move { local_page_turner.turn_page(request).await }));
+                }
+            }
+
+            /// Awaits the next page requested by the stream or injected into it
+            async fn next_completed(
+                &mut self,
+            ) -> Option<Completed<Result<TurnedPage<PageItems<P, R>, R>, PageError<P, R>>>>
+            where
+                P: Clone,
+            {
+                self.schedule_injected();
+
+                match (self.in_progress.is_empty(), self.injected.is_empty()) {
+                    (true, true) => None,
+                    (false, true) => self
+                        .in_progress
+                        .next()
+                        .await
+                        .map(|(num, result)| Completed::Page(num, result)),
+                    (true, false) => self.injected.next().await.map(Completed::Injected),
+                    (false, false) => {
+                        match futures::future::select(self.in_progress.next(), self.injected.next())
+                            .await
+                        {
+                            futures::future::Either::Left((page, _)) => {
+                                page.map(|(num, result)| Completed::Page(num, result))
+                            }
+                            futures::future::Either::Right((page, _)) => {
+                                page.map(Completed::Injected)
+                            }
+                        }
+                    }
+                }
+            }
         }
     };
 }
 
 macro_rules! request_pages_ahead_unordered_decl {
     ($($extra_bounds:tt)*) => {
-        async fn request_pages_ahead_unordered<'p, P, R>(
-            mut state: Box<PagesAheadUnorderedState<'p, P, R>>,
-        ) -> Result<Option<(PageItems<P, R>, Box<PagesAheadUnorderedState<'p, P, R>>)>, PageError<P, R>>
+        async fn request_pages_ahead_unordered<'p, P, R, I>(
+            mut state: Box<PagesAheadUnorderedState<'p, P, R, I>>,
+        ) -> Result<
+            Option<((usize, PageItems<P, R>), Box<PagesAheadUnorderedState<'p, P, R, I>>)>,
+            PageError<P, R>,
+        >
         where
             P: 'p + Clone + PageTurner<R>,
-            R: 'p + RequestAhead,
+            R: 'p,
+            I: Iterator<Item = R>,
             $($extra_bounds)*
         {
             // This and nested loops are required to discard all errors except the error for the first failed request without yielding them to the user.
             loop {
                 // Once we're in this branch no code below will be executed
                 if let Some(last_page_num) = state.last_page {
-                    while let Some((num, result)) = state.in_progress.next().await {
-                        match result {
-                            Ok(turned_page) => return Ok(Some((turned_page.items, state))),
-                            Err(new_err) => {
+                    state.schedule_remaining();
+                    while let Some(completed) = state.next_completed().await {
+                        match completed {
+                            Completed::Page(num, Ok(turned_page)) => {
+                                return Ok(Some(((num, turned_page.items), state)));
+                            }
+                            Completed::Page(num, Err(new_err)) => {
                                 state.update_err(num, new_err);
                             }
+                            Completed::Injected(result) => {
+                                return state
+                                    .injected_page(result)
+                                    .map(|items| Some(((INJECTED_PAGE, items), state)));
+                            }
                         }
+
+                        state.schedule_remaining();
                     }
 
+                    state.stats.record_last_page(last_page_num);
+
                     match state.first_error.take() {
                         Some((err_num, err)) if err_num <= last_page_num => {
                             return Err(err);
                         }
                         // If an error occured past the last existing page it will be discarded at this
                         // point
-                        _ => {
+                        Some(_) => {
+                            state.stats.record_discarded_error();
+                            return Ok(None);
+                        }
+                        None => {
                             return Ok(None);
                         }
                     }
@@ -84,17 +268,23 @@ async fn request_pages_ahead_unordered<'p, P, R>(
 
                 // Once we're in this branch no code below will be executed
                 while state.first_error.is_some() {
-                    match state.in_progress.next().await {
-                        Some((num, result)) => match result {
+                    state.schedule_remaining();
+                    match state.next_completed().await {
+                        Some(Completed::Injected(result)) => {
+                            return state
+                                .injected_page(result)
+                                .map(|items| Some(((INJECTED_PAGE, items), state)));
+                        }
+                        Some(Completed::Page(num, result)) => match result {
                             Ok(TurnedPage {
                                 items,
                                 next_request,
                             }) => {
                                 if next_request.is_none() {
-                                    state.last_page = Some(num);
+                                    state.set_last_page(num);
                                 }
 
-                                return Ok(Some((items, state)));
+                                return Ok(Some(((num, items), state)));
                             }
                             Err(new_err) => state.update_err(num, new_err),
                         },
@@ -106,17 +296,25 @@ async fn request_pages_ahead_unordered<'p, P, R>(
 
                 // Schedule
                 if state.in_progress.is_empty() {
-                    // Initial schedule of the first futures chunk
-                    match state.numbered_requests.next_chunk() {
+                    // Initial schedule of the first futures chunk. Injected requests may occupy
+                    // some of the slots.
+                    // The chunk borrows the requests so collect it before scheduling
+                    let chunk: Option<Vec<_>> = match state.free_slots() {
+                        0 => None,
+                        free_slots => state
+                            .numbered_requests
+                            .next_chunk()
+                            .map(|chunk| chunk.take(free_slots).collect()),
+                    };
+
+                    match chunk {
                         // If chunk is some then there is at least 1 request inside
                         Some(chunk) => {
                             for req in chunk {
-                                let local_page_turner = state.page_turner.clone();
-                                state.in_progress.push(Box::pin(async move {
-                                    (req.0, local_page_turner.turn_page(req.1).await)
-                                }));
+                                state.schedule(req);
                             }
                         }
+                        None if state.has_injected() => {}
                         None => {
                             return Ok(None);
                         }
@@ -124,25 +322,25 @@ async fn request_pages_ahead_unordered<'p, P, R>(
                 } else {
                     // At this point one of the first requests succeeded. Lets push the next one from the next_chunk to proceed in
                     // a sliding window maner.
-                    if let Some(req) = state.numbered_requests.next_item() {
-                        let local_page_turner = state.page_turner.clone();
-                        state.in_progress.push(Box::pin(async move {
-                            (req.0, local_page_turner.turn_page(req.1).await)
-                        }))
-                    }
+                    state.schedule_remaining();
                 }
 
-                match state.in_progress.next().await {
-                    Some((num, result)) => match result {
+                match state.next_completed().await {
+                    Some(Completed::Injected(result)) => {
+                        return state
+                            .injected_page(result)
+                            .map(|items| Some(((INJECTED_PAGE, items), state)));
+                    }
+                    Some(Completed::Page(num, result)) => match result {
                         Ok(TurnedPage {
                             items,
                             next_request,
                         }) => {
                             if next_request.is_none() {
-                                state.last_page = Some(num);
+                                state.set_last_page(num);
                             }
 
-                            return Ok(Some((items, state)));
+                            return Ok(Some(((num, items), state)));
                         }
                         // Don't return an error immediately, continue the loop to find the one for the
                         // first failed page instead, or to discard an error if it occured past the last existing page
@@ -158,5 +356,87 @@ async fn request_pages_ahead_unordered<'p, P, R>(
     };
 }
 
+macro_rules! pages_ahead_unordered_stream_def {
+    ($($extra_bounds:tt)*) => {
+        /// A stream returned by [`PageTurner::pages_ahead_unordered`] and
+        /// [`PageTurner::into_pages_ahead_unordered`]. Its [`OverqueryStats`] show how many
+        /// requests were wasted past the last existing page and how many errors were discarded.
+        pub struct PagesAheadUnordered<'p, T, E> {
+            stream: PagesBoxStream<'p, Result<(usize, T), E>>,
+            stats: OverqueryStats,
+            stream_id: StreamId,
+        }
+
+        impl<'p, T, E> PagesAheadUnordered<'p, T, E> {
+            fn new<P, R, I>(state: PagesAheadUnorderedState<'p, P, R, I>) -> Self
+            where
+                P: 'p + Clone + PageTurner<R, PageItems = T, PageError = E>,
+                R: 'p,
+                I: 'p + Iterator<Item = R>,
+                $($extra_bounds)*
+            {
+                Self {
+                    stats: state.stats.clone(),
+                    stream_id: state.events.id(),
+                    stream: Box::pin(stream::try_unfold(
+                        Box::new(state),
+                        request_pages_ahead_unordered,
+                    )),
+                }
+            }
+
+            /// Returns a handle to statistics of the stream which remains valid after the stream
+            /// is consumed
+            pub fn stats(&self) -> OverqueryStats {
+                self.stats.clone()
+            }
+
+            /// Returns the identifier of this stream
+            pub fn stream_id(&self) -> StreamId {
+                self.stream_id
+            }
+
+            /// Makes the stream yield pages along with their indices in the requests generation
+            /// order. The page of the initial request has the index 0.
+            pub fn indexed(self) -> IndexedPagesAheadUnordered<'p, T, E> {
+                IndexedPagesAheadUnordered(self.stream)
+            }
+        }
+
+        impl<'p, T, E> Stream for PagesAheadUnordered<'p, T, E> {
+            type Item = Result<T, E>;
+
+            fn poll_next(
+                mut self: Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<Option<Self::Item>> {
+                self.stream
+                    .as_mut()
+                    .poll_next(cx)
+                    .map(|page| page.map(|result| result.map(|(_, items)| items)))
+            }
+        }
+
+        crate::internal::async_iterator_impl!(['p, T, E] PagesAheadUnordered<'p, T, E>);
+
+        /// A stream returned by [`PagesAheadUnordered::indexed`]
+        pub struct IndexedPagesAheadUnordered<'p, T, E>(PagesBoxStream<'p, Result<(usize, T), E>>);
+
+        impl<'p, T, E> Stream for IndexedPagesAheadUnordered<'p, T, E> {
+            type Item = Result<(usize, T), E>;
+
+            fn poll_next(
+                mut self: Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<Option<Self::Item>> {
+                self.0.as_mut().poll_next(cx)
+            }
+        }
+
+        crate::internal::async_iterator_impl!(['p, T, E] IndexedPagesAheadUnordered<'p, T, E>);
+    };
+}
+
 pub(crate) use pages_ahead_unordered_state_def;
+pub(crate) use pages_ahead_unordered_stream_def;
 pub(crate) use request_pages_ahead_unordered_decl;
@@ -0,0 +1,54 @@
+use futures::{stream, Stream, TryStreamExt};
+
+/// Yields pages of `stream` until `pred` returns false for one of them. That page is still yielded
+/// but `stream` is dropped right away, so neither the next page is requested nor the requests
+/// that are already in flight are awaited.
+pub fn pages_while<S, T, E, F>(stream: S, pred: F) -> impl Stream<Item = Result<T, E>>
+where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(&T) -> bool,
+{
+    stream::try_unfold(
+        (Some(Box::pin(stream)), pred),
+        |(stream, mut pred)| async move {
+            let Some(mut stream) = stream else {
+                return Ok(None);
+            };
+
+            Ok(stream.try_next().await?.map(|items| {
+                let stream = pred(&items).then_some(stream);
+                (items, (stream, pred))
+            }))
+        },
+    )
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use crate::adapters::slice::{SlicePage, SlicePageTurner};
+    use crate::mt::prelude::*;
+    use crate::stats::{Measured, StreamStats};
+    use futures::TryStreamExt;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn cancels_requests_ahead() {
+        let stats = StreamStats::new();
+        let numbers = Measured::new(
+            SlicePageTurner::new((0..20).collect::<Vec<u8>>(), 1).delay(Duration::from_millis(20)),
+            stats.clone(),
+        );
+
+        let pages: Vec<_> = numbers
+            .pages_ahead_while(4, Limit::None, SlicePage(0), |page| page[0] < 1)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(pages, [vec![0], vec![1]]);
+
+        let completed = stats.requests();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(stats.requests(), completed, "Requests ahead are cancelled");
+        assert!(completed < 20);
+    }
+}
@@ -0,0 +1,95 @@
+macro_rules! tail_state_def {
+    ($($extra_bounds:tt)*) => {
+        struct TailState<P, R, S>
+        where
+            P: PageTurner<R>,
+            $($extra_bounds)*
+        {
+            page_turner: P,
+            store: S,
+            cursor: Option<R>,
+            unsaved_cursor: Option<R>,
+            cursor_loaded: bool,
+            caught_up: bool,
+            interval: std::time::Duration,
+        }
+
+        impl<P, R, S> TailState<P, R, S>
+        where
+            P: PageTurner<R>,
+            $($extra_bounds)*
+        {
+            fn new(page_turner: P, store: S, request: R, interval: std::time::Duration) -> Self {
+                Self {
+                    page_turner,
+                    store,
+                    cursor: Some(request),
+                    unsaved_cursor: None,
+                    cursor_loaded: false,
+                    caught_up: false,
+                    interval,
+                }
+            }
+        }
+    };
+}
+
+macro_rules! request_tail_page_decl {
+    ($($extra_bounds:tt)*) => {
+        #[allow(clippy::type_complexity)]
+        async fn request_tail_page<P, R, S>(
+            mut state: Box<TailState<P, R, S>>,
+        ) -> Result<
+            Option<(PageItems<P, R>, Box<TailState<P, R, S>>)>,
+            TailError<PageError<P, R>, S::Error>,
+        >
+        where
+            P: PageTurner<R>,
+            R: Clone,
+            S: CursorStore<R>,
+            $($extra_bounds)*
+        {
+            if !state.cursor_loaded {
+                if let Some(cursor) = state.store.load().await.map_err(TailError::Store)? {
+                    state.cursor = Some(cursor);
+                }
+
+                state.cursor_loaded = true;
+            }
+
+            // The previous page is considered processed once the consumer polls the next one
+            if let Some(cursor) = state.unsaved_cursor.take() {
+                state.store.save(&cursor).await.map_err(TailError::Store)?;
+            }
+
+            if state.caught_up {
+                futures_timer::Delay::new(state.interval).await;
+            }
+
+            let request = match state.cursor.take() {
+                Some(request) => request,
+                None => unreachable!("BUG(page-turner): The cursor is restored after each page"),
+            };
+
+            let TurnedPage {
+                items,
+                next_request,
+            } = state
+                .page_turner
+                .turn_page(request.clone())
+                .await
+                .map_err(TailError::Page)?;
+
+            // The last page is polled again after the interval to pick up new items
+            state.caught_up = next_request.is_none();
+            let cursor = next_request.unwrap_or(request);
+            state.unsaved_cursor = Some(cursor.clone());
+            state.cursor = Some(cursor);
+
+            Ok(Some((items, state)))
+        }
+    };
+}
+
+pub(crate) use request_tail_page_decl;
+pub(crate) use tail_state_def;
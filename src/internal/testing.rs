@@ -0,0 +1,54 @@
+macro_rules! assert_pagination_equivalent_decl {
+    ($($extra_bounds:tt)*) => {
+        /// Queries pages with [`PageTurner::pages`] and [`PageTurner::pages_ahead`] and asserts
+        /// that both streams yield identical pages. Use it in tests of your page turners to verify
+        /// that their [`RequestAhead`] implementations are safe for concurrent querying.
+        ///
+        /// With [`Limit::Pages`] only the first pages of the `pages` stream are compared, with
+        /// [`Limit::Duration`] only as many pages as `pages_ahead` managed to query are compared.
+        /// Both apply to [`Limit::All`].
+        ///
+        /// # Panics
+        ///
+        /// Panics if any of the streams fails or if pages differ.
+        pub async fn assert_pagination_equivalent<P, R>(
+            page_turner: &P,
+            request: R,
+            requests_ahead_count: usize,
+            limit: Limit,
+        ) where
+            P: PageTurner<R>,
+            R: RequestAhead + Clone,
+            PageItems<P, R>: std::fmt::Debug + PartialEq,
+            PageError<P, R>: std::fmt::Debug,
+            $($extra_bounds)*
+        {
+            let max_pages = limit.pages().unwrap_or(usize::MAX);
+
+            let mut expected: Vec<_> = page_turner
+                .pages(request.clone())
+                .take(max_pages)
+                .try_collect()
+                .await
+                .unwrap_or_else(|e| panic!("`pages` stream failed: {e:?}"));
+
+            let actual: Vec<_> = page_turner
+                .pages_ahead(requests_ahead_count, limit, request)
+                .try_collect()
+                .await
+                .unwrap_or_else(|e| panic!("`pages_ahead` stream failed: {e:?}"));
+
+            if limit.duration().is_some() {
+                expected.truncate(actual.len());
+            }
+
+            assert_eq!(
+                expected, actual,
+                "`pages` and `pages_ahead` streams yielded different pages, make sure that \
+                 `RequestAhead::next_request` produces the same requests as `turn_page`"
+            );
+        }
+    };
+}
+
+pub(crate) use assert_pagination_equivalent_decl;
@@ -1,6 +1,12 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(feature = "nightly-async-iter", feature(async_iterator))]
 #![doc = include_str!("doc/Main.md")]
 
+use std::sync::{
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+    Arc,
+};
+
 #[cfg(feature = "local")]
 #[cfg_attr(docsrs, doc(cfg(feature = "local")))]
 pub mod local;
@@ -8,6 +14,34 @@
 #[cfg(feature = "mt")]
 pub mod mt;
 
+pub mod adapters;
+pub mod audit;
+pub mod best_effort;
+pub mod bidirectional;
+pub mod blocking;
+pub mod cache;
+pub mod cancel;
+pub mod chain;
+pub mod combinators;
+pub mod concurrency;
+pub mod config;
+pub mod inject;
+pub mod merge;
+pub mod position;
+pub mod progress;
+pub mod retry;
+pub mod server;
+pub mod shared;
+pub mod split;
+pub mod stats;
+pub mod strict;
+pub mod summary;
+pub mod throttle;
+pub mod throughput;
+pub mod timeout;
+pub mod watch;
+pub mod watchdog;
+
 #[cfg(feature = "mutable")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mutable")))]
 pub use local::mutable;
@@ -16,6 +50,10 @@
 #[cfg_attr(docsrs, doc(cfg(feature = "dynamic")))]
 pub use mt::dynamic;
 
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use page_turner_macros::{OffsetPagination, RequestAhead};
+
 // `mt` is enabled by default so prelude reexports the mt::prelude. Users will need to specify a
 // prelude module manually like `page_turner::local::prelude*` if they want to use other flavours
 // of page turner.
@@ -33,6 +71,8 @@ pub mod prelude {
 /// next page. If `next_request` is `None` `PageTurner` stops querying pages.
 ///
 /// [`TurnedPage::next`] and [`TurnedPage::last`] constructors can be used for convenience.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TurnedPage<I, R> {
     pub items: I,
     pub next_request: Option<R>,
@@ -68,13 +108,37 @@ pub fn last(items: I) -> Self {
 /// # Caveats
 ///
 /// - Ensure that page turner's `turn_page` returns [`TurnedPage::last`] at some point or that you
-/// always use [`Limit::Pages`] in `*pages_ahead*` methods, otherwise `*pages_ahead*` streams will
-/// always end with errors.
+///   always use [`Limit::Pages`] in `*pages_ahead*` methods, otherwise `*pages_ahead*` streams will
+///   always end with errors.
 ///
 /// - Ensure that page turner's `turn_page` produces equivalent next requests that query the same
-/// data so that `*pages_ahead*` streams and `pages` stream yield the same results.
+///   data so that `*pages_ahead*` streams and `pages` stream yield the same results.
 pub trait RequestAhead {
     fn next_request(&self) -> Self;
+
+    /// Returns the request `n` pages after the next one, so `nth_request(0)` is equivalent to
+    /// [`RequestAhead::next_request`]. The default implementation calls `next_request` `n + 1`
+    /// times, override it if the request can jump to an arbitrary page directly.
+    fn nth_request(&self, n: usize) -> Self
+    where
+        Self: Sized,
+    {
+        let mut request = self.next_request();
+        for _ in 0..n {
+            request = request.next_request();
+        }
+
+        request
+    }
+}
+
+/// State accumulated over responses of a sequential pages stream for APIs that need more than the
+/// latest response to build the next request, i.e. a sum of returned counts or the latest
+/// timestamp seen. Pass it to `pages_with_state` instead of smuggling it inside the page turner.
+pub trait PaginationState<R, I> {
+    /// Accounts the turned page and returns the request for the next page, `None` ends the stream.
+    /// `next_request` is the one returned by `turn_page`.
+    fn next_request(&mut self, items: &I, next_request: Option<R>) -> Option<R>;
 }
 
 /// If you use `pages_ahead` or `pages_ahead_unordered` families of methods and you know in advance
@@ -82,10 +146,270 @@ pub trait RequestAhead {
 /// the last existing page from being executed.
 #[allow(dead_code)]
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum Limit {
     #[default]
     None,
     Pages(usize),
+    /// Stops sending requests once the duration passes since the stream creation. Requests that
+    /// are expected to complete past the deadline judging by the average latency of previous
+    /// requests aren't sent either, while pages of already sent requests are still yielded.
+    Duration(std::time::Duration),
+    /// Stops at whichever of the limits is reached first. Build it with [`Limit::and`].
+    All {
+        pages: usize,
+        duration: std::time::Duration,
+    },
+}
+
+impl Limit {
+    /// Combines limits into one that's reached once any of them is reached, i.e.
+    /// `Limit::Pages(100).and(Limit::Duration(budget))`. Combine it with `PagesStream::limit_items`
+    /// to cap the amount of items as well.
+    ///
+    /// # Limits of the same kind
+    ///
+    /// Limits of the same kind are combined into the stricter one: the lower amount of pages and
+    /// the shorter duration, i.e. `Limit::Pages(5).and(Limit::Pages(10))` is `Limit::Pages(5)`.
+    /// [`Limit::None`] doesn't restrict anything and is ignored.
+    pub fn and(self, other: Limit) -> Limit {
+        fn min<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            }
+        }
+
+        match (
+            min(self.pages(), other.pages()),
+            min(self.duration(), other.duration()),
+        ) {
+            (None, None) => Limit::None,
+            (Some(pages), None) => Limit::Pages(pages),
+            (None, Some(duration)) => Limit::Duration(duration),
+            (Some(pages), Some(duration)) => Limit::All { pages, duration },
+        }
+    }
+
+    /// The maximum amount of pages to query if there is one
+    pub fn pages(&self) -> Option<usize> {
+        match *self {
+            Limit::Pages(pages) | Limit::All { pages, .. } => Some(pages),
+            Limit::None | Limit::Duration(_) => None,
+        }
+    }
+
+    /// The time budget of the stream if there is one
+    pub fn duration(&self) -> Option<std::time::Duration> {
+        match *self {
+            Limit::Duration(duration) | Limit::All { duration, .. } => Some(duration),
+            Limit::None | Limit::Pages(_) => None,
+        }
+    }
+}
+
+/// Bounds of a lookahead window that grows and shrinks with observed latency, see
+/// `PagesBuilder::ahead_adaptive`.
+///
+/// The window follows AIMD: it grows by one request after a whole window of fast responses and is
+/// halved after a response slower than `latency_tolerance` times the fastest one seen so far.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveAhead {
+    pub(crate) initial: usize,
+    pub(crate) min: usize,
+    pub(crate) max: usize,
+    pub(crate) latency_tolerance: f64,
+}
+
+impl AdaptiveAhead {
+    /// Starts with a single request in flight and grows up to `max` requests. `0` is treated as
+    /// `1`.
+    pub fn new(max: usize) -> Self {
+        Self {
+            initial: 1,
+            min: 1,
+            max: max.max(1),
+            latency_tolerance: 2.0,
+        }
+    }
+
+    /// The amount of requests in flight the stream starts with
+    pub fn initial(mut self, initial: usize) -> Self {
+        self.initial = initial;
+        self
+    }
+
+    /// The amount of requests in flight the window never shrinks below. `0` is treated as `1`.
+    pub fn min(mut self, min: usize) -> Self {
+        self.min = min.max(1);
+        self
+    }
+
+    /// How many times a response may be slower than the fastest one before the window shrinks.
+    /// Defaults to `2.0`.
+    pub fn latency_tolerance(mut self, latency_tolerance: f64) -> Self {
+        self.latency_tolerance = latency_tolerance;
+        self
+    }
+}
+
+/// Whether `pages_from_requests` yields concurrently queried pages in the order of requests
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulingMode {
+    /// Yields pages in the order of requests like `pages_ahead`
+    #[default]
+    Ordered,
+    /// Yields pages as soon as they become available like `pages_ahead_unordered`
+    Unordered,
+}
+
+/// A type-erased page error. See `PagesStream::pages_boxed_err`.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The amount of pages or items a stream is known to yield in advance, i.e. from a total count
+/// reported by an API. See `PagesStream::with_progress`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Total {
+    Pages(usize),
+    Items(usize),
+}
+
+/// Completion of a stream yielded along with each page by `PagesStream::with_progress`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Progress {
+    /// The amount of pages or items yielded so far, depending on the [`Total`] variant
+    pub done: usize,
+    pub total: usize,
+    /// `done / total` in the `0.0..=1.0` range. It's `1.0` when the total is `0` or when the
+    /// stream yields more than the total.
+    pub fraction: f64,
+}
+
+impl Progress {
+    #[cfg(any(feature = "mt", feature = "local"))]
+    pub(crate) fn new(done: usize, total: usize) -> Self {
+        let fraction = if total == 0 {
+            1.0
+        } else {
+            (done as f64 / total as f64).min(1.0)
+        };
+
+        Self {
+            done,
+            total,
+            fraction,
+        }
+    }
+}
+
+/// Outcomes of page handlers run by `PagesStream::process_pages_concurrently`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessSummary<E, H> {
+    /// The amount of pages processed successfully
+    pub processed: usize,
+    /// Errors of failed handlers in the order of their completion
+    pub failed: Vec<H>,
+    /// The error of the pages stream that ended the processing early
+    pub page_error: Option<E>,
+}
+
+impl<E, H> ProcessSummary<E, H> {
+    /// Returns `true` if all pages were queried and processed without errors
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty() && self.page_error.is_none()
+    }
+
+    #[cfg(any(feature = "mt", feature = "local"))]
+    pub(crate) fn record(&mut self, outcome: Result<Result<(), H>, E>) {
+        match outcome {
+            Ok(Ok(())) => self.processed += 1,
+            Ok(Err(e)) => self.failed.push(e),
+            Err(e) => self.page_error = Some(e),
+        }
+    }
+}
+
+impl<E, H> Default for ProcessSummary<E, H> {
+    fn default() -> Self {
+        Self {
+            processed: 0,
+            failed: Vec::new(),
+            page_error: None,
+        }
+    }
+}
+
+/// A process wide unique identifier of a pages stream. Use it to tell apart logs and errors of
+/// concurrent streams, it's also reported as `stream_id` in events of the `log-json` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StreamId(u64);
+
+impl StreamId {
+    #[cfg(any(feature = "mt", feature = "local"))]
+    pub(crate) fn next() -> Self {
+        static NEXT_STREAM_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_STREAM_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for StreamId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "stream-{}", self.0)
+    }
+}
+
+/// Request statistics of a `pages_ahead_unordered` stream which intentionally queries pages past
+/// the last existing one and discards errors of such requests. Use it to tune
+/// `requests_ahead_count` and [`Limit`]. Cloning is cheap and all clones observe the same stream.
+#[derive(Debug, Clone, Default)]
+pub struct OverqueryStats(Arc<OverqueryCounters>);
+
+#[derive(Debug, Default)]
+struct OverqueryCounters {
+    requests_sent: AtomicUsize,
+    wasted_requests: AtomicUsize,
+    discarded_errors: AtomicUsize,
+}
+
+impl OverqueryStats {
+    /// The total amount of `turn_page` calls made by the stream
+    pub fn requests_sent(&self) -> usize {
+        self.0.requests_sent.load(Ordering::Relaxed)
+    }
+
+    /// The amount of requests sent for pages past the last existing page. It's only known after
+    /// the last page was found and the stream has ended.
+    pub fn wasted_requests(&self) -> usize {
+        self.0.wasted_requests.load(Ordering::Relaxed)
+    }
+
+    /// The amount of errors that were never yielded by the stream
+    pub fn discarded_errors(&self) -> usize {
+        self.0.discarded_errors.load(Ordering::Relaxed)
+    }
+
+    #[cfg(any(feature = "mt", feature = "local"))]
+    pub(crate) fn record_request(&self) {
+        self.0.requests_sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[cfg(any(feature = "mt", feature = "local"))]
+    pub(crate) fn record_discarded_error(&self) {
+        self.0.discarded_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[cfg(any(feature = "mt", feature = "local"))]
+    pub(crate) fn record_last_page(&self, last_page_num: usize) {
+        let wasted = self.requests_sent().saturating_sub(last_page_num + 1);
+        self.0.wasted_requests.store(wasted, Ordering::Relaxed);
+    }
 }
 
 mod internal;
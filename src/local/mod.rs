@@ -4,14 +4,40 @@
 use crate::internal::*;
 use futures::{
     stream::{self, FuturesOrdered, FuturesUnordered},
-    Stream, StreamExt, TryStreamExt,
+    Stream, StreamExt, TryStream, TryStreamExt,
+};
+use std::{
+    convert::Infallible,
+    future::{self, Future},
+    pin::Pin,
 };
-use std::{future::Future, pin::Pin};
 
-pub use crate::{Limit, RequestAhead, TurnedPage};
+use crate::bidirectional::{Backward, WithPrev};
+use crate::blocking::{BlockOn, BlockingPages, ParkThread};
+use crate::cancel::{Cancellable, Cancellation};
+use crate::chain::{ChainState, ChainSummary};
+use crate::combinators::{AndThenPage, MapErr, MapItems, OrElse};
+pub use crate::inject::RequestInjector;
+use crate::merge::{select_pages, SelectPages};
+use crate::progress::{ProgressHandle, Reporting};
+use crate::retry::{Retry, RetryPolicy};
+pub use crate::split::{SplitItems, SplitPages};
+use crate::stats::{StreamStats, WithStats};
+pub use crate::summary::{RunOutcome, RunReport, RunSummary, Summarized};
+use crate::throttle::{Rate, Throttled};
+pub use crate::throughput::{Throughput, ThroughputStats};
+use crate::timeout::{Timeout, TimeoutError};
+// Tail macros expect the cursor store trait of the flavor under the `CursorStore` name
+use crate::watch::{HighWaterMark, KeyOrder, LocalCursorStore as CursorStore};
+pub use crate::{
+    AdaptiveAhead, BoxError, Limit, PaginationState, ProcessSummary, Progress, RequestAhead,
+    SchedulingMode, StreamId, Total, TurnedPage,
+};
 #[doc = include_str!("../doc/prelude")]
 pub mod prelude {
-    pub use super::{Limit, PageTurner, PagesStream, RequestAhead, TurnedPage, TurnedPageResult};
+    pub use super::{
+        ArcPageTurner, Limit, PageTurner, PagesStream, RequestAhead, TurnedPage, TurnedPageResult,
+    };
 }
 
 #[doc = include_str!("../doc/PageItems")]
@@ -23,8 +49,13 @@ pub mod prelude {
 #[doc = include_str!("../doc/PageTurnerFuture")]
 pub type PageTurnerFuture<'a, P, R> = Pin<Box<dyn 'a + Future<Output = TurnedPageResult<P, R>>>>;
 
+#[doc = include_str!("../doc/BoxPagesStream")]
+pub type BoxPagesStream<'a, T, E> = Pin<Box<dyn 'a + Stream<Item = Result<T, E>>>>;
+
 type NumberedRequestFuture<'a, P, R> =
     Pin<Box<dyn 'a + Future<Output = (usize, TurnedPageResult<P, R>)>>>;
+type PagesBoxStream<'a, T> = Pin<Box<dyn 'a + Stream<Item = T>>>;
+type PagesFuture<'a, P, R> = Pin<Box<dyn 'a + Future<Output = (P, TurnedPageResult<P, R>)>>>;
 
 /// This is one of the less constrained page turners which produces `?Send`(may be Send) futures
 /// and streams that should run on single threaded executors. Occasionally, it might also work with
@@ -39,21 +70,47 @@ pub trait PageTurner<R>: Sized {
     #[doc = include_str!("../doc/PageTurner__turn_page")]
     fn turn_page(&self, request: R) -> impl Future<Output = TurnedPageResult<Self, R>>;
 
+    #[doc = include_str!("../doc/PageTurner__turn_pages")]
+    fn turn_pages(&self, requests: Vec<R>) -> impl Future<Output = Vec<TurnedPageResult<Self, R>>> {
+        futures::future::join_all(requests.into_iter().map(|request| self.turn_page(request)))
+    }
+
     #[doc = include_str!("../doc/PageTurner__pages")]
-    fn pages<'s>(&self, request: R) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    fn pages<'s>(&'s self, request: R) -> Pages<'s, &'s Self, R>
+    where
+        R: 's,
+    {
+        Pages::new(self, request)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_resumable")]
+    fn pages_resumable<'s>(&'s self, request: R) -> ResumablePages<'s, &'s Self, R>
+    where
+        R: 's + Clone,
+    {
+        Pages::new(self, request).resumable()
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_while")]
+    fn pages_while<'s, F>(
+        &'s self,
+        request: R,
+        pred: F,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
     where
         R: 's,
+        F: 's + FnMut(&Self::PageItems) -> bool,
     {
-        stream::try_unfold(PagesState::new(self, request), request_next_page)
+        pages_while(self.pages(request), pred)
     }
 
     #[doc = include_str!("../doc/PageTurner__into_pages")]
-    fn into_pages<'s>(self, request: R) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    fn into_pages<'s>(self, request: R) -> Pages<'s, Self, R>
     where
         Self: 's,
         R: 's,
     {
-        stream::try_unfold(PagesState::new(self, request), request_next_page)
+        Pages::new(self, request)
     }
 
     #[doc = include_str!("../doc/PageTurner__pages_ahead")]
@@ -105,19 +162,63 @@ fn pages_ahead_unordered<'s>(
         requests_ahead_count: usize,
         limit: Limit,
         request: R,
+    ) -> PagesAheadUnordered<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + RequestAhead,
+    {
+        PagesAheadUnordered::new(PagesAheadUnorderedState::new(
+            self,
+            request,
+            requests_ahead_count,
+            limit,
+        ))
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_ahead_unordered_indexed")]
+    fn pages_ahead_unordered_indexed<'s>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> IndexedPagesAheadUnordered<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + RequestAhead,
+    {
+        self.pages_ahead_unordered(requests_ahead_count, limit, request)
+            .indexed()
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_map_ahead")]
+    fn pages_map_ahead<'s, U, F, Fut>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+        f: F,
+    ) -> impl PagesStream<'s, U, Self::PageError>
+    where
+        R: 's + RequestAhead,
+        F: 's + FnMut(Self::PageItems) -> Fut,
+        Fut: 's + Future<Output = Result<U, Self::PageError>>,
+    {
+        self.pages_ahead(requests_ahead_count, limit, request)
+            .map_ok(f)
+            .try_buffered(requests_ahead_count.max(1))
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_ahead_while")]
+    fn pages_ahead_while<'s, F>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+        pred: F,
     ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
     where
         R: 's + RequestAhead,
+        F: 's + FnMut(&Self::PageItems) -> bool,
     {
-        stream::try_unfold(
-            Box::new(PagesAheadUnorderedState::new(
-                self,
-                request,
-                requests_ahead_count,
-                limit,
-            )),
-            request_pages_ahead_unordered,
-        )
+        pages_while(self.pages_ahead(requests_ahead_count, limit, request), pred)
     }
 
     #[doc = include_str!("../doc/PageTurner__into_pages_ahead_unordered")]
@@ -126,157 +227,1310 @@ fn into_pages_ahead_unordered<'s>(
         requests_ahead_count: usize,
         limit: Limit,
         request: R,
-    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    ) -> PagesAheadUnordered<'s, Self::PageItems, Self::PageError>
     where
         Self: 's + Clone,
         R: 's + RequestAhead,
     {
-        stream::try_unfold(
-            Box::new(PagesAheadUnorderedState::new(
-                self,
-                request,
-                requests_ahead_count,
-                limit,
+        PagesAheadUnordered::new(PagesAheadUnorderedState::new(
+            self,
+            request,
+            requests_ahead_count,
+            limit,
+        ))
+    }
+
+    /// Like [`PageTurner::into_pages_ahead`] but moves the page turner into an
+    /// [`std::sync::Arc`] shared by concurrent requests instead of cloning it, so it doesn't
+    /// need to implement `Clone`
+    fn into_pages_ahead_arc<'s>(
+        self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        Self: 's,
+        R: 's + RequestAhead,
+    {
+        std::sync::Arc::new(self).into_pages_ahead(requests_ahead_count, limit, request)
+    }
+
+    /// Like [`PageTurner::into_pages_ahead_unordered`] but moves the page turner into an
+    /// [`std::sync::Arc`] shared by concurrent requests instead of cloning it, so it doesn't
+    /// need to implement `Clone`
+    fn into_pages_ahead_unordered_arc<'s>(
+        self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> PagesAheadUnordered<'s, Self::PageItems, Self::PageError>
+    where
+        Self: 's,
+        R: 's + RequestAhead,
+    {
+        std::sync::Arc::new(self).into_pages_ahead_unordered(requests_ahead_count, limit, request)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_from_requests")]
+    fn pages_from_requests<'s, I>(
+        &'s self,
+        requests: I,
+        concurrency: usize,
+        mode: SchedulingMode,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's,
+        I: IntoIterator<Item = R>,
+        I::IntoIter: 's,
+    {
+        let requests = requests.into_iter();
+        let concurrency = concurrency.max(1);
+
+        let stream: PagesBoxStream<'s, Result<Self::PageItems, Self::PageError>> = match mode {
+            SchedulingMode::Ordered => Box::pin(stream::try_unfold(
+                Box::new(PagesAheadState::from_requests(
+                    self,
+                    requests,
+                    concurrency,
+                    None,
+                )),
+                request_pages_ahead,
             )),
-            request_pages_ahead_unordered,
+            SchedulingMode::Unordered => Box::pin(
+                stream::try_unfold(
+                    Box::new(PagesAheadUnorderedState::with_requests(
+                        self,
+                        NumberedRequests::sequential(requests),
+                        concurrency,
+                        None,
+                    )),
+                    request_pages_ahead_unordered,
+                )
+                .map_ok(|(_, items)| items),
+            ),
+        };
+
+        stream
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_with_state")]
+    fn pages_with_state<'s, S>(
+        &'s self,
+        request: R,
+        state: S,
+    ) -> impl 's + Stream<Item = Result<Self::PageItems, Self::PageError>>
+    where
+        R: 's,
+        S: 's + PaginationState<R, Self::PageItems>,
+    {
+        stream::try_unfold(
+            (Some(request), state),
+            move |(request, mut state)| async move {
+                let Some(request) = request else {
+                    return Ok(None);
+                };
+
+                let TurnedPage {
+                    items,
+                    next_request,
+                } = self.turn_page(request).await?;
+                let next_request = state.next_request(&items, next_request);
+
+                Ok(Some((items, (next_request, state))))
+            },
         )
     }
-}
 
-impl<D, P, R> PageTurner<R> for D
-where
-    D: std::ops::Deref<Target = P>,
-    P: PageTurner<R>,
-{
-    type PageItems = PageItems<P, R>;
-    type PageError = PageError<P, R>;
+    #[doc = include_str!("../doc/PageTurner__pages_with_retry")]
+    fn pages_with_retry<'s, Policy>(
+        &'s self,
+        policy: Policy,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + Clone,
+        Policy: 's + RetryPolicy<Self::PageError>,
+    {
+        Retry::new(self, policy).into_pages(request)
+    }
 
-    async fn turn_page(&self, request: R) -> TurnedPageResult<Self, R> {
-        self.deref().turn_page(request).await
+    #[doc = include_str!("../doc/PageTurner__pages_ahead_with_retry")]
+    fn pages_ahead_with_retry<'s, Policy>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        policy: Policy,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + Clone + RequestAhead,
+        Policy: 's + RetryPolicy<Self::PageError> + Clone,
+    {
+        Retry::new(self, policy).into_pages_ahead(requests_ahead_count, limit, request)
     }
-}
 
-#[doc = include_str!("../doc/PagesStream")]
-pub trait PagesStream<'a, T, E>: Stream<Item = Result<T, E>> {
-    #[doc = include_str!("../doc/PagesStream__items")]
-    fn items(self) -> impl 'a + Stream<Item = Result<<T as IntoIterator>::Item, E>>
+    #[doc = include_str!("../doc/PageTurner__pages_with_timeout")]
+    fn pages_with_timeout<'s>(
+        &'s self,
+        timeout: std::time::Duration,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, TimeoutError<Self::PageError>>
     where
-        Self: 'a,
-        T: IntoIterator;
-}
+        R: 's,
+    {
+        Timeout::new(self, timeout).into_pages(request)
+    }
 
-impl<'a, S, T, E> PagesStream<'a, T, E> for S
-where
-    S: Stream<Item = Result<T, E>>,
-{
-    fn items(self) -> impl 'a + Stream<Item = Result<<T as IntoIterator>::Item, E>>
+    #[doc = include_str!("../doc/PageTurner__pages_ahead_with_timeout")]
+    fn pages_ahead_with_timeout<'s>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        timeout: std::time::Duration,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, TimeoutError<Self::PageError>>
     where
-        Self: 'a,
-        T: IntoIterator,
+        R: 's + RequestAhead,
     {
-        self.map_ok(|items| stream::iter(items.into_iter().map(Ok)))
-            .try_flatten()
+        Timeout::new(self, timeout).into_pages_ahead(requests_ahead_count, limit, request)
     }
-}
 
-pages_ahead_state_def!();
-pages_ahead_unordered_state_def!();
+    #[doc = include_str!("../doc/PageTurner__map_items")]
+    fn map_items<F, I>(self, f: F) -> MapItems<Self, F>
+    where
+        F: Fn(Self::PageItems) -> I,
+    {
+        MapItems::new(self, f)
+    }
 
-request_next_page_decl!();
-request_pages_ahead_decl!();
-request_pages_ahead_unordered_decl!();
+    #[doc = include_str!("../doc/PageTurner__map_err")]
+    fn map_err<F, E>(self, f: F) -> MapErr<Self, F>
+    where
+        F: Fn(Self::PageError) -> E,
+    {
+        MapErr::new(self, f)
+    }
 
-#[cfg(feature = "mutable")]
-#[cfg_attr(docsrs, doc(cfg(feature = "mutable")))]
-pub mod mutable {
-    //! Provides a page turner which takes `&mut self` instead of `&self` if you don't want to bother
-    //! with interior mutability in single threaded contexts.
+    #[doc = include_str!("../doc/PageTurner__and_then_page")]
+    fn and_then_page<F, I>(self, f: F) -> AndThenPage<Self, F>
+    where
+        F: Fn(Self::PageItems) -> Result<I, Self::PageError>,
+    {
+        AndThenPage::new(self, f)
+    }
 
-    use crate::internal::*;
-    use futures::stream;
-    use std::{future::Future, pin::Pin};
+    #[doc = include_str!("../doc/PageTurner__or_else")]
+    fn or_else<Q>(self, fallback: Q) -> OrElse<Self, Q>
+    where
+        Q: PageTurner<R, PageItems = Self::PageItems, PageError = Self::PageError>,
+        R: Clone,
+    {
+        OrElse::new(self, fallback)
+    }
 
-    pub use super::PagesStream;
-    pub use crate::{Limit, RequestAhead, TurnedPage};
-    #[doc = include_str!("../doc/prelude")]
-    pub mod prelude {
-        pub use super::{
-            Limit, PageTurner, PagesStream, RequestAhead, TurnedPage, TurnedPageResult,
-        };
+    #[doc = include_str!("../doc/PageTurner__pages_backward")]
+    fn pages_backward<'s, I>(&'s self, request: R) -> impl PagesStream<'s, I, Self::PageError>
+    where
+        Self: PageTurner<R, PageItems = WithPrev<I, R>>,
+        R: 's,
+    {
+        Backward::new(self).into_pages(request)
     }
 
-    #[doc = include_str!("../doc/PageItems")]
-    pub type PageItems<P, R> = <P as PageTurner<R>>::PageItems;
-    #[doc = include_str!("../doc/PageError")]
-    pub type PageError<P, R> = <P as PageTurner<R>>::PageError;
-    #[doc = include_str!("../doc/TurnedPageResult")]
-    pub type TurnedPageResult<P, R> = Result<TurnedPage<PageItems<P, R>, R>, PageError<P, R>>;
-    #[doc = include_str!("../doc/PageTurnerFuture")]
-    pub type PageTurnerFuture<'a, P, R> =
-        Pin<Box<dyn 'a + Future<Output = TurnedPageResult<P, R>>>>;
+    #[doc = include_str!("../doc/PageTurner__pages_bidirectional")]
+    fn pages_bidirectional(&self, request: R) -> BidirectionalPages<'_, Self, R> {
+        BidirectionalPages::new(self, request)
+    }
 
-    /// The least constrained page turner that allows an implementor to mutate during request
-    /// execution and, therefore, doesn't provide the `pages_ahead` family of methods as it's
-    /// invalid to hold multiple `&mut self` references concurrently. For uses in single threaded
-    /// contexts when you don't want to bother with interior mutability of the implementor.
-    ///
-    #[doc = include_str!("../doc/PageTurner")]
-    pub trait PageTurner<R>: Sized {
-        type PageItems;
-        type PageError;
+    #[doc = include_str!("../doc/PageTurner__pages_with_progress")]
+    fn pages_with_progress<'s>(
+        &'s self,
+        request: R,
+        progress: ProgressHandle<R>,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + Clone,
+        for<'t> &'t Self::PageItems: IntoIterator,
+    {
+        Reporting::new(self, progress).into_pages(request)
+    }
 
-        #[doc = include_str!("../doc/PageTurner__turn_page")]
-        fn turn_page(&mut self, request: R) -> impl Future<Output = TurnedPageResult<Self, R>>;
+    #[doc = include_str!("../doc/PageTurner__pages_chain")]
+    fn pages_chain<'s, R2, F>(
+        &'s self,
+        request: R,
+        next_query: F,
+    ) -> impl PagesStream<'s, PageItems<Self, R>, PageError<Self, R>>
+    where
+        Self: PageTurner<R2, PageItems = PageItems<Self, R>, PageError = PageError<Self, R>>,
+        R: 's + Clone,
+        R2: 's,
+        F: 's + FnOnce(ChainSummary<R>) -> Option<R2>,
+        for<'t> &'t PageItems<Self, R>: IntoIterator,
+    {
+        stream::try_unfold(
+            ChainState::new(request, next_query),
+            move |state| async move {
+                match state {
+                    ChainState::First {
+                        request,
+                        pages,
+                        items: items_count,
+                        next_query,
+                    } => {
+                        let TurnedPage {
+                            items,
+                            next_request,
+                        } = PageTurner::<R>::turn_page(self, request.clone()).await?;
 
-        #[doc = include_str!("../doc/PageTurner__pages")]
-        fn pages<'s>(
-            &'s mut self,
-            request: R,
-        ) -> impl PagesStream<'s, PageItems<Self, R>, PageError<Self, R>>
-        where
-            R: 's,
-        {
-            stream::try_unfold(PagesState::new(self, request), request_next_page)
-        }
+                        let pages = pages + 1;
+                        let items_count = items_count + (&items).into_iter().count();
 
-        #[doc = include_str!("../doc/PageTurner__into_pages")]
-        fn into_pages<'s>(
-            self,
-            request: R,
-        ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
-        where
-            Self: 's,
-            R: 's,
-        {
-            stream::try_unfold(PagesState::new(self, request), request_next_page)
-        }
+                        let state = match next_request {
+                            Some(request) => ChainState::First {
+                                request,
+                                pages,
+                                items: items_count,
+                                next_query,
+                            },
+                            None => next_query(ChainSummary {
+                                pages,
+                                items: items_count,
+                                last_request: request,
+                            })
+                            .map_or(ChainState::Done, ChainState::Second),
+                        };
+
+                        Ok(Some((items, state)))
+                    }
+                    ChainState::Second(request) => {
+                        let TurnedPage {
+                            items,
+                            next_request,
+                        } = PageTurner::<R2>::turn_page(self, request).await?;
+
+                        Ok(Some((
+                            items,
+                            next_request.map_or(ChainState::Done, ChainState::Second),
+                        )))
+                    }
+                    ChainState::Done => Ok(None),
+                }
+            },
+        )
     }
 
-    impl<P, R> PageTurner<R> for &mut P
+    #[doc = include_str!("../doc/PageTurner__pages_ahead_throttled")]
+    fn pages_ahead_throttled<'s>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        rate: Rate,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
     where
-        P: PageTurner<R>,
+        R: 's + RequestAhead,
     {
-        type PageItems = PageItems<P, R>;
-        type PageError = PageError<P, R>;
-
-        fn turn_page(&mut self, request: R) -> impl Future<Output = TurnedPageResult<Self, R>> {
-            P::turn_page(self, request)
-        }
+        Throttled::new(self, rate).into_pages_ahead(requests_ahead_count, limit, request)
     }
 
-    impl<P, R> PageTurner<R> for Box<P>
+    #[doc = include_str!("../doc/PageTurner__pages_ahead_batched")]
+    fn pages_ahead_batched<'s>(
+        &'s self,
+        batch_size: usize,
+        limit: Limit,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
     where
-        P: PageTurner<R>,
+        R: 's + RequestAhead,
     {
-        type PageItems = PageItems<P, R>;
-        type PageError = PageError<P, R>;
+        stream::unfold(
+            Some(RequestIter::new(request, limit)),
+            move |requests| async move {
+                let mut requests = requests?;
+                let batch: Vec<_> = requests.by_ref().take(batch_size.max(1)).collect();
+                if batch.is_empty() {
+                    return None;
+                }
 
-        fn turn_page(&mut self, request: R) -> impl Future<Output = TurnedPageResult<Self, R>> {
+                let mut pages = record_latency(requests.latency(), self.turn_pages(batch)).await;
+
+                // The last page or an error end the stream, pages after them are discarded
+                let end = pages.iter().position(|page| {
+                    !matches!(
+                        page,
+                        Ok(TurnedPage {
+                            next_request: Some(_),
+                            ..
+                        })
+                    )
+                });
+
+                let requests = match end {
+                    Some(end) => {
+                        pages.truncate(end + 1);
+                        None
+                    }
+                    None => Some(requests),
+                };
+
+                Some((stream::iter(pages), requests))
+            },
+        )
+        .flatten()
+        .map_ok(|page| page.items)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_tail_durable")]
+    fn pages_tail_durable<'s, S>(
+        &'s self,
+        store: S,
+        request: R,
+        interval: std::time::Duration,
+    ) -> impl 's + Stream<Item = Result<Self::PageItems, TailError<Self::PageError, S::Error>>>
+    where
+        R: 's + Clone,
+        S: 's + CursorStore<R>,
+    {
+        stream::try_unfold(
+            Box::new(TailState::new(self, store, request, interval)),
+            request_tail_page,
+        )
+    }
+}
+
+impl<D, P, R> PageTurner<R> for D
+where
+    D: std::ops::Deref<Target = P>,
+    P: PageTurner<R>,
+{
+    type PageItems = PageItems<P, R>;
+    type PageError = PageError<P, R>;
+
+    async fn turn_page(&self, request: R) -> TurnedPageResult<Self, R> {
+        self.deref().turn_page(request).await
+    }
+
+    async fn turn_pages(&self, requests: Vec<R>) -> Vec<TurnedPageResult<Self, R>> {
+        self.deref().turn_pages(requests).await
+    }
+}
+
+#[doc = include_str!("../doc/ItemPageTurner")]
+pub trait ItemPageTurner<R>: PageTurner<R, PageItems = <Self as ItemPageTurner<R>>::Items> {
+    /// The same type as [`PageTurner::PageItems`]
+    type Items: IntoIterator<
+        Item = <Self as ItemPageTurner<R>>::Item,
+        IntoIter = <Self as ItemPageTurner<R>>::ItemsIter,
+    >;
+    type Item;
+    type ItemsIter: Iterator<Item = <Self as ItemPageTurner<R>>::Item>;
+}
+
+impl<P, R> ItemPageTurner<R> for P
+where
+    P: PageTurner<R>,
+    P::PageItems: IntoIterator,
+{
+    type Items = P::PageItems;
+    type Item = <P::PageItems as IntoIterator>::Item;
+    type ItemsIter = <P::PageItems as IntoIterator>::IntoIter;
+}
+
+#[doc = include_str!("../doc/ArcPageTurner")]
+pub trait ArcPageTurner<P, R>
+where
+    P: PageTurner<R>,
+    R: 'static,
+{
+    /// Like [`PageTurner::into_pages`] on a clone of the [`std::sync::Arc`]
+    fn arc_pages(&self, request: R) -> Pages<'static, std::sync::Arc<P>, R>;
+
+    /// Like [`PageTurner::into_pages_ahead`] on a clone of the [`std::sync::Arc`]. The stream is
+    /// boxed to not borrow `self`.
+    fn arc_pages_ahead(
+        &self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> BoxPagesStream<'static, P::PageItems, P::PageError>
+    where
+        R: RequestAhead;
+
+    /// Like [`PageTurner::into_pages_ahead_unordered`] on a clone of the [`std::sync::Arc`]
+    fn arc_pages_ahead_unordered(
+        &self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> PagesAheadUnordered<'static, P::PageItems, P::PageError>
+    where
+        R: RequestAhead;
+}
+
+impl<P, R> ArcPageTurner<P, R> for std::sync::Arc<P>
+where
+    P: 'static + PageTurner<R>,
+    R: 'static,
+{
+    fn arc_pages(&self, request: R) -> Pages<'static, std::sync::Arc<P>, R> {
+        std::sync::Arc::clone(self).into_pages(request)
+    }
+
+    fn arc_pages_ahead(
+        &self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> BoxPagesStream<'static, P::PageItems, P::PageError>
+    where
+        R: RequestAhead,
+    {
+        std::sync::Arc::clone(self)
+            .into_pages_ahead(requests_ahead_count, limit, request)
+            .boxed_pages()
+    }
+
+    fn arc_pages_ahead_unordered(
+        &self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> PagesAheadUnordered<'static, P::PageItems, P::PageError>
+    where
+        R: RequestAhead,
+    {
+        std::sync::Arc::clone(self).into_pages_ahead_unordered(requests_ahead_count, limit, request)
+    }
+}
+
+#[doc = include_str!("../doc/PagesStream")]
+pub trait PagesStream<'a, T, E>: Stream<Item = Result<T, E>> {
+    #[doc = include_str!("../doc/PagesStream__boxed_pages")]
+    fn boxed_pages(self) -> BoxPagesStream<'a, T, E>
+    where
+        Self: 'a;
+
+    #[doc = include_str!("../doc/PagesStream__items")]
+    fn items(self) -> impl 'a + Stream<Item = Result<<T as IntoIterator>::Item, E>>
+    where
+        Self: 'a,
+        T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__items_streamed")]
+    fn items_streamed<I, IE>(self) -> impl 'a + Stream<Item = Result<I, IE>>
+    where
+        Self: 'a,
+        T: TryStream<Ok = I, Error = IE>,
+        IE: From<E>;
+
+    #[doc = include_str!("../doc/PagesStream__try_collect_items")]
+    fn try_collect_items<C>(self) -> impl 'a + Future<Output = Result<C, E>>
+    where
+        Self: 'a,
+        E: 'a,
+        T: IntoIterator,
+        C: 'a + Default + Extend<<T as IntoIterator>::Item>;
+
+    #[doc = include_str!("../doc/PagesStream__try_collect_items_capped")]
+    fn try_collect_items_capped<C>(
+        self,
+        max_items: usize,
+    ) -> impl 'a + Future<Output = Result<C, E>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        C: 'a + Default + Extend<<T as IntoIterator>::Item>;
+
+    #[doc = include_str!("../doc/PagesStream__try_collect_items_into")]
+    fn try_collect_items_into<C>(self, collection: C) -> impl 'a + Future<Output = Result<C, E>>
+    where
+        Self: 'a,
+        E: 'a,
+        T: IntoIterator,
+        C: 'a + Extend<<T as IntoIterator>::Item>;
+
+    #[doc = include_str!("../doc/PagesStream__process_pages_concurrently")]
+    fn process_pages_concurrently<F, Fut, H>(
+        self,
+        concurrency: usize,
+        handler: F,
+    ) -> impl 'a + Future<Output = ProcessSummary<E, H>>
+    where
+        Self: 'a,
+        E: 'a,
+        F: 'a + FnMut(T) -> Fut,
+        Fut: 'a + Future<Output = Result<(), H>>,
+        H: 'a;
+
+    #[doc = include_str!("../doc/PagesStream__only_new_items")]
+    fn only_new_items<K, F>(
+        self,
+        mark: HighWaterMark<K>,
+        order: KeyOrder,
+        key: F,
+    ) -> impl 'a + Stream<Item = Result<<T as IntoIterator>::Item, E>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        K: 'a + Ord + Clone,
+        F: 'a + FnMut(&<T as IntoIterator>::Item) -> K;
+
+    #[doc = include_str!("../doc/PagesStream__lazy_pages")]
+    fn lazy_pages<F, U>(
+        self,
+        decode: F,
+    ) -> impl 'a + Stream<Item = Result<impl 'a + Stream<Item = U>, E>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        F: 'a + Clone + FnMut(<T as IntoIterator>::Item) -> U,
+        <T as IntoIterator>::IntoIter: 'a;
+
+    #[doc = include_str!("../doc/PagesStream__decode_pages")]
+    fn decode_pages<F, Fut, U>(
+        self,
+        concurrency: usize,
+        decode: F,
+    ) -> impl 'a + Stream<Item = Result<U, E>>
+    where
+        Self: 'a,
+        E: 'a,
+        F: 'a + FnMut(T) -> Fut,
+        Fut: 'a + Future<Output = Result<U, E>>,
+        U: 'a;
+
+    #[doc = include_str!("../doc/PagesStream__split_items")]
+    fn split_items(self) -> (SplitPages<Self, T, E>, SplitItems<Self, T, E>)
+    where
+        Self: Sized,
+        T: IntoIterator + Clone,
+        E: Clone;
+
+    #[doc = include_str!("../doc/PagesStream__pages_infallible")]
+    fn pages_infallible(self) -> impl 'a + Stream<Item = T>
+    where
+        Self: 'a,
+        E: Into<Infallible>;
+
+    #[doc = include_str!("../doc/PagesStream__pages_boxed_err")]
+    fn pages_boxed_err(self) -> impl 'a + Stream<Item = Result<T, BoxError>>
+    where
+        Self: 'a,
+        E: 'a + Into<BoxError>;
+
+    #[doc = include_str!("../doc/PagesStream__items_boxed_err")]
+    fn items_boxed_err(
+        self,
+    ) -> impl 'a + Stream<Item = Result<<T as IntoIterator>::Item, BoxError>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        E: 'a + Into<BoxError>;
+
+    #[doc = include_str!("../doc/PagesStream__blocking")]
+    fn blocking(self) -> BlockingPages<Self, ParkThread>
+    where
+        Self: Sized;
+
+    #[doc = include_str!("../doc/PagesStream__blocking_on")]
+    fn blocking_on<B>(self, executor: B) -> BlockingPages<Self, B>
+    where
+        Self: Sized,
+        B: BlockOn;
+
+    #[doc = include_str!("../doc/PagesStream__until_cancelled")]
+    fn until_cancelled<C>(self, token: C) -> Cancellable<Self, C>
+    where
+        Self: Sized,
+        C: Cancellation;
+
+    #[doc = include_str!("../doc/PagesStream__with_throughput")]
+    fn with_throughput(self, stats: ThroughputStats) -> Throughput<Self>
+    where
+        Self: Sized,
+        for<'t> &'t T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__with_stats")]
+    fn with_stats(self, stats: StreamStats) -> WithStats<Self>
+    where
+        Self: Sized,
+        for<'t> &'t T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__with_summary")]
+    fn with_summary<R>(self, report: RunReport<R>) -> Summarized<Self, R>
+    where
+        Self: Sized,
+        for<'t> &'t T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__inspect_page")]
+    fn inspect_page<F>(self, f: F) -> impl 'a + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        F: 'a + FnMut(&T);
+
+    #[doc = include_str!("../doc/PagesStream__inspect_page_err")]
+    fn inspect_page_err<F>(self, f: F) -> impl 'a + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        F: 'a + FnMut(&E);
+
+    #[doc = include_str!("../doc/PagesStream__limit_items")]
+    fn limit_items(self, max_items: usize) -> impl 'a + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        for<'t> &'t T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__with_progress")]
+    fn with_progress(self, total: Total) -> impl 'a + Stream<Item = Result<(T, Progress), E>>
+    where
+        Self: 'a,
+        for<'t> &'t T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__with_pages_progress_bar")]
+    #[cfg(feature = "indicatif")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "indicatif")))]
+    fn with_pages_progress_bar(
+        self,
+        bar: indicatif::ProgressBar,
+    ) -> impl 'a + Stream<Item = Result<T, E>>
+    where
+        Self: 'a;
+
+    #[doc = include_str!("../doc/PagesStream__with_items_progress_bar")]
+    #[cfg(feature = "indicatif")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "indicatif")))]
+    fn with_items_progress_bar(
+        self,
+        bar: indicatif::ProgressBar,
+    ) -> impl 'a + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        for<'t> &'t T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__items_infallible")]
+    fn items_infallible(self) -> impl 'a + Stream<Item = <T as IntoIterator>::Item>
+    where
+        Self: 'a,
+        E: Into<Infallible>,
+        T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__collect_items")]
+    fn collect_items<C>(self) -> impl 'a + Future<Output = C>
+    where
+        Self: 'a,
+        E: 'a + Into<Infallible>,
+        T: IntoIterator,
+        C: 'a + Default + Extend<<T as IntoIterator>::Item>;
+}
+
+impl<'a, S, T, E> PagesStream<'a, T, E> for S
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    fn boxed_pages(self) -> BoxPagesStream<'a, T, E>
+    where
+        Self: 'a,
+    {
+        Box::pin(self)
+    }
+
+    fn items(self) -> impl 'a + Stream<Item = Result<<T as IntoIterator>::Item, E>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+    {
+        self.map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    fn items_streamed<I, IE>(self) -> impl 'a + Stream<Item = Result<I, IE>>
+    where
+        Self: 'a,
+        T: TryStream<Ok = I, Error = IE>,
+        IE: From<E>,
+    {
+        self.try_flatten()
+    }
+
+    fn try_collect_items<C>(self) -> impl 'a + Future<Output = Result<C, E>>
+    where
+        Self: 'a,
+        E: 'a,
+        T: IntoIterator,
+        C: 'a + Default + Extend<<T as IntoIterator>::Item>,
+    {
+        self.try_collect_items_into(C::default())
+    }
+
+    fn try_collect_items_capped<C>(
+        self,
+        max_items: usize,
+    ) -> impl 'a + Future<Output = Result<C, E>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        C: 'a + Default + Extend<<T as IntoIterator>::Item>,
+    {
+        self.items().take(max_items).try_collect()
+    }
+
+    fn try_collect_items_into<C>(self, collection: C) -> impl 'a + Future<Output = Result<C, E>>
+    where
+        Self: 'a,
+        E: 'a,
+        T: IntoIterator,
+        C: 'a + Extend<<T as IntoIterator>::Item>,
+    {
+        self.try_fold(collection, |mut collection, items| {
+            collection.extend(items);
+            future::ready(Ok(collection))
+        })
+    }
+
+    fn process_pages_concurrently<F, Fut, H>(
+        self,
+        concurrency: usize,
+        handler: F,
+    ) -> impl 'a + Future<Output = ProcessSummary<E, H>>
+    where
+        Self: 'a,
+        E: 'a,
+        F: 'a + FnMut(T) -> Fut,
+        Fut: 'a + Future<Output = Result<(), H>>,
+        H: 'a,
+    {
+        let mut failed = false;
+        let mut handler = handler;
+
+        self.take_while(move |page| {
+            let proceed = !failed;
+            failed = page.is_err();
+            future::ready(proceed)
+        })
+        .map(move |page| {
+            let handled = page.map(&mut handler);
+            async move {
+                match handled {
+                    Ok(fut) => Ok(fut.await),
+                    Err(e) => Err(e),
+                }
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .fold(ProcessSummary::default(), |mut summary, outcome| {
+            summary.record(outcome);
+            future::ready(summary)
+        })
+    }
+
+    fn only_new_items<K, F>(
+        self,
+        mark: HighWaterMark<K>,
+        order: KeyOrder,
+        key: F,
+    ) -> impl 'a + Stream<Item = Result<<T as IntoIterator>::Item, E>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        K: 'a + Ord + Clone,
+        F: 'a + FnMut(&<T as IntoIterator>::Item) -> K,
+    {
+        let mut key = key;
+        let mut is_new = mark.new_keys(order);
+        self.items()
+            .try_filter(move |item| future::ready(is_new(key(item))))
+    }
+
+    fn lazy_pages<F, U>(
+        self,
+        decode: F,
+    ) -> impl 'a + Stream<Item = Result<impl 'a + Stream<Item = U>, E>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        F: 'a + Clone + FnMut(<T as IntoIterator>::Item) -> U,
+        <T as IntoIterator>::IntoIter: 'a,
+    {
+        self.map_ok(move |items| stream::iter(items.into_iter().map(decode.clone())))
+    }
+
+    fn decode_pages<F, Fut, U>(
+        self,
+        concurrency: usize,
+        decode: F,
+    ) -> impl 'a + Stream<Item = Result<U, E>>
+    where
+        Self: 'a,
+        E: 'a,
+        F: 'a + FnMut(T) -> Fut,
+        Fut: 'a + Future<Output = Result<U, E>>,
+        U: 'a,
+    {
+        let mut fetch_failed = false;
+        let mut decode_failed = false;
+        let mut decode = decode;
+
+        self.take_while(move |page| {
+            let proceed = !fetch_failed;
+            fetch_failed = page.is_err();
+            future::ready(proceed)
+        })
+        .map(move |page| {
+            let decoded = page.map(&mut decode);
+            async move {
+                match decoded {
+                    Ok(fut) => fut.await,
+                    Err(e) => Err(e),
+                }
+            }
+        })
+        // The next page is fetched only while fewer than `concurrency` pages are decoded
+        .buffered(concurrency.max(1))
+        .take_while(move |page| {
+            let proceed = !decode_failed;
+            decode_failed = page.is_err();
+            future::ready(proceed)
+        })
+    }
+
+    fn split_items(self) -> (SplitPages<Self, T, E>, SplitItems<Self, T, E>)
+    where
+        Self: Sized,
+        T: IntoIterator + Clone,
+        E: Clone,
+    {
+        crate::split::split(self)
+    }
+
+    fn pages_infallible(self) -> impl 'a + Stream<Item = T>
+    where
+        Self: 'a,
+        E: Into<Infallible>,
+    {
+        self.map(|page| unwrap_infallible(page))
+    }
+
+    fn pages_boxed_err(self) -> impl 'a + Stream<Item = Result<T, BoxError>>
+    where
+        Self: 'a,
+        E: 'a + Into<BoxError>,
+    {
+        self.map_err(Into::into)
+    }
+
+    fn items_boxed_err(self) -> impl 'a + Stream<Item = Result<<T as IntoIterator>::Item, BoxError>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        E: 'a + Into<BoxError>,
+    {
+        self.items().map_err(Into::into)
+    }
+
+    fn blocking(self) -> BlockingPages<Self, ParkThread>
+    where
+        Self: Sized,
+    {
+        BlockingPages::new(self, ParkThread)
+    }
+
+    fn blocking_on<B>(self, executor: B) -> BlockingPages<Self, B>
+    where
+        Self: Sized,
+        B: BlockOn,
+    {
+        BlockingPages::new(self, executor)
+    }
+
+    fn until_cancelled<C>(self, token: C) -> Cancellable<Self, C>
+    where
+        Self: Sized,
+        C: Cancellation,
+    {
+        Cancellable::new(self, &token)
+    }
+
+    fn with_throughput(self, stats: ThroughputStats) -> Throughput<Self>
+    where
+        Self: Sized,
+        for<'t> &'t T: IntoIterator,
+    {
+        Throughput::new(self, stats)
+    }
+
+    fn with_stats(self, stats: StreamStats) -> WithStats<Self>
+    where
+        Self: Sized,
+        for<'t> &'t T: IntoIterator,
+    {
+        WithStats::new(self, stats)
+    }
+
+    fn with_summary<R>(self, report: RunReport<R>) -> Summarized<Self, R>
+    where
+        Self: Sized,
+        for<'t> &'t T: IntoIterator,
+    {
+        Summarized::new(self, report)
+    }
+
+    fn inspect_page<F>(self, f: F) -> impl 'a + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        F: 'a + FnMut(&T),
+    {
+        self.inspect_ok(f)
+    }
+
+    fn inspect_page_err<F>(self, f: F) -> impl 'a + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        F: 'a + FnMut(&E),
+    {
+        TryStreamExt::inspect_err(self, f)
+    }
+
+    fn limit_items(self, max_items: usize) -> impl 'a + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        for<'t> &'t T: IntoIterator,
+    {
+        stream::unfold(
+            (Some(Box::pin(self)), 0),
+            move |(pages, yielded)| async move {
+                let mut pages = pages.filter(|_| yielded < max_items)?;
+                let page = pages.next().await?;
+                let yielded = match &page {
+                    Ok(items) => yielded + items.into_iter().count(),
+                    Err(_) => yielded,
+                };
+
+                // Requests sent ahead of the page that reaches the limit are already in flight,
+                // drop the stream right away to cancel them
+                let pages = (yielded < max_items).then_some(pages);
+                Some((page, (pages, yielded)))
+            },
+        )
+    }
+
+    fn with_progress(self, total: Total) -> impl 'a + Stream<Item = Result<(T, Progress), E>>
+    where
+        Self: 'a,
+        for<'t> &'t T: IntoIterator,
+    {
+        let mut done = 0;
+
+        self.map_ok(move |items| {
+            let progress = update_progress(&mut done, total, &items);
+            (items, progress)
+        })
+    }
+
+    #[cfg(feature = "indicatif")]
+    fn with_pages_progress_bar(
+        self,
+        bar: indicatif::ProgressBar,
+    ) -> impl 'a + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+    {
+        self.inspect_ok(move |_| bar.inc(1))
+    }
+
+    #[cfg(feature = "indicatif")]
+    fn with_items_progress_bar(
+        self,
+        bar: indicatif::ProgressBar,
+    ) -> impl 'a + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        for<'t> &'t T: IntoIterator,
+    {
+        self.inspect_ok(move |items| bar.inc(items.into_iter().count() as u64))
+    }
+
+    fn items_infallible(self) -> impl 'a + Stream<Item = <T as IntoIterator>::Item>
+    where
+        Self: 'a,
+        E: Into<Infallible>,
+        T: IntoIterator,
+    {
+        self.map(|page| stream::iter(unwrap_infallible(page)))
+            .flatten()
+    }
+
+    fn collect_items<C>(self) -> impl 'a + Future<Output = C>
+    where
+        Self: 'a,
+        E: 'a + Into<Infallible>,
+        T: IntoIterator,
+        C: 'a + Default + Extend<<T as IntoIterator>::Item>,
+    {
+        let items = self.try_collect_items_into(C::default());
+        async move { unwrap_infallible(items.await) }
+    }
+}
+
+/// Creates a page turner from an async closure, handy for scripts and one-off paginations that
+/// don't deserve a dedicated client struct.
+///
+/// ```
+/// use page_turner::local::{prelude::*, turner_fn};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use futures::TryStreamExt;
+///
+/// let page_turner = turner_fn(|page: u32| async move {
+///     Ok::<_, ()>(TurnedPage::new(vec![page], (page < 3).then_some(page + 1)))
+/// });
+///
+/// let pages: Vec<_> = page_turner.pages(1).items().try_collect().await.unwrap();
+/// assert_eq!(pages, [1, 2, 3]);
+/// # }
+/// ```
+pub fn turner_fn<F>(f: F) -> TurnerFn<F> {
+    TurnerFn(f)
+}
+
+/// A page turner returned by [`turner_fn`]
+#[derive(Debug, Clone, Copy)]
+pub struct TurnerFn<F>(F);
+
+impl<F> TurnerFn<F> {
+    pub fn into_inner(self) -> F {
+        self.0
+    }
+}
+
+impl<F, Fut, R, I, E> PageTurner<R> for TurnerFn<F>
+where
+    F: Fn(R) -> Fut,
+    Fut: Future<Output = Result<TurnedPage<I, R>, E>>,
+{
+    type PageItems = I;
+    type PageError = E;
+
+    fn turn_page(&self, request: R) -> impl Future<Output = TurnedPageResult<Self, R>> {
+        (self.0)(request)
+    }
+}
+
+/// Queries `pages` of all page turner and request pairs concurrently and yields them as they
+/// arrive, see [`select_pages`](crate::merge::select_pages).
+pub fn merge_pages<'p, P, R>(
+    turners_and_requests: impl IntoIterator<Item = (&'p P, R)>,
+) -> SelectPages<Pages<'p, &'p P, R>>
+where
+    P: 'p + PageTurner<R>,
+    R: 'p,
+{
+    select_pages(
+        turners_and_requests
+            .into_iter()
+            .map(|(page_turner, request)| page_turner.pages(request)),
+    )
+}
+
+bidirectional_pages_def!();
+pages_stream_def!();
+pages_builder_def!();
+pages_ahead_state_def!();
+pages_ahead_auto_state_def!();
+pages_ahead_unordered_state_def!();
+pages_ahead_unordered_stream_def!();
+
+request_pages_ahead_decl!();
+request_pages_ahead_auto_decl!();
+request_pages_ahead_unordered_decl!();
+
+tail_state_def!();
+request_tail_page_decl!();
+
+pub mod testing {
+    //! Helpers for testing page turner implementations
+
+    use super::{Limit, PageError, PageItems, PageTurner, RequestAhead};
+    use crate::internal::*;
+    use futures::{StreamExt, TryStreamExt};
+
+    assert_pagination_equivalent_decl!();
+}
+
+#[cfg(feature = "mutable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mutable")))]
+pub mod mutable {
+    //! Provides a page turner which takes `&mut self` instead of `&self` if you don't want to bother
+    //! with interior mutability in single threaded contexts.
+
+    use crate::internal::*;
+    use futures::{stream, Stream};
+    use std::{future::Future, pin::Pin};
+
+    pub use super::PagesStream;
+    pub use crate::{
+        BoxError, Limit, ProcessSummary, Progress, RequestAhead, StreamId, Total, TurnedPage,
+    };
+    #[doc = include_str!("../doc/prelude")]
+    pub mod prelude {
+        pub use super::{
+            Limit, PageTurner, PagesStream, RequestAhead, TurnedPage, TurnedPageResult,
+        };
+    }
+
+    #[doc = include_str!("../doc/PageItems")]
+    pub type PageItems<P, R> = <P as PageTurner<R>>::PageItems;
+    #[doc = include_str!("../doc/PageError")]
+    pub type PageError<P, R> = <P as PageTurner<R>>::PageError;
+    #[doc = include_str!("../doc/TurnedPageResult")]
+    pub type TurnedPageResult<P, R> = Result<TurnedPage<PageItems<P, R>, R>, PageError<P, R>>;
+    #[doc = include_str!("../doc/PageTurnerFuture")]
+    pub type PageTurnerFuture<'a, P, R> =
+        Pin<Box<dyn 'a + Future<Output = TurnedPageResult<P, R>>>>;
+
+    type PagesFuture<'a, P, R> = Pin<Box<dyn 'a + Future<Output = (P, TurnedPageResult<P, R>)>>>;
+
+    /// The least constrained page turner that allows an implementor to mutate during request
+    /// execution and, therefore, doesn't provide the `pages_ahead` family of methods as it's
+    /// invalid to hold multiple `&mut self` references concurrently. Use
+    /// [`PageTurner::pages_ahead_sequential`] to generate requests ahead with [`RequestAhead`]
+    /// while querying them one by one. For uses in single threaded contexts when you don't want
+    /// to bother with interior mutability of the implementor.
+    ///
+    #[doc = include_str!("../doc/PageTurner")]
+    pub trait PageTurner<R>: Sized {
+        type PageItems;
+        type PageError;
+
+        #[doc = include_str!("../doc/PageTurner__turn_page")]
+        fn turn_page(&mut self, request: R) -> impl Future<Output = TurnedPageResult<Self, R>>;
+
+        #[doc = include_str!("../doc/PageTurner__pages")]
+        fn pages<'s>(&'s mut self, request: R) -> Pages<'s, &'s mut Self, R>
+        where
+            R: 's,
+        {
+            Pages::new(self, request)
+        }
+
+        #[doc = include_str!("../doc/PageTurner__into_pages")]
+        fn into_pages<'s>(self, request: R) -> Pages<'s, Self, R>
+        where
+            Self: 's,
+            R: 's,
+        {
+            Pages::new(self, request)
+        }
+
+        /// Queries pages one by one like [`PageTurner::pages`] but takes requests from
+        /// [`RequestAhead`] instead of responses and respects the [`Limit`] like `pages_ahead` of
+        /// other flavors, so a mutable page turner can serve the same requests. `&mut self` calls
+        /// are never concurrent. The stream ends after the last page, the limit or an error.
+        fn pages_ahead_sequential<'s>(
+            &'s mut self,
+            limit: Limit,
+            request: R,
+        ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+        where
+            R: 's + RequestAhead,
+        {
+            stream::try_unfold(
+                (self, Some(RequestIter::new(request, limit))),
+                |(page_turner, requests)| async move {
+                    let Some(mut requests) = requests else {
+                        return Ok(None);
+                    };
+
+                    let Some(request) = requests.next() else {
+                        return Ok(None);
+                    };
+
+                    let page =
+                        record_latency(requests.latency(), page_turner.turn_page(request)).await?;
+                    let requests = page.next_request.is_some().then_some(requests);
+
+                    Ok(Some((page.items, (page_turner, requests))))
+                },
+            )
+        }
+    }
+
+    impl<P, R> PageTurner<R> for &mut P
+    where
+        P: PageTurner<R>,
+    {
+        type PageItems = PageItems<P, R>;
+        type PageError = PageError<P, R>;
+
+        fn turn_page(&mut self, request: R) -> impl Future<Output = TurnedPageResult<Self, R>> {
+            P::turn_page(self, request)
+        }
+    }
+
+    impl<P, R> PageTurner<R> for Box<P>
+    where
+        P: PageTurner<R>,
+    {
+        type PageItems = PageItems<P, R>;
+        type PageError = PageError<P, R>;
+
+        fn turn_page(&mut self, request: R) -> impl Future<Output = TurnedPageResult<Self, R>> {
             P::turn_page(self.as_mut(), request)
         }
     }
 
-    request_next_page_decl!();
+    /// Creates a page turner from an `FnMut(R) -> impl Future<Output = Result<TurnedPage<I, R>,
+    /// E>>` closure. The closure may mutate its captures between requests which is handy for
+    /// stateful one-off paginations that don't deserve a dedicated struct.
+    ///
+    /// ```
+    /// use page_turner::mutable::{from_fn_mut, prelude::*};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// use futures::TryStreamExt;
+    ///
+    /// let mut calls = 0;
+    /// let mut page_turner = from_fn_mut(|page: u32| {
+    ///     calls += 1;
+    ///     async move { Ok::<_, ()>(TurnedPage::new(vec![page], (page < 3).then_some(page + 1))) }
+    /// });
+    ///
+    /// let pages: Vec<_> = page_turner.pages(1).items().try_collect().await.unwrap();
+    /// assert_eq!(pages, [1, 2, 3]);
+    /// assert_eq!(calls, 3);
+    /// # }
+    /// ```
+    pub fn from_fn_mut<F>(f: F) -> FromFnMut<F> {
+        FromFnMut(f)
+    }
+
+    /// A page turner returned by [`from_fn_mut`]
+    #[derive(Debug, Clone, Copy)]
+    pub struct FromFnMut<F>(F);
+
+    impl<F> FromFnMut<F> {
+        pub fn into_inner(self) -> F {
+            self.0
+        }
+    }
+
+    impl<F, Fut, R, I, E> PageTurner<R> for FromFnMut<F>
+    where
+        F: FnMut(R) -> Fut,
+        Fut: Future<Output = Result<TurnedPage<I, R>, E>>,
+    {
+        type PageItems = I;
+        type PageError = E;
+
+        fn turn_page(&mut self, request: R) -> impl Future<Output = TurnedPageResult<Self, R>> {
+            (self.0)(request)
+        }
+    }
+
+    pages_stream_def!();
 }
 
 #[cfg(test)]
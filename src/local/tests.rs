@@ -1,6 +1,9 @@
-use crate::local::{prelude::*, PageError, PageItems};
+use crate::local::{
+    prelude::*, testing, turner_fn, BoxPagesStream, ItemPageTurner, PageError, PageItems,
+    PagesBuilder, Progress, Total,
+};
 use crate::test_utils::*;
-use futures::TryStreamExt;
+use futures::{StreamExt, TryStreamExt};
 
 #[tokio::test(flavor = "current_thread")]
 async fn pages() {
@@ -8,10 +11,188 @@ async fn pages() {
     generic_pages_usage(NumbersClient::new(17, 3), GetNumbersQuery::default()).await;
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn pages_requests() {
+    pages_requests_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_split() {
+    pages_split_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_infallible() {
+    pages_infallible_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_try_collect_items() {
+    pages_try_collect_items_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_process_concurrently() {
+    pages_process_concurrently_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_boxed_err() {
+    pages_boxed_err_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_only_new_items() {
+    pages_only_new_items_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_tail_durable() {
+    pages_tail_durable_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_lazy() {
+    pages_lazy_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn arc_pages() {
+    arc_pages_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn boxed_pages() {
+    boxed_pages_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_inspect() {
+    pages_inspect_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_decode() {
+    pages_decode_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_with_state() {
+    pages_with_state_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_from_requests() {
+    pages_from_requests_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_with_retry() {
+    pages_with_retry_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_limit_items() {
+    pages_limit_items_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_resumable() {
+    pages_resumable_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_ahead_batched() {
+    pages_ahead_batched_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_ahead_throttled() {
+    pages_ahead_throttled_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_with_timeout() {
+    pages_with_timeout_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_items_streamed() {
+    pages_items_streamed_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn into_pages_ahead_arc() {
+    into_pages_ahead_arc_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_with_progress_handle() {
+    pages_with_progress_handle_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_from_turner_fn() {
+    turner_fn_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_bidirectional() {
+    pages_bidirectional_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn page_turner_combinators() {
+    page_turner_combinators_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_chain() {
+    pages_chain_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_map_ahead() {
+    pages_map_ahead_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_while() {
+    pages_while_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_with_progress() {
+    pages_with_progress_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_ahead_unordered_stats() {
+    blogs_client_overquery_stats_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_ahead_unordered_indexed() {
+    blogs_client_indexed_unordered_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pagination_equivalent() {
+    blogs_client_pagination_equivalent_test!().await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pages_builder() {
+    blogs_client_pages_builder_test!().await;
+    blogs_client_request_injector_test!().await;
+    pages_builder_retry_timeout_test!().await;
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn pages_ahead() {
     pages_ahead_base_test!().await;
     generic_pages_ahead_usage(BlogClient::new(49), GetContentRequest { page: 0 }).await;
+    generic_item_page_turner_usage(BlogClient::new(49), GetContentRequest { page: 0 }).await;
 }
 
 #[tokio::test(flavor = "current_thread")]
@@ -43,6 +224,16 @@ async fn generic_pages_ahead_usage<P, R>(p: P, req: R)
     generic_pages_stream_usage(pages_stream).await;
 }
 
+async fn generic_item_page_turner_usage<P, R>(p: P, req: R)
+where
+    P: ItemPageTurner<R>,
+    R: RequestAhead + Clone,
+    PageError<P, R>: std::fmt::Debug,
+{
+    generic_pages_stream_usage(p.pages(req.clone())).await;
+    generic_pages_stream_usage(p.pages_ahead(2, Limit::None, req)).await;
+}
+
 async fn generic_pages_ahead_unordered_usage<P, R>(p: P, req: R)
 where
     P: PageTurner<R>,
@@ -74,6 +265,64 @@ async fn pages() {
         generic_pages_usage(NumbersClient::new(19, 5), GetNumbersQuery::default()).await;
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn pages_requests() {
+        pages_requests_test!(mut).await;
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn pages_ahead_sequential() {
+        let mut blog = BlogClient::new(10);
+
+        let records: Vec<_> = blog
+            .pages_ahead_sequential(Limit::None, GetContentRequest { page: 0 })
+            .items()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(records, (0..10).map(BlogRecord).collect::<Vec<_>>());
+
+        let records: Vec<_> = blog
+            .pages_ahead_sequential(Limit::Pages(4), GetContentRequest { page: 2 })
+            .items()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(records, (2..6).map(BlogRecord).collect::<Vec<_>>());
+
+        blog.set_error(3);
+        let result: Result<Vec<_>, _> = blog
+            .pages_ahead_sequential(Limit::None, GetContentRequest { page: 0 })
+            .try_collect()
+            .await;
+        assert_eq!(result, Err("Custom error".to_owned()));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn from_fn_mut() {
+        let mut requests = Vec::new();
+
+        let mut page_turner = crate::mutable::from_fn_mut(|page: usize| {
+            requests.push(page);
+
+            async move {
+                match page {
+                    0..=2 => Ok(TurnedPage::next(vec![page], page + 1)),
+                    3 => Ok(TurnedPage::last(vec![page])),
+                    _ => Err(page),
+                }
+            }
+        });
+
+        let items: Vec<_> = page_turner.pages(0).items().try_collect().await.unwrap();
+        assert_eq!(items, [0, 1, 2, 3]);
+
+        let result: Result<Vec<_>, _> = page_turner.pages(7).try_collect().await;
+        assert_eq!(result, Err(7));
+
+        assert_eq!(requests, [0, 1, 2, 3, 7]);
+    }
+
     page_turner_impls!(mut);
 
     async fn generic_pages_usage<P, R>(mut p: P, req: R)
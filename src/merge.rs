@@ -0,0 +1,164 @@
+//! Concurrent pagination of many sources, i.e. shards or regions of the same API.
+//!
+//! [`select_pages`] polls many pages streams at once and yields their pages interleaved in the
+//! order they arrive. Errors are wrapped into [`MergeError`] to tell which stream failed, the
+//! other streams keep going. `merge_pages` of the `mt` and `local` flavors is a shortcut to select
+//! `pages` streams of many page turner and request pairs. Streams of different page turners can be
+//! selected after boxing them with `PagesStream::boxed_pages`.
+//!
+//! ```
+//! use page_turner::prelude::*;
+//!
+//! struct Region(&'static str);
+//!
+//! impl PageTurner<usize> for Region {
+//!     type PageItems = Vec<String>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         let servers = vec![format!("{}-{page}", self.0)];
+//!         Ok(TurnedPage::new(servers, (page < 1).then_some(page + 1)))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let (eu, us) = (Region("eu"), Region("us"));
+//! let mut servers: Vec<_> = page_turner::merge_pages([(&eu, 0), (&us, 0)])
+//!     .items()
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//!
+//! servers.sort();
+//! assert_eq!(servers, ["eu-0", "eu-1", "us-0", "us-1"]);
+//! # }
+//! ```
+
+use crate::internal::async_iterator_impl;
+use futures::{stream::SelectAll, Stream};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// An error of one of the selected streams along with the index of that stream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeError<E> {
+    pub index: usize,
+    pub error: E,
+}
+
+impl<E> std::fmt::Display for MergeError<E>
+where
+    E: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "stream #{} failed: {}", self.index, self.error)
+    }
+}
+
+impl<E> std::error::Error for MergeError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Polls all `streams` concurrently and yields their pages as they arrive. Errors are tagged with
+/// the index of the failed stream in `streams`. See the [module docs](self).
+pub fn select_pages<S, T, E>(streams: impl IntoIterator<Item = S>) -> SelectPages<S>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    SelectPages {
+        streams: streams
+            .into_iter()
+            .enumerate()
+            .map(|(index, stream)| Indexed {
+                stream: Box::pin(stream),
+                index,
+            })
+            .collect(),
+    }
+}
+
+/// A stream returned by [`select_pages`]
+pub struct SelectPages<S> {
+    streams: SelectAll<Indexed<S>>,
+}
+
+impl<S, T, E> Stream for SelectPages<S>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    type Item = Result<T, MergeError<E>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.streams).poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.streams.size_hint()
+    }
+}
+
+async_iterator_impl!([S, T, E] SelectPages<S> where S: Stream<Item = Result<T, E>>);
+
+struct Indexed<S> {
+    stream: Pin<Box<S>>,
+    index: usize,
+}
+
+impl<S, T, E> Stream for Indexed<S>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    type Item = Result<T, MergeError<E>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let index = self.index;
+        self.stream
+            .as_mut()
+            .poll_next(cx)
+            .map(|page| page.map(|page| page.map_err(|error| MergeError { index, error })))
+    }
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::adapters::slice::{SliceError, SlicePage, SlicePageTurner};
+    use crate::mt::prelude::*;
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn interleaves_pages_as_they_arrive() {
+        let slow = SlicePageTurner::new(vec![0, 1, 2, 3], 2).delay(Duration::from_millis(30));
+        let fast = SlicePageTurner::new(vec![10, 11, 12, 13], 2)
+            .delay(Duration::from_millis(5))
+            .fail_at(1, 1);
+
+        let pages: Vec<_> = select_pages([slow.pages(SlicePage(0)), fast.pages(SlicePage(0))])
+            .collect()
+            .await;
+
+        assert_eq!(
+            pages,
+            [
+                Ok(vec![10, 11]),
+                Err(MergeError {
+                    index: 1,
+                    error: SliceError::Injected(1),
+                }),
+                Ok(vec![0, 1]),
+                Ok(vec![2, 3]),
+            ]
+        );
+    }
+}
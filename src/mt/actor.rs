@@ -0,0 +1,212 @@
+//! Turns a client that can't be shared between threads into a cheap to clone [`PageTurner`].
+//!
+//! [`Actor::spawn`] starts a dedicated thread with a single threaded tokio runtime which creates
+//! and owns a [`mutable::PageTurner`](crate::mutable::PageTurner). The returned [`Actor`] is a
+//! handle that sends requests to that thread over a channel, so the client may be `!Send`,
+//! `!Sync` or require `&mut self` and still be used with `into_pages_ahead*` and `'static`
+//! streams. Requests are executed by the client one at a time in the order they were sent.
+//!
+//! The thread stops when the last handle is dropped.
+//!
+//! ```
+//! use page_turner::mt::actor::Actor;
+//! use page_turner::mutable;
+//! use page_turner::prelude::*;
+//! use std::rc::Rc;
+//!
+//! // `Rc` makes the client neither `Send` nor `Sync`
+//! struct Squares {
+//!     base: Rc<u64>,
+//! }
+//!
+//! #[derive(Clone)]
+//! struct Page(u64);
+//!
+//! impl RequestAhead for Page {
+//!     fn next_request(&self) -> Self {
+//!         Page(self.0 + 1)
+//!     }
+//! }
+//!
+//! impl mutable::PageTurner<Page> for Squares {
+//!     type PageItems = Vec<u64>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&mut self, Page(n): Page) -> mutable::TurnedPageResult<Self, Page> {
+//!         let next = (n < 4).then_some(Page(n + 1));
+//!         Ok(TurnedPage::new(vec![*self.base + n * n], next))
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let squares = Actor::spawn(|| Squares { base: Rc::new(100) });
+//! let pages = squares.into_pages_ahead(3, Limit::Pages(5), Page(0));
+//! let numbers: Vec<_> = tokio::spawn(pages.items().try_collect()).await.unwrap().unwrap();
+//!
+//! assert_eq!(numbers, [100, 101, 104, 109, 116]);
+//! # }
+//! ```
+
+use super::{PageTurner, TurnedPageResult};
+use crate::mutable;
+use std::marker::PhantomData;
+use tokio::sync::{mpsc, oneshot};
+
+type Message<P, R> = (R, oneshot::Sender<mutable::TurnedPageResult<P, R>>);
+
+const ACTOR_STOPPED: &str = "The page turner actor has stopped because the page turner panicked";
+
+/// A handle to a page turner running on its own thread. See the [module docs](self).
+pub struct Actor<P, R>
+where
+    P: mutable::PageTurner<R>,
+{
+    requests: mpsc::UnboundedSender<Message<P, R>>,
+    _page_turner: PhantomData<fn() -> P>,
+}
+
+impl<P, R> Actor<P, R>
+where
+    P: 'static + mutable::PageTurner<R>,
+    P::PageItems: 'static + Send,
+    P::PageError: 'static + Send,
+    R: 'static + Send,
+{
+    /// Spawns a thread which creates a page turner with `make_page_turner` and serves requests
+    /// of all handles cloned from the returned one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS fails to spawn a thread or if a tokio runtime can't be created.
+    pub fn spawn<F>(make_page_turner: F) -> Self
+    where
+        F: 'static + Send + FnOnce() -> P,
+    {
+        let (requests, mut inbox) = mpsc::unbounded_channel::<Message<P, R>>();
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to build a runtime for the page turner actor");
+
+        std::thread::Builder::new()
+            .name("page-turner-actor".to_owned())
+            .spawn(move || {
+                runtime.block_on(async move {
+                    let mut page_turner = make_page_turner();
+
+                    while let Some((request, reply)) = inbox.recv().await {
+                        // The receiver might be gone if the stream was dropped, that's fine
+                        let _ = reply.send(page_turner.turn_page(request).await);
+                    }
+                })
+            })
+            .expect("Failed to spawn the page turner actor thread");
+
+        Self {
+            requests,
+            _page_turner: PhantomData,
+        }
+    }
+}
+
+impl<P, R> Clone for Actor<P, R>
+where
+    P: mutable::PageTurner<R>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            requests: self.requests.clone(),
+            _page_turner: PhantomData,
+        }
+    }
+}
+
+impl<P, R> PageTurner<R> for Actor<P, R>
+where
+    P: mutable::PageTurner<R>,
+    P::PageItems: Send,
+    P::PageError: Send,
+    R: Send,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> TurnedPageResult<Self, R> {
+        let (reply, response) = oneshot::channel();
+
+        self.requests.send((request, reply)).expect(ACTOR_STOPPED);
+        response.await.expect(ACTOR_STOPPED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+    use std::{cell::RefCell, rc::Rc};
+
+    #[derive(Clone)]
+    struct Ix(usize);
+
+    impl RequestAhead for Ix {
+        fn next_request(&self) -> Self {
+            Ix(self.0 + 1)
+        }
+    }
+
+    struct Recorder {
+        requests: Rc<RefCell<Vec<usize>>>,
+        pages: usize,
+    }
+
+    impl mutable::PageTurner<Ix> for Recorder {
+        type PageItems = Vec<usize>;
+        type PageError = usize;
+
+        async fn turn_page(&mut self, Ix(ix): Ix) -> mutable::TurnedPageResult<Self, Ix> {
+            self.requests.borrow_mut().push(ix);
+            tokio::task::yield_now().await;
+
+            if ix >= self.pages {
+                return Err(ix);
+            }
+
+            Ok(TurnedPage::new(
+                vec![ix],
+                (ix + 1 < self.pages).then_some(Ix(ix + 1)),
+            ))
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serves_requests_of_all_handles() {
+        let recorder = Actor::spawn(|| Recorder {
+            requests: Rc::default(),
+            pages: 20,
+        });
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let pages = recorder
+                    .clone()
+                    .into_pages_ahead(5, Limit::None, Ix(0))
+                    .items()
+                    .try_collect::<Vec<_>>();
+
+                tokio::spawn(pages)
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.await.unwrap(), Ok((0..20).collect()));
+        }
+
+        let result = recorder.pages(Ix(25)).try_collect::<Vec<_>>().await;
+        assert_eq!(result, Err(25));
+    }
+}
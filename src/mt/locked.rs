@@ -0,0 +1,148 @@
+//! Allows to use a [`mutable::PageTurner`](crate::mutable::PageTurner) from multithreaded
+//! executors by serializing its calls through a [`tokio::sync::Mutex`].
+//!
+//! Futures of mutable page turners aren't required to be `Send`, therefore, [`Locked`] drives
+//! every request to completion on a blocking thread of the current tokio runtime while holding the
+//! lock. This is intended for sequential streams like [`PageTurner::pages`], `pages_ahead*` methods
+//! work as well but their requests are queried one at a time.
+//!
+//! ```
+//! use page_turner::mt::locked::Locked;
+//! use page_turner::mutable;
+//! use page_turner::prelude::*;
+//!
+//! struct Countdown {
+//!     requests_sent: usize,
+//! }
+//!
+//! impl mutable::PageTurner<u32> for Countdown {
+//!     type PageItems = Vec<u32>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&mut self, n: u32) -> mutable::TurnedPageResult<Self, u32> {
+//!         self.requests_sent += 1;
+//!         Ok(TurnedPage::new(vec![n], n.checked_sub(1)))
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let countdown = Locked::new(Countdown { requests_sent: 0 });
+//! let numbers: Vec<_> = tokio::spawn(countdown.clone().into_pages(3).items().try_collect())
+//!     .await
+//!     .unwrap()
+//!     .unwrap();
+//!
+//! assert_eq!(numbers, [3, 2, 1, 0]);
+//! assert_eq!(countdown.into_inner().lock().await.requests_sent, 4);
+//! # }
+//! ```
+
+use super::{PageTurner, TurnedPageResult};
+use crate::mutable;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Implements [`PageTurner`] for a shared mutable page turner. Cloning is cheap and all clones
+/// share the same page turner.
+#[derive(Debug)]
+pub struct Locked<P>(Arc<Mutex<P>>);
+
+impl<P> Locked<P> {
+    pub fn new(page_turner: P) -> Self {
+        Self(Arc::new(Mutex::new(page_turner)))
+    }
+
+    pub fn into_inner(self) -> Arc<Mutex<P>> {
+        self.0
+    }
+}
+
+impl<P> Clone for Locked<P> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<P> From<Arc<Mutex<P>>> for Locked<P> {
+    fn from(page_turner: Arc<Mutex<P>>) -> Self {
+        Self(page_turner)
+    }
+}
+
+impl<P, R> PageTurner<R> for Locked<P>
+where
+    P: 'static + Send + mutable::PageTurner<R>,
+    P::PageItems: 'static + Send,
+    P::PageError: 'static + Send,
+    R: 'static + Send,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> TurnedPageResult<Self, R> {
+        let mut page_turner = self.0.clone().lock_owned().await;
+        let runtime = tokio::runtime::Handle::current();
+
+        let result =
+            tokio::task::spawn_blocking(move || runtime.block_on(page_turner.turn_page(request)))
+                .await;
+
+        match result {
+            Ok(result) => result,
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(e) => panic!("BUG(page-turner): The runtime is shutting down: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+
+    #[derive(Default)]
+    struct Recorder {
+        requests: Vec<usize>,
+    }
+
+    impl mutable::PageTurner<usize> for Recorder {
+        type PageItems = Vec<usize>;
+        type PageError = ();
+
+        async fn turn_page(&mut self, page: usize) -> mutable::TurnedPageResult<Self, usize> {
+            self.requests.push(page);
+            tokio::task::yield_now().await;
+            Ok(TurnedPage::new(vec![page], (page < 9).then_some(page + 1)))
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serializes_mutable_calls() {
+        let recorder = Locked::new(Recorder::default());
+
+        let handles: Vec<_> = (0..3)
+            .map(|_| {
+                let recorder = recorder.clone();
+                tokio::spawn(
+                    async move { recorder.into_pages(0).items().try_collect::<Vec<_>>().await },
+                )
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.await.unwrap(), Ok((0..10).collect()));
+        }
+
+        let recorder = recorder.into_inner();
+        let mut requests = std::mem::take(&mut recorder.lock().await.requests);
+        requests.sort();
+        assert_eq!(
+            requests,
+            (0..10).flat_map(|page| [page; 3]).collect::<Vec<_>>()
+        );
+    }
+}
@@ -2,13 +2,40 @@
 //! [`dynamic`] if you also need `dyn PageTurner` objects for some reason.
 
 use crate::internal::*;
-use futures::stream::{self, FuturesOrdered, FuturesUnordered, Stream, StreamExt, TryStreamExt};
-use std::{future::Future, pin::Pin};
+use futures::stream::{
+    self, FuturesOrdered, FuturesUnordered, Stream, StreamExt, TryStream, TryStreamExt,
+};
+use std::{
+    convert::Infallible,
+    future::{self, Future},
+    pin::Pin,
+};
 
-pub use crate::{Limit, RequestAhead, TurnedPage};
+use crate::bidirectional::{Backward, WithPrev};
+use crate::blocking::{BlockOn, BlockingPages, ParkThread};
+use crate::cancel::{Cancellable, Cancellation};
+use crate::chain::{ChainState, ChainSummary};
+use crate::combinators::{AndThenPage, MapErr, MapItems, OrElse};
+pub use crate::inject::RequestInjector;
+use crate::merge::{select_pages, SelectPages};
+use crate::progress::{ProgressHandle, Reporting};
+use crate::retry::{Retry, RetryPolicy};
+pub use crate::split::{SplitItems, SplitPages};
+use crate::stats::{StreamStats, WithStats};
+pub use crate::summary::{RunOutcome, RunReport, RunSummary, Summarized};
+use crate::throttle::{Rate, Throttled};
+pub use crate::throughput::{Throughput, ThroughputStats};
+use crate::timeout::{Timeout, TimeoutError};
+use crate::watch::{CursorStore, HighWaterMark, KeyOrder};
+pub use crate::{
+    AdaptiveAhead, BoxError, Limit, PaginationState, ProcessSummary, Progress, RequestAhead,
+    SchedulingMode, StreamId, Total, TurnedPage,
+};
 #[doc = include_str!("../doc/prelude")]
 pub mod prelude {
-    pub use super::{Limit, PageTurner, PagesStream, RequestAhead, TurnedPage, TurnedPageResult};
+    pub use super::{
+        ArcPageTurner, Limit, PageTurner, PagesStream, RequestAhead, TurnedPage, TurnedPageResult,
+    };
 }
 
 #[doc = include_str!("../doc/PageItems")]
@@ -21,180 +48,1359 @@ pub mod prelude {
 pub type PageTurnerFuture<'a, P, R> =
     Pin<Box<dyn 'a + Send + Future<Output = TurnedPageResult<P, R>>>>;
 
-type NumberedRequestFuture<'a, P, R> =
-    Pin<Box<dyn 'a + Send + Future<Output = (usize, TurnedPageResult<P, R>)>>>;
+#[doc = include_str!("../doc/BoxPagesStream")]
+pub type BoxPagesStream<'a, T, E> = Pin<Box<dyn 'a + Send + Stream<Item = Result<T, E>>>>;
+
+type NumberedRequestFuture<'a, P, R> =
+    Pin<Box<dyn 'a + Send + Future<Output = (usize, TurnedPageResult<P, R>)>>>;
+type PagesBoxStream<'a, T> = Pin<Box<dyn 'a + Send + Stream<Item = T>>>;
+type PagesFuture<'a, P, R> = Pin<Box<dyn 'a + Send + Future<Output = (P, TurnedPageResult<P, R>)>>>;
+
+/// A page turner suitable for use in multithreaded contexts
+///
+#[doc = include_str!("../doc/PageTurner")]
+pub trait PageTurner<R>: Sized + Send + Sync
+where
+    R: Send,
+{
+    type PageItems: Send;
+    type PageError: Send;
+
+    #[doc = include_str!("../doc/PageTurner__turn_page")]
+    fn turn_page(&self, request: R) -> impl Send + Future<Output = TurnedPageResult<Self, R>>;
+
+    #[doc = include_str!("../doc/PageTurner__turn_pages")]
+    fn turn_pages(
+        &self,
+        requests: Vec<R>,
+    ) -> impl Send + Future<Output = Vec<TurnedPageResult<Self, R>>> {
+        futures::future::join_all(requests.into_iter().map(|request| self.turn_page(request)))
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages")]
+    fn pages<'s>(&'s self, request: R) -> Pages<'s, &'s Self, R>
+    where
+        R: 's,
+    {
+        Pages::new(self, request)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_resumable")]
+    fn pages_resumable<'s>(&'s self, request: R) -> ResumablePages<'s, &'s Self, R>
+    where
+        R: 's + Clone,
+    {
+        Pages::new(self, request).resumable()
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_while")]
+    fn pages_while<'s, F>(
+        &'s self,
+        request: R,
+        pred: F,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's,
+        F: 's + Send + FnMut(&Self::PageItems) -> bool,
+    {
+        pages_while(self.pages(request), pred)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__into_pages")]
+    fn into_pages<'s>(self, request: R) -> Pages<'s, Self, R>
+    where
+        R: 's,
+        Self: 's,
+    {
+        Pages::new(self, request)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_ahead")]
+    fn pages_ahead<'s>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + RequestAhead,
+    {
+        stream::try_unfold(
+            Box::new(PagesAheadState::new(
+                self,
+                request,
+                requests_ahead_count,
+                limit,
+            )),
+            request_pages_ahead,
+        )
+    }
+
+    #[doc = include_str!("../doc/PageTurner__into_pages_ahead")]
+    fn into_pages_ahead<'s>(
+        self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + RequestAhead,
+        Self: 's + Clone,
+    {
+        stream::try_unfold(
+            Box::new(PagesAheadState::new(
+                self,
+                request,
+                requests_ahead_count,
+                limit,
+            )),
+            request_pages_ahead,
+        )
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_ahead_unordered")]
+    fn pages_ahead_unordered<'s>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> PagesAheadUnordered<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + RequestAhead,
+    {
+        PagesAheadUnordered::new(PagesAheadUnorderedState::new(
+            self,
+            request,
+            requests_ahead_count,
+            limit,
+        ))
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_ahead_unordered_indexed")]
+    fn pages_ahead_unordered_indexed<'s>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> IndexedPagesAheadUnordered<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + RequestAhead,
+    {
+        self.pages_ahead_unordered(requests_ahead_count, limit, request)
+            .indexed()
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_map_ahead")]
+    fn pages_map_ahead<'s, U, F, Fut>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+        f: F,
+    ) -> impl PagesStream<'s, U, Self::PageError>
+    where
+        R: 's + RequestAhead,
+        F: 's + Send + FnMut(Self::PageItems) -> Fut,
+        Fut: 's + Send + Future<Output = Result<U, Self::PageError>>,
+        U: Send,
+    {
+        self.pages_ahead(requests_ahead_count, limit, request)
+            .map_ok(f)
+            .try_buffered(requests_ahead_count.max(1))
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_ahead_while")]
+    fn pages_ahead_while<'s, F>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+        pred: F,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + RequestAhead,
+        F: 's + Send + FnMut(&Self::PageItems) -> bool,
+    {
+        pages_while(self.pages_ahead(requests_ahead_count, limit, request), pred)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__into_pages_ahead_unordered")]
+    fn into_pages_ahead_unordered<'s>(
+        self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> PagesAheadUnordered<'s, Self::PageItems, Self::PageError>
+    where
+        Self: 's + Clone,
+        R: 's + RequestAhead,
+    {
+        PagesAheadUnordered::new(PagesAheadUnorderedState::new(
+            self,
+            request,
+            requests_ahead_count,
+            limit,
+        ))
+    }
+
+    /// Like [`PageTurner::into_pages_ahead`] but moves the page turner into an
+    /// [`std::sync::Arc`] shared by concurrent requests instead of cloning it, so it doesn't
+    /// need to implement `Clone`
+    fn into_pages_ahead_arc<'s>(
+        self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        Self: 's,
+        R: 's + RequestAhead,
+    {
+        std::sync::Arc::new(self).into_pages_ahead(requests_ahead_count, limit, request)
+    }
+
+    /// Like [`PageTurner::into_pages_ahead_unordered`] but moves the page turner into an
+    /// [`std::sync::Arc`] shared by concurrent requests instead of cloning it, so it doesn't
+    /// need to implement `Clone`
+    fn into_pages_ahead_unordered_arc<'s>(
+        self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> PagesAheadUnordered<'s, Self::PageItems, Self::PageError>
+    where
+        Self: 's,
+        R: 's + RequestAhead,
+    {
+        std::sync::Arc::new(self).into_pages_ahead_unordered(requests_ahead_count, limit, request)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_from_requests")]
+    fn pages_from_requests<'s, I>(
+        &'s self,
+        requests: I,
+        concurrency: usize,
+        mode: SchedulingMode,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's,
+        I: IntoIterator<Item = R>,
+        I::IntoIter: 's + Send,
+    {
+        let requests = requests.into_iter();
+        let concurrency = concurrency.max(1);
+
+        let stream: PagesBoxStream<'s, Result<Self::PageItems, Self::PageError>> = match mode {
+            SchedulingMode::Ordered => Box::pin(stream::try_unfold(
+                Box::new(PagesAheadState::from_requests(
+                    self,
+                    requests,
+                    concurrency,
+                    None,
+                )),
+                request_pages_ahead,
+            )),
+            SchedulingMode::Unordered => Box::pin(
+                stream::try_unfold(
+                    Box::new(PagesAheadUnorderedState::with_requests(
+                        self,
+                        NumberedRequests::sequential(requests),
+                        concurrency,
+                        None,
+                    )),
+                    request_pages_ahead_unordered,
+                )
+                .map_ok(|(_, items)| items),
+            ),
+        };
+
+        stream
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_with_state")]
+    fn pages_with_state<'s, S>(
+        &'s self,
+        request: R,
+        state: S,
+    ) -> impl 's + Send + Stream<Item = Result<Self::PageItems, Self::PageError>>
+    where
+        R: 's,
+        S: 's + Send + PaginationState<R, Self::PageItems>,
+    {
+        stream::try_unfold(
+            (Some(request), state),
+            move |(request, mut state)| async move {
+                let Some(request) = request else {
+                    return Ok(None);
+                };
+
+                let TurnedPage {
+                    items,
+                    next_request,
+                } = self.turn_page(request).await?;
+                let next_request = state.next_request(&items, next_request);
+
+                Ok(Some((items, (next_request, state))))
+            },
+        )
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_with_retry")]
+    fn pages_with_retry<'s, Policy>(
+        &'s self,
+        policy: Policy,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + Clone,
+        Policy: 's + Send + Sync + RetryPolicy<Self::PageError>,
+    {
+        Retry::new(self, policy).into_pages(request)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_ahead_with_retry")]
+    fn pages_ahead_with_retry<'s, Policy>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        policy: Policy,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + Clone + RequestAhead,
+        Policy: 's + Send + Sync + RetryPolicy<Self::PageError> + Clone,
+    {
+        Retry::new(self, policy).into_pages_ahead(requests_ahead_count, limit, request)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_with_timeout")]
+    fn pages_with_timeout<'s>(
+        &'s self,
+        timeout: std::time::Duration,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, TimeoutError<Self::PageError>>
+    where
+        R: 's,
+    {
+        Timeout::new(self, timeout).into_pages(request)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_ahead_with_timeout")]
+    fn pages_ahead_with_timeout<'s>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        timeout: std::time::Duration,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, TimeoutError<Self::PageError>>
+    where
+        R: 's + RequestAhead,
+    {
+        Timeout::new(self, timeout).into_pages_ahead(requests_ahead_count, limit, request)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__map_items")]
+    fn map_items<F, I>(self, f: F) -> MapItems<Self, F>
+    where
+        F: Fn(Self::PageItems) -> I,
+    {
+        MapItems::new(self, f)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__map_err")]
+    fn map_err<F, E>(self, f: F) -> MapErr<Self, F>
+    where
+        F: Fn(Self::PageError) -> E,
+    {
+        MapErr::new(self, f)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__and_then_page")]
+    fn and_then_page<F, I>(self, f: F) -> AndThenPage<Self, F>
+    where
+        F: Fn(Self::PageItems) -> Result<I, Self::PageError>,
+    {
+        AndThenPage::new(self, f)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__or_else")]
+    fn or_else<Q>(self, fallback: Q) -> OrElse<Self, Q>
+    where
+        Q: PageTurner<R, PageItems = Self::PageItems, PageError = Self::PageError>,
+        R: Clone,
+    {
+        OrElse::new(self, fallback)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_backward")]
+    fn pages_backward<'s, I>(&'s self, request: R) -> impl PagesStream<'s, I, Self::PageError>
+    where
+        Self: PageTurner<R, PageItems = WithPrev<I, R>>,
+        R: 's,
+        I: Send,
+    {
+        Backward::new(self).into_pages(request)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_bidirectional")]
+    fn pages_bidirectional(&self, request: R) -> BidirectionalPages<'_, Self, R> {
+        BidirectionalPages::new(self, request)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_with_progress")]
+    fn pages_with_progress<'s>(
+        &'s self,
+        request: R,
+        progress: ProgressHandle<R>,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + Clone,
+        for<'t> &'t Self::PageItems: IntoIterator,
+    {
+        Reporting::new(self, progress).into_pages(request)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_chain")]
+    fn pages_chain<'s, R2, F>(
+        &'s self,
+        request: R,
+        next_query: F,
+    ) -> impl PagesStream<'s, PageItems<Self, R>, PageError<Self, R>>
+    where
+        Self: PageTurner<R2, PageItems = PageItems<Self, R>, PageError = PageError<Self, R>>,
+        R: 's + Clone,
+        R2: 's + Send,
+        F: 's + Send + FnOnce(ChainSummary<R>) -> Option<R2>,
+        for<'t> &'t PageItems<Self, R>: IntoIterator,
+    {
+        stream::try_unfold(
+            ChainState::new(request, next_query),
+            move |state| async move {
+                match state {
+                    ChainState::First {
+                        request,
+                        pages,
+                        items: items_count,
+                        next_query,
+                    } => {
+                        let TurnedPage {
+                            items,
+                            next_request,
+                        } = PageTurner::<R>::turn_page(self, request.clone()).await?;
+
+                        let pages = pages + 1;
+                        let items_count = items_count + (&items).into_iter().count();
+
+                        let state = match next_request {
+                            Some(request) => ChainState::First {
+                                request,
+                                pages,
+                                items: items_count,
+                                next_query,
+                            },
+                            None => next_query(ChainSummary {
+                                pages,
+                                items: items_count,
+                                last_request: request,
+                            })
+                            .map_or(ChainState::Done, ChainState::Second),
+                        };
+
+                        Ok(Some((items, state)))
+                    }
+                    ChainState::Second(request) => {
+                        let TurnedPage {
+                            items,
+                            next_request,
+                        } = PageTurner::<R2>::turn_page(self, request).await?;
+
+                        Ok(Some((
+                            items,
+                            next_request.map_or(ChainState::Done, ChainState::Second),
+                        )))
+                    }
+                    ChainState::Done => Ok(None),
+                }
+            },
+        )
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_ahead_throttled")]
+    fn pages_ahead_throttled<'s>(
+        &'s self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        rate: Rate,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + RequestAhead,
+    {
+        Throttled::new(self, rate).into_pages_ahead(requests_ahead_count, limit, request)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_ahead_batched")]
+    fn pages_ahead_batched<'s>(
+        &'s self,
+        batch_size: usize,
+        limit: Limit,
+        request: R,
+    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    where
+        R: 's + RequestAhead,
+    {
+        stream::unfold(
+            Some(RequestIter::new(request, limit)),
+            move |requests| async move {
+                let mut requests = requests?;
+                let batch: Vec<_> = requests.by_ref().take(batch_size.max(1)).collect();
+                if batch.is_empty() {
+                    return None;
+                }
+
+                let mut pages = record_latency(requests.latency(), self.turn_pages(batch)).await;
+
+                // The last page or an error end the stream, pages after them are discarded
+                let end = pages.iter().position(|page| {
+                    !matches!(
+                        page,
+                        Ok(TurnedPage {
+                            next_request: Some(_),
+                            ..
+                        })
+                    )
+                });
+
+                let requests = match end {
+                    Some(end) => {
+                        pages.truncate(end + 1);
+                        None
+                    }
+                    None => Some(requests),
+                };
+
+                Some((stream::iter(pages), requests))
+            },
+        )
+        .flatten()
+        .map_ok(|page| page.items)
+    }
+
+    #[doc = include_str!("../doc/PageTurner__pages_tail_durable")]
+    fn pages_tail_durable<'s, S>(
+        &'s self,
+        store: S,
+        request: R,
+        interval: std::time::Duration,
+    ) -> impl 's + Send + Stream<Item = Result<Self::PageItems, TailError<Self::PageError, S::Error>>>
+    where
+        R: 's + Clone,
+        S: 's + Send + CursorStore<R>,
+    {
+        stream::try_unfold(
+            Box::new(TailState::new(self, store, request, interval)),
+            request_tail_page,
+        )
+    }
+}
+
+impl<D, P, R> PageTurner<R> for D
+where
+    D: Send + Sync + std::ops::Deref<Target = P>,
+    P: PageTurner<R>,
+    R: Send,
+{
+    type PageItems = PageItems<P, R>;
+    type PageError = PageError<P, R>;
+
+    async fn turn_page(&self, request: R) -> TurnedPageResult<Self, R> {
+        self.deref().turn_page(request).await
+    }
+
+    async fn turn_pages(&self, requests: Vec<R>) -> Vec<TurnedPageResult<Self, R>> {
+        self.deref().turn_pages(requests).await
+    }
+}
+
+#[doc = include_str!("../doc/ItemPageTurner")]
+pub trait ItemPageTurner<R>: PageTurner<R, PageItems = <Self as ItemPageTurner<R>>::Items>
+where
+    R: Send,
+{
+    /// The same type as [`PageTurner::PageItems`]
+    type Items: Send
+        + IntoIterator<
+            Item = <Self as ItemPageTurner<R>>::Item,
+            IntoIter = <Self as ItemPageTurner<R>>::ItemsIter,
+        >;
+    type Item: Send;
+    type ItemsIter: Send + Iterator<Item = <Self as ItemPageTurner<R>>::Item>;
+}
+
+impl<P, R> ItemPageTurner<R> for P
+where
+    P: PageTurner<R>,
+    P::PageItems: IntoIterator,
+    <P::PageItems as IntoIterator>::Item: Send,
+    <P::PageItems as IntoIterator>::IntoIter: Send,
+    R: Send,
+{
+    type Items = P::PageItems;
+    type Item = <P::PageItems as IntoIterator>::Item;
+    type ItemsIter = <P::PageItems as IntoIterator>::IntoIter;
+}
+
+#[doc = include_str!("../doc/ArcPageTurner")]
+pub trait ArcPageTurner<P, R>
+where
+    P: PageTurner<R>,
+    R: 'static + Send,
+{
+    /// Like [`PageTurner::into_pages`] on a clone of the [`std::sync::Arc`]
+    fn arc_pages(&self, request: R) -> Pages<'static, std::sync::Arc<P>, R>;
+
+    /// Like [`PageTurner::into_pages_ahead`] on a clone of the [`std::sync::Arc`]. The stream is
+    /// boxed to not borrow `self`.
+    fn arc_pages_ahead(
+        &self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> BoxPagesStream<'static, P::PageItems, P::PageError>
+    where
+        R: RequestAhead;
+
+    /// Like [`PageTurner::into_pages_ahead_unordered`] on a clone of the [`std::sync::Arc`]
+    fn arc_pages_ahead_unordered(
+        &self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> PagesAheadUnordered<'static, P::PageItems, P::PageError>
+    where
+        R: RequestAhead;
+}
+
+impl<P, R> ArcPageTurner<P, R> for std::sync::Arc<P>
+where
+    P: 'static + PageTurner<R>,
+    R: 'static + Send,
+{
+    fn arc_pages(&self, request: R) -> Pages<'static, std::sync::Arc<P>, R> {
+        std::sync::Arc::clone(self).into_pages(request)
+    }
+
+    fn arc_pages_ahead(
+        &self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> BoxPagesStream<'static, P::PageItems, P::PageError>
+    where
+        R: RequestAhead,
+    {
+        std::sync::Arc::clone(self)
+            .into_pages_ahead(requests_ahead_count, limit, request)
+            .boxed_pages()
+    }
+
+    fn arc_pages_ahead_unordered(
+        &self,
+        requests_ahead_count: usize,
+        limit: Limit,
+        request: R,
+    ) -> PagesAheadUnordered<'static, P::PageItems, P::PageError>
+    where
+        R: RequestAhead,
+    {
+        std::sync::Arc::clone(self).into_pages_ahead_unordered(requests_ahead_count, limit, request)
+    }
+}
+
+#[doc = include_str!("../doc/PagesStream")]
+pub trait PagesStream<'a, T, E>: Send + Stream<Item = Result<T, E>>
+where
+    T: Send,
+    E: Send,
+{
+    #[doc = include_str!("../doc/PagesStream__boxed_pages")]
+    fn boxed_pages(self) -> BoxPagesStream<'a, T, E>
+    where
+        Self: 'a;
+
+    #[doc = include_str!("../doc/PagesStream__items")]
+    fn items(self) -> impl 'a + Send + Stream<Item = Result<<T as IntoIterator>::Item, E>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        <T as IntoIterator>::Item: Send,
+        <T as IntoIterator>::IntoIter: Send;
+
+    #[doc = include_str!("../doc/PagesStream__items_streamed")]
+    fn items_streamed<I, IE>(self) -> impl 'a + Send + Stream<Item = Result<I, IE>>
+    where
+        Self: 'a,
+        T: Send + TryStream<Ok = I, Error = IE>,
+        IE: From<E>;
+
+    #[doc = include_str!("../doc/PagesStream__try_collect_items")]
+    fn try_collect_items<C>(self) -> impl 'a + Send + Future<Output = Result<C, E>>
+    where
+        Self: 'a,
+        E: 'a,
+        T: IntoIterator,
+        C: 'a + Send + Default + Extend<<T as IntoIterator>::Item>;
+
+    #[doc = include_str!("../doc/PagesStream__try_collect_items_capped")]
+    fn try_collect_items_capped<C>(
+        self,
+        max_items: usize,
+    ) -> impl 'a + Send + Future<Output = Result<C, E>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        C: 'a + Send + Default + Extend<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: Send,
+        <T as IntoIterator>::IntoIter: Send;
+
+    #[doc = include_str!("../doc/PagesStream__try_collect_items_into")]
+    fn try_collect_items_into<C>(
+        self,
+        collection: C,
+    ) -> impl 'a + Send + Future<Output = Result<C, E>>
+    where
+        Self: 'a,
+        E: 'a,
+        T: IntoIterator,
+        C: 'a + Send + Extend<<T as IntoIterator>::Item>;
+
+    #[doc = include_str!("../doc/PagesStream__process_pages_concurrently")]
+    fn process_pages_concurrently<F, Fut, H>(
+        self,
+        concurrency: usize,
+        handler: F,
+    ) -> impl 'a + Send + Future<Output = ProcessSummary<E, H>>
+    where
+        Self: 'a,
+        E: 'a,
+        F: 'a + Send + FnMut(T) -> Fut,
+        Fut: 'a + Send + Future<Output = Result<(), H>>,
+        H: 'a + Send;
+
+    #[doc = include_str!("../doc/PagesStream__only_new_items")]
+    fn only_new_items<K, F>(
+        self,
+        mark: HighWaterMark<K>,
+        order: KeyOrder,
+        key: F,
+    ) -> impl 'a + Send + Stream<Item = Result<<T as IntoIterator>::Item, E>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        K: 'a + Send + Ord + Clone,
+        F: 'a + Send + FnMut(&<T as IntoIterator>::Item) -> K,
+        <T as IntoIterator>::Item: Send,
+        <T as IntoIterator>::IntoIter: Send;
+
+    #[doc = include_str!("../doc/PagesStream__lazy_pages")]
+    fn lazy_pages<F, U>(
+        self,
+        decode: F,
+    ) -> impl 'a + Send + Stream<Item = Result<impl 'a + Send + Stream<Item = U>, E>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        F: 'a + Send + Clone + FnMut(<T as IntoIterator>::Item) -> U,
+        <T as IntoIterator>::IntoIter: 'a + Send;
+
+    #[doc = include_str!("../doc/PagesStream__decode_pages")]
+    fn decode_pages<F, Fut, U>(
+        self,
+        concurrency: usize,
+        decode: F,
+    ) -> impl 'a + Send + Stream<Item = Result<U, E>>
+    where
+        Self: 'a,
+        E: 'a,
+        F: 'a + Send + FnMut(T) -> Fut,
+        Fut: 'a + Send + Future<Output = Result<U, E>>,
+        U: 'a + Send;
+
+    #[doc = include_str!("../doc/PagesStream__split_items")]
+    fn split_items(self) -> (SplitPages<Self, T, E>, SplitItems<Self, T, E>)
+    where
+        Self: Sized,
+        T: IntoIterator + Clone,
+        E: Clone;
+
+    #[doc = include_str!("../doc/PagesStream__pages_infallible")]
+    fn pages_infallible(self) -> impl 'a + Send + Stream<Item = T>
+    where
+        Self: 'a,
+        E: Into<Infallible>;
+
+    #[doc = include_str!("../doc/PagesStream__pages_boxed_err")]
+    fn pages_boxed_err(self) -> impl 'a + Send + Stream<Item = Result<T, BoxError>>
+    where
+        Self: 'a,
+        E: 'a + Into<BoxError>;
+
+    #[doc = include_str!("../doc/PagesStream__items_boxed_err")]
+    fn items_boxed_err(
+        self,
+    ) -> impl 'a + Send + Stream<Item = Result<<T as IntoIterator>::Item, BoxError>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        E: 'a + Into<BoxError>,
+        <T as IntoIterator>::Item: Send,
+        <T as IntoIterator>::IntoIter: Send;
+
+    #[doc = include_str!("../doc/PagesStream__blocking")]
+    fn blocking(self) -> BlockingPages<Self, ParkThread>
+    where
+        Self: Sized;
+
+    #[doc = include_str!("../doc/PagesStream__blocking_on")]
+    fn blocking_on<B>(self, executor: B) -> BlockingPages<Self, B>
+    where
+        Self: Sized,
+        B: BlockOn;
+
+    #[doc = include_str!("../doc/PagesStream__until_cancelled")]
+    fn until_cancelled<C>(self, token: C) -> Cancellable<Self, C>
+    where
+        Self: Sized,
+        C: Cancellation;
+
+    #[doc = include_str!("../doc/PagesStream__with_throughput")]
+    fn with_throughput(self, stats: ThroughputStats) -> Throughput<Self>
+    where
+        Self: Sized,
+        for<'t> &'t T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__with_stats")]
+    fn with_stats(self, stats: StreamStats) -> WithStats<Self>
+    where
+        Self: Sized,
+        for<'t> &'t T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__with_summary")]
+    fn with_summary<R>(self, report: RunReport<R>) -> Summarized<Self, R>
+    where
+        Self: Sized,
+        for<'t> &'t T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__inspect_page")]
+    fn inspect_page<F>(self, f: F) -> impl 'a + Send + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        F: 'a + Send + FnMut(&T);
+
+    #[doc = include_str!("../doc/PagesStream__inspect_page_err")]
+    fn inspect_page_err<F>(self, f: F) -> impl 'a + Send + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        F: 'a + Send + FnMut(&E);
+
+    #[doc = include_str!("../doc/PagesStream__limit_items")]
+    fn limit_items(self, max_items: usize) -> impl 'a + Send + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        for<'t> &'t T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__with_progress")]
+    fn with_progress(
+        self,
+        total: Total,
+    ) -> impl 'a + Send + Stream<Item = Result<(T, Progress), E>>
+    where
+        Self: 'a,
+        for<'t> &'t T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__with_pages_progress_bar")]
+    #[cfg(feature = "indicatif")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "indicatif")))]
+    fn with_pages_progress_bar(
+        self,
+        bar: indicatif::ProgressBar,
+    ) -> impl 'a + Send + Stream<Item = Result<T, E>>
+    where
+        Self: 'a;
+
+    #[doc = include_str!("../doc/PagesStream__with_items_progress_bar")]
+    #[cfg(feature = "indicatif")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "indicatif")))]
+    fn with_items_progress_bar(
+        self,
+        bar: indicatif::ProgressBar,
+    ) -> impl 'a + Send + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        for<'t> &'t T: IntoIterator;
+
+    #[doc = include_str!("../doc/PagesStream__items_infallible")]
+    fn items_infallible(self) -> impl 'a + Send + Stream<Item = <T as IntoIterator>::Item>
+    where
+        Self: 'a,
+        E: Into<Infallible>,
+        T: IntoIterator,
+        <T as IntoIterator>::Item: Send,
+        <T as IntoIterator>::IntoIter: Send;
+
+    #[doc = include_str!("../doc/PagesStream__collect_items")]
+    fn collect_items<C>(self) -> impl 'a + Send + Future<Output = C>
+    where
+        Self: 'a,
+        E: 'a + Into<Infallible>,
+        T: IntoIterator,
+        C: 'a + Send + Default + Extend<<T as IntoIterator>::Item>;
+}
+
+impl<'a, S, T, E> PagesStream<'a, T, E> for S
+where
+    T: Send,
+    E: Send,
+    S: Send + Stream<Item = Result<T, E>>,
+{
+    fn boxed_pages(self) -> BoxPagesStream<'a, T, E>
+    where
+        Self: 'a,
+    {
+        Box::pin(self)
+    }
+
+    fn items(self) -> impl 'a + Send + Stream<Item = Result<<T as IntoIterator>::Item, E>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        <T as IntoIterator>::Item: Send,
+        <T as IntoIterator>::IntoIter: Send,
+    {
+        self.map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    fn items_streamed<I, IE>(self) -> impl 'a + Send + Stream<Item = Result<I, IE>>
+    where
+        Self: 'a,
+        T: Send + TryStream<Ok = I, Error = IE>,
+        IE: From<E>,
+    {
+        self.try_flatten()
+    }
+
+    fn try_collect_items<C>(self) -> impl 'a + Send + Future<Output = Result<C, E>>
+    where
+        Self: 'a,
+        E: 'a,
+        T: IntoIterator,
+        C: 'a + Send + Default + Extend<<T as IntoIterator>::Item>,
+    {
+        self.try_collect_items_into(C::default())
+    }
 
-/// A page turner suitable for use in multithreaded contexts
-///
-#[doc = include_str!("../doc/PageTurner")]
-pub trait PageTurner<R>: Sized + Send + Sync
-where
-    R: Send,
-{
-    type PageItems: Send;
-    type PageError: Send;
+    fn try_collect_items_capped<C>(
+        self,
+        max_items: usize,
+    ) -> impl 'a + Send + Future<Output = Result<C, E>>
+    where
+        Self: 'a,
+        T: IntoIterator,
+        C: 'a + Send + Default + Extend<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: Send,
+        <T as IntoIterator>::IntoIter: Send,
+    {
+        self.items().take(max_items).try_collect()
+    }
 
-    #[doc = include_str!("../doc/PageTurner__turn_page")]
-    fn turn_page(&self, request: R) -> impl Send + Future<Output = TurnedPageResult<Self, R>>;
+    fn try_collect_items_into<C>(
+        self,
+        collection: C,
+    ) -> impl 'a + Send + Future<Output = Result<C, E>>
+    where
+        Self: 'a,
+        E: 'a,
+        T: IntoIterator,
+        C: 'a + Send + Extend<<T as IntoIterator>::Item>,
+    {
+        self.try_fold(collection, |mut collection, items| {
+            collection.extend(items);
+            future::ready(Ok(collection))
+        })
+    }
 
-    #[doc = include_str!("../doc/PageTurner__pages")]
-    fn pages<'s>(&'s self, request: R) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    fn process_pages_concurrently<F, Fut, H>(
+        self,
+        concurrency: usize,
+        handler: F,
+    ) -> impl 'a + Send + Future<Output = ProcessSummary<E, H>>
     where
-        R: 's,
+        Self: 'a,
+        E: 'a,
+        F: 'a + Send + FnMut(T) -> Fut,
+        Fut: 'a + Send + Future<Output = Result<(), H>>,
+        H: 'a + Send,
     {
-        stream::try_unfold(PagesState::new(self, request), request_next_page)
+        let mut failed = false;
+        let mut handler = handler;
+
+        self.take_while(move |page| {
+            let proceed = !failed;
+            failed = page.is_err();
+            future::ready(proceed)
+        })
+        .map(move |page| {
+            let handled = page.map(&mut handler);
+            async move {
+                match handled {
+                    Ok(fut) => Ok(fut.await),
+                    Err(e) => Err(e),
+                }
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .fold(ProcessSummary::default(), |mut summary, outcome| {
+            summary.record(outcome);
+            future::ready(summary)
+        })
     }
 
-    #[doc = include_str!("../doc/PageTurner__into_pages")]
-    fn into_pages<'s>(self, request: R) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    fn only_new_items<K, F>(
+        self,
+        mark: HighWaterMark<K>,
+        order: KeyOrder,
+        key: F,
+    ) -> impl 'a + Send + Stream<Item = Result<<T as IntoIterator>::Item, E>>
     where
-        R: 's,
-        Self: 's,
+        Self: 'a,
+        T: IntoIterator,
+        K: 'a + Send + Ord + Clone,
+        F: 'a + Send + FnMut(&<T as IntoIterator>::Item) -> K,
+        <T as IntoIterator>::Item: Send,
+        <T as IntoIterator>::IntoIter: Send,
     {
-        stream::try_unfold(PagesState::new(self, request), request_next_page)
+        let mut key = key;
+        let mut is_new = mark.new_keys(order);
+        self.items()
+            .try_filter(move |item| future::ready(is_new(key(item))))
     }
 
-    #[doc = include_str!("../doc/PageTurner__pages_ahead")]
-    fn pages_ahead<'s>(
-        &'s self,
-        requests_ahead_count: usize,
-        limit: Limit,
-        request: R,
-    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    fn lazy_pages<F, U>(
+        self,
+        decode: F,
+    ) -> impl 'a + Send + Stream<Item = Result<impl 'a + Send + Stream<Item = U>, E>>
     where
-        R: 's + RequestAhead,
+        Self: 'a,
+        T: IntoIterator,
+        F: 'a + Send + Clone + FnMut(<T as IntoIterator>::Item) -> U,
+        <T as IntoIterator>::IntoIter: 'a + Send,
     {
-        stream::try_unfold(
-            Box::new(PagesAheadState::new(
-                self,
-                request,
-                requests_ahead_count,
-                limit,
-            )),
-            request_pages_ahead,
-        )
+        self.map_ok(move |items| stream::iter(items.into_iter().map(decode.clone())))
     }
 
-    #[doc = include_str!("../doc/PageTurner__into_pages_ahead")]
-    fn into_pages_ahead<'s>(
+    fn decode_pages<F, Fut, U>(
         self,
-        requests_ahead_count: usize,
-        limit: Limit,
-        request: R,
-    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+        concurrency: usize,
+        decode: F,
+    ) -> impl 'a + Send + Stream<Item = Result<U, E>>
     where
-        R: 's + RequestAhead,
-        Self: 's + Clone,
+        Self: 'a,
+        E: 'a,
+        F: 'a + Send + FnMut(T) -> Fut,
+        Fut: 'a + Send + Future<Output = Result<U, E>>,
+        U: 'a + Send,
     {
-        stream::try_unfold(
-            Box::new(PagesAheadState::new(
-                self,
-                request,
-                requests_ahead_count,
-                limit,
-            )),
-            request_pages_ahead,
-        )
+        let mut fetch_failed = false;
+        let mut decode_failed = false;
+        let mut decode = decode;
+
+        self.take_while(move |page| {
+            let proceed = !fetch_failed;
+            fetch_failed = page.is_err();
+            future::ready(proceed)
+        })
+        .map(move |page| {
+            let decoded = page.map(&mut decode);
+            async move {
+                match decoded {
+                    Ok(fut) => fut.await,
+                    Err(e) => Err(e),
+                }
+            }
+        })
+        // The next page is fetched only while fewer than `concurrency` pages are decoded
+        .buffered(concurrency.max(1))
+        .take_while(move |page| {
+            let proceed = !decode_failed;
+            decode_failed = page.is_err();
+            future::ready(proceed)
+        })
     }
 
-    #[doc = include_str!("../doc/PageTurner__pages_ahead_unordered")]
-    fn pages_ahead_unordered<'s>(
-        &'s self,
-        requests_ahead_count: usize,
-        limit: Limit,
-        request: R,
-    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    fn split_items(self) -> (SplitPages<Self, T, E>, SplitItems<Self, T, E>)
     where
-        R: 's + RequestAhead,
+        Self: Sized,
+        T: IntoIterator + Clone,
+        E: Clone,
     {
-        stream::try_unfold(
-            Box::new(PagesAheadUnorderedState::new(
-                self,
-                request,
-                requests_ahead_count,
-                limit,
-            )),
-            request_pages_ahead_unordered,
-        )
+        crate::split::split(self)
     }
 
-    #[doc = include_str!("../doc/PageTurner__into_pages_ahead_unordered")]
-    fn into_pages_ahead_unordered<'s>(
+    fn pages_infallible(self) -> impl 'a + Send + Stream<Item = T>
+    where
+        Self: 'a,
+        E: Into<Infallible>,
+    {
+        self.map(|page| unwrap_infallible(page))
+    }
+
+    fn pages_boxed_err(self) -> impl 'a + Send + Stream<Item = Result<T, BoxError>>
+    where
+        Self: 'a,
+        E: 'a + Into<BoxError>,
+    {
+        self.map_err(Into::into)
+    }
+
+    fn items_boxed_err(
         self,
-        requests_ahead_count: usize,
-        limit: Limit,
-        request: R,
-    ) -> impl PagesStream<'s, Self::PageItems, Self::PageError>
+    ) -> impl 'a + Send + Stream<Item = Result<<T as IntoIterator>::Item, BoxError>>
     where
-        Self: 's + Clone,
-        R: 's + RequestAhead,
+        Self: 'a,
+        T: IntoIterator,
+        E: 'a + Into<BoxError>,
+        <T as IntoIterator>::Item: Send,
+        <T as IntoIterator>::IntoIter: Send,
     {
-        stream::try_unfold(
-            Box::new(PagesAheadUnorderedState::new(
-                self,
-                request,
-                requests_ahead_count,
-                limit,
-            )),
-            request_pages_ahead_unordered,
+        self.items().map_err(Into::into)
+    }
+
+    fn blocking(self) -> BlockingPages<Self, ParkThread>
+    where
+        Self: Sized,
+    {
+        BlockingPages::new(self, ParkThread)
+    }
+
+    fn blocking_on<B>(self, executor: B) -> BlockingPages<Self, B>
+    where
+        Self: Sized,
+        B: BlockOn,
+    {
+        BlockingPages::new(self, executor)
+    }
+
+    fn until_cancelled<C>(self, token: C) -> Cancellable<Self, C>
+    where
+        Self: Sized,
+        C: Cancellation,
+    {
+        Cancellable::new(self, &token)
+    }
+
+    fn with_throughput(self, stats: ThroughputStats) -> Throughput<Self>
+    where
+        Self: Sized,
+        for<'t> &'t T: IntoIterator,
+    {
+        Throughput::new(self, stats)
+    }
+
+    fn with_stats(self, stats: StreamStats) -> WithStats<Self>
+    where
+        Self: Sized,
+        for<'t> &'t T: IntoIterator,
+    {
+        WithStats::new(self, stats)
+    }
+
+    fn with_summary<R>(self, report: RunReport<R>) -> Summarized<Self, R>
+    where
+        Self: Sized,
+        for<'t> &'t T: IntoIterator,
+    {
+        Summarized::new(self, report)
+    }
+
+    fn inspect_page<F>(self, f: F) -> impl 'a + Send + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        F: 'a + Send + FnMut(&T),
+    {
+        self.inspect_ok(f)
+    }
+
+    fn inspect_page_err<F>(self, f: F) -> impl 'a + Send + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        F: 'a + Send + FnMut(&E),
+    {
+        TryStreamExt::inspect_err(self, f)
+    }
+
+    fn limit_items(self, max_items: usize) -> impl 'a + Send + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        for<'t> &'t T: IntoIterator,
+    {
+        stream::unfold(
+            (Some(Box::pin(self)), 0),
+            move |(pages, yielded)| async move {
+                let mut pages = pages.filter(|_| yielded < max_items)?;
+                let page = pages.next().await?;
+                let yielded = match &page {
+                    Ok(items) => yielded + items.into_iter().count(),
+                    Err(_) => yielded,
+                };
+
+                // Requests sent ahead of the page that reaches the limit are already in flight,
+                // drop the stream right away to cancel them
+                let pages = (yielded < max_items).then_some(pages);
+                Some((page, (pages, yielded)))
+            },
         )
     }
-}
 
-impl<D, P, R> PageTurner<R> for D
-where
-    D: Send + Sync + std::ops::Deref<Target = P>,
-    P: PageTurner<R>,
-    R: Send,
-{
-    type PageItems = PageItems<P, R>;
-    type PageError = PageError<P, R>;
+    fn with_progress(self, total: Total) -> impl 'a + Send + Stream<Item = Result<(T, Progress), E>>
+    where
+        Self: 'a,
+        for<'t> &'t T: IntoIterator,
+    {
+        let mut done = 0;
 
-    async fn turn_page(&self, request: R) -> TurnedPageResult<Self, R> {
-        self.deref().turn_page(request).await
+        self.map_ok(move |items| {
+            let progress = update_progress(&mut done, total, &items);
+            (items, progress)
+        })
     }
-}
 
-#[doc = include_str!("../doc/PagesStream")]
-pub trait PagesStream<'a, T, E>: Send + Stream<Item = Result<T, E>>
-where
-    T: Send,
-    E: Send,
-{
-    #[doc = include_str!("../doc/PagesStream__items")]
-    fn items(self) -> impl 'a + Send + Stream<Item = Result<<T as IntoIterator>::Item, E>>
+    #[cfg(feature = "indicatif")]
+    fn with_pages_progress_bar(
+        self,
+        bar: indicatif::ProgressBar,
+    ) -> impl 'a + Send + Stream<Item = Result<T, E>>
     where
         Self: 'a,
-        T: IntoIterator,
-        <T as IntoIterator>::Item: Send,
-        <T as IntoIterator>::IntoIter: Send;
-}
+    {
+        self.inspect_ok(move |_| bar.inc(1))
+    }
 
-impl<'a, S, T, E> PagesStream<'a, T, E> for S
-where
-    T: Send,
-    E: Send,
-    S: Send + Stream<Item = Result<T, E>>,
-{
-    fn items(self) -> impl 'a + Send + Stream<Item = Result<<T as IntoIterator>::Item, E>>
+    #[cfg(feature = "indicatif")]
+    fn with_items_progress_bar(
+        self,
+        bar: indicatif::ProgressBar,
+    ) -> impl 'a + Send + Stream<Item = Result<T, E>>
+    where
+        Self: 'a,
+        for<'t> &'t T: IntoIterator,
+    {
+        self.inspect_ok(move |items| bar.inc(items.into_iter().count() as u64))
+    }
+
+    fn items_infallible(self) -> impl 'a + Send + Stream<Item = <T as IntoIterator>::Item>
     where
         Self: 'a,
+        E: Into<Infallible>,
         T: IntoIterator,
         <T as IntoIterator>::Item: Send,
         <T as IntoIterator>::IntoIter: Send,
     {
-        self.map_ok(|items| stream::iter(items.into_iter().map(Ok)))
-            .try_flatten()
+        self.map(|page| stream::iter(unwrap_infallible(page)))
+            .flatten()
+    }
+
+    fn collect_items<C>(self) -> impl 'a + Send + Future<Output = C>
+    where
+        Self: 'a,
+        E: 'a + Into<Infallible>,
+        T: IntoIterator,
+        C: 'a + Send + Default + Extend<<T as IntoIterator>::Item>,
+    {
+        let items = self.try_collect_items_into(C::default());
+        async move { unwrap_infallible(items.await) }
+    }
+}
+
+/// Creates a page turner from an async closure, handy for scripts and one-off paginations that
+/// don't deserve a dedicated client struct.
+///
+/// ```
+/// use page_turner::mt::{prelude::*, turner_fn};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use futures::TryStreamExt;
+///
+/// let page_turner = turner_fn(|page: u32| async move {
+///     Ok::<_, ()>(TurnedPage::new(vec![page], (page < 3).then_some(page + 1)))
+/// });
+///
+/// let pages: Vec<_> = page_turner.pages(1).items().try_collect().await.unwrap();
+/// assert_eq!(pages, [1, 2, 3]);
+/// # }
+/// ```
+pub fn turner_fn<F>(f: F) -> TurnerFn<F> {
+    TurnerFn(f)
+}
+
+/// A page turner returned by [`turner_fn`]
+#[derive(Debug, Clone, Copy)]
+pub struct TurnerFn<F>(F);
+
+impl<F> TurnerFn<F> {
+    pub fn into_inner(self) -> F {
+        self.0
+    }
+}
+
+impl<F, Fut, R, I, E> PageTurner<R> for TurnerFn<F>
+where
+    F: Send + Sync + Fn(R) -> Fut,
+    Fut: Send + Future<Output = Result<TurnedPage<I, R>, E>>,
+    R: Send,
+    I: Send,
+    E: Send,
+{
+    type PageItems = I;
+    type PageError = E;
+
+    fn turn_page(&self, request: R) -> impl Send + Future<Output = TurnedPageResult<Self, R>> {
+        (self.0)(request)
     }
 }
 
+/// Queries `pages` of all page turner and request pairs concurrently and yields them as they
+/// arrive, see [`select_pages`](crate::merge::select_pages).
+pub fn merge_pages<'p, P, R>(
+    turners_and_requests: impl IntoIterator<Item = (&'p P, R)>,
+) -> SelectPages<Pages<'p, &'p P, R>>
+where
+    P: 'p + PageTurner<R>,
+    R: 'p + Send,
+{
+    select_pages(
+        turners_and_requests
+            .into_iter()
+            .map(|(page_turner, request)| page_turner.pages(request)),
+    )
+}
+
+bidirectional_pages_def!(R: Send);
+pages_stream_def!(R: Send);
+pages_builder_def!(R: Send);
 pages_ahead_state_def!(R: Send);
+pages_ahead_auto_state_def!(R: Send);
 pages_ahead_unordered_state_def!(R: Send);
+pages_ahead_unordered_stream_def!(R: Send, T: Send, E: Send, I: Send);
 
-request_next_page_decl!(R: Send);
 request_pages_ahead_decl!(R: Send);
+request_pages_ahead_auto_decl!(R: Send);
 request_pages_ahead_unordered_decl!(R: Send);
 
+tail_state_def!(R: Send);
+request_tail_page_decl!(R: Send, S: Send);
+
 #[cfg(feature = "dynamic")]
 #[cfg_attr(docsrs, doc(cfg(feature = "dynamic")))]
 pub mod dynamic {
@@ -208,7 +1414,11 @@ pub mod dynamic {
     use std::{future::Future, pin::Pin};
 
     pub use super::PagesStream;
-    pub use crate::{Limit, RequestAhead, TurnedPage};
+    pub use crate::inject::RequestInjector;
+    pub use crate::{
+        AdaptiveAhead, BoxError, Limit, ProcessSummary, Progress, RequestAhead, StreamId, Total,
+        TurnedPage,
+    };
     #[doc = include_str!("../doc/prelude")]
     pub mod prelude {
         pub use super::{
@@ -229,6 +1439,9 @@ pub mod prelude {
 
     type NumberedRequestFuture<'a, P, R> =
         Pin<Box<dyn 'a + Send + Future<Output = (usize, TurnedPageResult<P, R>)>>>;
+    type PagesFuture<'a, P, R> =
+        Pin<Box<dyn 'a + Send + Future<Output = (P, TurnedPageResult<P, R>)>>>;
+    type PagesBoxStream<'a, T> = BoxStream<'a, T>;
 
     /// A page turner which yields dynamic objects. All methods are object safe and can be used
     /// with dynamic dispatch. Requires `#[async_trait]` to be implemented
@@ -247,9 +1460,7 @@ pub trait PageTurner<R>: Send + Sync
 
         #[doc = include_str!("../doc/PageTurner__pages")]
         fn pages(&self, request: R) -> BoxedPagesStream<'_, Self::PageItems, Self::PageError> {
-            BoxedPagesStream(
-                stream::try_unfold(PagesState::new(self, request), request_next_page).boxed(),
-            )
+            BoxedPagesStream(Pages::new(self, request).boxed())
         }
 
         #[doc = include_str!("../doc/PageTurner__into_pages")]
@@ -260,9 +1471,7 @@ fn into_pages<'s>(
         where
             Self: 's + Sized,
         {
-            BoxedPagesStream(
-                stream::try_unfold(PagesState::new(self, request), request_next_page).boxed(),
-            )
+            BoxedPagesStream(Pages::new(self, request).boxed())
         }
 
         #[doc = include_str!("../doc/PageTurner__pages_ahead")]
@@ -334,6 +1543,7 @@ fn pages_ahead_unordered<'s>(
                     )),
                     request_pages_ahead_unordered,
                 )
+                .map_ok(|(_, items)| items)
                 .boxed(),
             )
         }
@@ -359,6 +1569,7 @@ fn into_pages_ahead_unordered<'s>(
                     )),
                     request_pages_ahead_unordered,
                 )
+                .map_ok(|(_, items)| items)
                 .boxed(),
             )
         }
@@ -398,13 +1609,38 @@ fn poll_next(
         }
     }
 
+    crate::internal::async_iterator_impl!(['a, T, E] BoxedPagesStream<'a, T, E>);
+
+    pages_stream_def!(R: 'static + Send);
+    pages_builder_def!(R: 'static + Send);
     pages_ahead_state_def!(R: 'static + Send);
+    pages_ahead_auto_state_def!(R: 'static + Send);
     pages_ahead_unordered_state_def!(R: 'static + Send);
 
-    request_next_page_decl!(R: 'static + Send);
     request_pages_ahead_decl!(R: 'static + Send);
+    request_pages_ahead_auto_decl!(R: 'static + Send);
     request_pages_ahead_unordered_decl!(R: 'static + Send);
 }
 
+pub mod testing {
+    //! Helpers for testing page turner implementations
+
+    use super::{Limit, PageError, PageItems, PageTurner, RequestAhead};
+    use crate::internal::*;
+    use futures::{StreamExt, TryStreamExt};
+
+    assert_pagination_equivalent_decl!(R: Send);
+}
+
+pub mod paginated;
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub mod actor;
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub mod locked;
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,199 @@
+//! An alternative to [`PageTurner`] for clients that handle a single kind of paginated requests.
+//!
+//! With [`Paginated`] the request is an associated type rather than a generic parameter, therefore,
+//! it never needs to be spelled out for type inference, and `turn_page` returns a boxed future
+//! which makes the trait object safe without `async_trait`. So it's possible to erase a client
+//! into a `Box<dyn Paginated<Request = R, PageItems = I, PageError = E>>` and still query pages
+//! from it.
+//!
+//! Use [`AsPageTurner`] to pass a [`Paginated`] where a [`PageTurner`] is expected and
+//! [`ForRequest`] to turn a [`PageTurner`] into a [`Paginated`].
+//!
+//! ```
+//! use page_turner::paginated::{Paginated, PaginatedFuture};
+//! use page_turner::prelude::*;
+//!
+//! struct Numbers;
+//!
+//! impl Paginated for Numbers {
+//!     type Request = u32;
+//!     type PageItems = Vec<u32>;
+//!     type PageError = ();
+//!
+//!     fn turn_page(&self, page: u32) -> PaginatedFuture<'_, Self> {
+//!         Box::pin(async move {
+//!             let next = (page < 3).then_some(page + 1);
+//!             Ok(TurnedPage::new(vec![page], next))
+//!         })
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let client: Box<dyn Paginated<Request = u32, PageItems = Vec<u32>, PageError = ()>> =
+//!     Box::new(Numbers);
+//!
+//! let numbers: Vec<_> = client.pages(1).items().try_collect().await.unwrap();
+//! assert_eq!(numbers, [1, 2, 3]);
+//! # }
+//! ```
+
+use super::{PageTurner, Pages, TurnedPageResult};
+use crate::TurnedPage;
+use std::{future::Future, marker::PhantomData, pin::Pin, sync::Arc};
+
+/// A [`Paginated::turn_page`] result
+pub type PaginatedResult<P> = Result<
+    TurnedPage<<P as Paginated>::PageItems, <P as Paginated>::Request>,
+    <P as Paginated>::PageError,
+>;
+
+/// A future returned by [`Paginated::turn_page`]
+pub type PaginatedFuture<'a, P> = Pin<Box<dyn 'a + Send + Future<Output = PaginatedResult<P>>>>;
+
+/// An object safe page turner for a single request type. See the [module docs](self).
+pub trait Paginated: Send + Sync {
+    type Request: Send;
+    type PageItems: Send;
+    type PageError: Send;
+
+    #[doc = include_str!("../doc/PageTurner__turn_page")]
+    fn turn_page(&self, request: Self::Request) -> PaginatedFuture<'_, Self>;
+
+    /// Same as [`PageTurner::pages`]
+    fn pages(&self, request: Self::Request) -> Pages<'_, AsPageTurner<&Self>, Self::Request>
+    where
+        Self: Sized,
+    {
+        AsPageTurner(self).into_pages(request)
+    }
+
+    /// Same as [`PageTurner::into_pages`]
+    fn into_pages<'s>(self, request: Self::Request) -> Pages<'s, AsPageTurner<Self>, Self::Request>
+    where
+        Self: 's + Sized,
+    {
+        AsPageTurner(self).into_pages(request)
+    }
+}
+
+macro_rules! paginated_deref_impl {
+    ($($ptr:tt)*) => {
+        impl<P> Paginated for $($ptr)*
+        where
+            P: ?Sized + Paginated,
+        {
+            type Request = P::Request;
+            type PageItems = P::PageItems;
+            type PageError = P::PageError;
+
+            fn turn_page(&self, request: Self::Request) -> PaginatedFuture<'_, Self> {
+                P::turn_page(self, request)
+            }
+        }
+    };
+}
+
+paginated_deref_impl!(&P);
+paginated_deref_impl!(Box<P>);
+paginated_deref_impl!(Arc<P>);
+
+/// Implements [`PageTurner`] for a [`Paginated`] client
+#[derive(Debug, Clone, Copy)]
+pub struct AsPageTurner<P>(pub P);
+
+impl<P> PageTurner<P::Request> for AsPageTurner<P>
+where
+    P: Paginated,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: P::Request) -> TurnedPageResult<Self, P::Request> {
+        self.0.turn_page(request).await
+    }
+}
+
+/// Implements [`Paginated`] for a [`PageTurner`] client fixing its request type to `R`
+#[derive(Debug)]
+pub struct ForRequest<P, R> {
+    page_turner: P,
+    _request: PhantomData<fn(R) -> R>,
+}
+
+impl<P, R> ForRequest<P, R> {
+    pub fn new(page_turner: P) -> Self {
+        Self {
+            page_turner,
+            _request: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.page_turner
+    }
+}
+
+impl<P, R> Paginated for ForRequest<P, R>
+where
+    P: PageTurner<R>,
+    R: Send,
+{
+    type Request = R;
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    fn turn_page(&self, request: R) -> PaginatedFuture<'_, Self> {
+        Box::pin(self.page_turner.turn_page(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+
+    struct Letters(Vec<char>);
+
+    #[derive(Clone, Copy)]
+    struct Ix(usize);
+
+    impl RequestAhead for Ix {
+        fn next_request(&self) -> Self {
+            Ix(self.0 + 1)
+        }
+    }
+
+    impl PageTurner<Ix> for Letters {
+        type PageItems = Option<char>;
+        type PageError = usize;
+
+        async fn turn_page(&self, Ix(ix): Ix) -> TurnedPageResult<Self, Ix> {
+            let letter = *self.0.get(ix).ok_or(ix)?;
+            let next = (ix + 1 < self.0.len()).then_some(Ix(ix + 1));
+            Ok(TurnedPage::new(Some(letter), next))
+        }
+    }
+
+    type DynLetters = dyn Paginated<Request = Ix, PageItems = Option<char>, PageError = usize>;
+
+    #[tokio::test]
+    async fn bridges() {
+        let letters: Arc<DynLetters> = Arc::new(ForRequest::new(Letters(vec!['a', 'b', 'c', 'd'])));
+
+        let output: String = letters.pages(Ix(0)).items().try_collect().await.unwrap();
+        assert_eq!(output, "abcd");
+
+        let output: String = AsPageTurner(letters)
+            .pages_ahead(3, Limit::None, Ix(1))
+            .items()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(output, "bcd");
+    }
+}
@@ -1,6 +1,9 @@
-use crate::mt::{prelude::*, PageError, PageItems};
+use crate::mt::{
+    prelude::*, testing, turner_fn, BoxPagesStream, ItemPageTurner, PageError, PageItems,
+    PagesBuilder, Progress, Total,
+};
 use crate::test_utils::*;
-use futures::TryStreamExt;
+use futures::{StreamExt, TryStreamExt};
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn pages() {
@@ -8,10 +11,218 @@ async fn pages() {
     generic_pages_usage(NumbersClient::new(48, 7), GetNumbersQuery::default()).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_requests() {
+    pages_requests_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_split() {
+    pages_split_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_infallible() {
+    pages_infallible_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_try_collect_items() {
+    pages_try_collect_items_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_process_concurrently() {
+    pages_process_concurrently_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_boxed_err() {
+    pages_boxed_err_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_only_new_items() {
+    pages_only_new_items_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_tail_durable() {
+    pages_tail_durable_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_lazy() {
+    pages_lazy_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn arc_pages() {
+    arc_pages_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn boxed_pages() {
+    boxed_pages_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_inspect() {
+    pages_inspect_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_decode() {
+    pages_decode_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_with_state() {
+    pages_with_state_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_from_requests() {
+    pages_from_requests_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_with_retry() {
+    pages_with_retry_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_limit_items() {
+    pages_limit_items_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_resumable() {
+    pages_resumable_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_ahead_batched() {
+    pages_ahead_batched_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_ahead_throttled() {
+    pages_ahead_throttled_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_with_timeout() {
+    pages_with_timeout_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_items_streamed() {
+    pages_items_streamed_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn into_pages_ahead_arc() {
+    into_pages_ahead_arc_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_with_progress_handle() {
+    pages_with_progress_handle_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_from_turner_fn() {
+    turner_fn_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_bidirectional() {
+    pages_bidirectional_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn page_turner_combinators() {
+    page_turner_combinators_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_chain() {
+    pages_chain_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_map_ahead() {
+    pages_map_ahead_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_while() {
+    pages_while_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_with_progress() {
+    pages_with_progress_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_ahead_unordered_stats() {
+    blogs_client_overquery_stats_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_ahead_unordered_indexed() {
+    blogs_client_indexed_unordered_test!().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pagination_equivalent() {
+    blogs_client_pagination_equivalent_test!().await;
+}
+
+#[cfg(feature = "nightly-async-iter")]
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_async_iterator() {
+    use std::async_iter::AsyncIterator;
+
+    async fn collect<I: AsyncIterator + Unpin>(mut iter: I) -> Vec<I::Item> {
+        let mut items = Vec::new();
+        while let Some(item) =
+            std::future::poll_fn(|cx| AsyncIterator::poll_next(std::pin::Pin::new(&mut iter), cx))
+                .await
+        {
+            items.push(item);
+        }
+
+        items
+    }
+
+    let client = NumbersClient::new(30, 5);
+    let pages = collect(client.pages(GetNumbersQuery::default())).await;
+    assert_eq!(pages.len(), 6);
+
+    let blog = BlogClient::new(10);
+    let pages =
+        collect(blog.pages_ahead_unordered(3, Limit::Pages(10), GetContentRequest { page: 0 }))
+            .await;
+    let mut records: Vec<_> = pages.into_iter().flat_map(Result::unwrap).collect();
+    records.sort_by_key(|record| record.0);
+    assert_eq!(records, (0..10).map(BlogRecord).collect::<Vec<_>>());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pages_builder() {
+    blogs_client_pages_builder_test!().await;
+    blogs_client_request_injector_test!().await;
+    pages_builder_retry_timeout_test!().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn pages_ahead() {
     pages_ahead_base_test!().await;
     generic_pages_ahead_usage(BlogClient::new(48), GetContentRequest { page: 0 }).await;
+    generic_item_page_turner_usage(BlogClient::new(48), GetContentRequest { page: 0 }).await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -20,6 +231,130 @@ async fn pages_ahead_unordered() {
     generic_pages_ahead_unordered_usage(BlogClient::new(48), GetContentRequest { page: 0 }).await;
 }
 
+#[tokio::test]
+async fn pages_builder_ahead_auto() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use std::time::Duration;
+
+    #[derive(Clone, Default)]
+    struct InFlight {
+        current: Arc<AtomicUsize>,
+        max: Arc<AtomicUsize>,
+    }
+
+    impl PageTurner<GetContentRequest> for InFlight {
+        type PageItems = Vec<usize>;
+        type PageError = ();
+
+        async fn turn_page(
+            &self,
+            request: GetContentRequest,
+        ) -> TurnedPageResult<Self, GetContentRequest> {
+            let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.current.fetch_sub(1, Ordering::SeqCst);
+
+            let page = request.page;
+            Ok(TurnedPage::new(
+                vec![page],
+                (page < 39).then(|| request.next_request()),
+            ))
+        }
+    }
+
+    let fast = InFlight::default();
+    let pages: Vec<_> = PagesBuilder::new(fast.clone())
+        .ahead_auto(8)
+        .build(GetContentRequest { page: 0 })
+        .items()
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(pages, (0..40).collect::<Vec<_>>());
+    assert_eq!(fast.max.load(Ordering::SeqCst), 8);
+
+    let slow = InFlight::default();
+    let mut stream = std::pin::pin!(PagesBuilder::new(slow.clone())
+        .ahead_auto(8)
+        .build(GetContentRequest { page: 0 }));
+
+    let mut pages = 0;
+    while stream.try_next().await.unwrap().is_some() {
+        pages += 1;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        if pages == 10 {
+            break;
+        }
+    }
+
+    assert_eq!(pages, 10);
+    assert!(slow.max.load(Ordering::SeqCst) <= 2);
+}
+
+#[tokio::test]
+async fn pages_builder_ahead_adaptive() {
+    use crate::AdaptiveAhead;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use std::time::Duration;
+
+    /// Slows down when more than 4 requests are in flight
+    #[derive(Clone, Default)]
+    struct Congested {
+        current: Arc<AtomicUsize>,
+        max: Arc<AtomicUsize>,
+    }
+
+    impl PageTurner<GetContentRequest> for Congested {
+        type PageItems = Vec<usize>;
+        type PageError = ();
+
+        async fn turn_page(
+            &self,
+            request: GetContentRequest,
+        ) -> TurnedPageResult<Self, GetContentRequest> {
+            let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max.fetch_max(current, Ordering::SeqCst);
+
+            let latency = if current > 4 { 60 } else { 5 };
+            tokio::time::sleep(Duration::from_millis(latency)).await;
+            self.current.fetch_sub(1, Ordering::SeqCst);
+
+            let page = request.page;
+            Ok(TurnedPage::new(
+                vec![page],
+                (page < 59).then(|| request.next_request()),
+            ))
+        }
+    }
+
+    let congested = Congested::default();
+    let pages: Vec<_> = PagesBuilder::new(congested.clone())
+        .ahead_adaptive(AdaptiveAhead::new(16).initial(2))
+        .build(GetContentRequest { page: 0 })
+        .items()
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(pages, (0..60).collect::<Vec<_>>());
+
+    let max = congested.max.load(Ordering::SeqCst);
+    assert!(max > 2, "The window grows while responses are fast");
+    assert!(
+        max < 8,
+        "The window shrinks once responses slow down, got {max}"
+    );
+}
+
 page_turner_impls!();
 
 async fn generic_pages_usage<P, R>(p: P, req: R)
@@ -31,7 +366,7 @@ async fn generic_pages_usage<P, R>(p: P, req: R)
     <PageItems<P, R> as IntoIterator>::Item: Send,
     PageError<P, R>: std::fmt::Debug,
 {
-    is_send(p.turn_page(req.clone()));
+    let _turn_page = is_send(p.turn_page(req.clone()));
 
     let pages_stream = is_send(p.pages(req.clone()));
     generic_pages_stream_usage(pages_stream).await;
@@ -46,13 +381,23 @@ async fn generic_pages_ahead_usage<P, R>(p: P, req: R)
     R: RequestAhead + Clone + Send,
     PageError<P, R>: std::fmt::Debug,
 {
-    is_send(p.turn_page(req.clone()));
+    let _turn_page = is_send(p.turn_page(req.clone()));
 
     let pages_stream = is_send(p.pages_ahead(4, Limit::None, req.clone()));
     generic_pages_stream_usage(pages_stream).await;
 }
 
-async fn generic_pages_ahead_unordered_usage<'p, P, R>(p: P, req: R)
+async fn generic_item_page_turner_usage<P, R>(p: P, req: R)
+where
+    P: ItemPageTurner<R>,
+    R: RequestAhead + Clone + Send,
+    PageError<P, R>: std::fmt::Debug,
+{
+    generic_pages_stream_usage(is_send(p.pages(req.clone()))).await;
+    generic_pages_stream_usage(is_send(p.pages_ahead(4, Limit::None, req))).await;
+}
+
+async fn generic_pages_ahead_unordered_usage<P, R>(p: P, req: R)
 where
     P: PageTurner<R>,
     R: RequestAhead + Clone + Send,
@@ -61,7 +406,7 @@ async fn generic_pages_ahead_unordered_usage<'p, P, R>(p: P, req: R)
     <PageItems<P, R> as IntoIterator>::Item: Send,
     PageError<P, R>: std::fmt::Debug,
 {
-    is_send(p.turn_page(req.clone()));
+    let _turn_page = is_send(p.turn_page(req.clone()));
 
     let pages_stream = is_send(p.pages_ahead_unordered(4, Limit::None, req));
     generic_pages_stream_usage(pages_stream).await;
@@ -84,7 +429,7 @@ fn is_send<T: Send>(t: T) -> T {
 #[cfg(feature = "dynamic")]
 mod dynamic {
     use super::is_send;
-    use crate::dynamic::prelude::*;
+    use crate::dynamic::{prelude::*, PagesBuilder};
     use crate::test_utils::*;
     use async_trait::async_trait;
     use futures::TryStreamExt;
@@ -96,6 +441,12 @@ async fn pages() {
         dyn_pages_usage(Arc::new(BlogClient::new(42))).await;
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn pages_builder() {
+        blogs_client_pages_builder_test!().await;
+        blogs_client_request_injector_test!().await;
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn pages_ahead() {
         pages_ahead_base_test!().await;
@@ -113,7 +464,7 @@ async fn pages_ahead_unordered() {
     async fn dyn_pages_usage(
         p: Arc<dyn PageTurner<GetContentRequest, PageItems = Vec<BlogRecord>, PageError = String>>,
     ) {
-        is_send(p.turn_page(GetContentRequest { page: 0 }));
+        let _turn_page = is_send(p.turn_page(GetContentRequest { page: 0 }));
 
         let pages_stream = is_send(p.pages(GetContentRequest { page: 0 }));
         generic_pages_stream_usage(pages_stream).await;
@@ -122,7 +473,7 @@ async fn dyn_pages_usage(
     async fn dyn_pages_ahead_usage(
         p: Arc<dyn PageTurner<GetContentRequest, PageItems = Vec<BlogRecord>, PageError = String>>,
     ) {
-        is_send(p.turn_page(GetContentRequest { page: 0 }));
+        let _turn_page = is_send(p.turn_page(GetContentRequest { page: 0 }));
 
         let pages_stream = is_send(p.pages_ahead(3, Limit::None, GetContentRequest { page: 0 }));
         generic_pages_stream_usage(pages_stream).await;
@@ -131,7 +482,7 @@ async fn dyn_pages_ahead_usage(
     async fn dyn_pages_ahead_unordered_usage(
         p: Arc<dyn PageTurner<GetContentRequest, PageItems = Vec<BlogRecord>, PageError = String>>,
     ) {
-        is_send(p.turn_page(GetContentRequest { page: 0 }));
+        let _turn_page = is_send(p.turn_page(GetContentRequest { page: 0 }));
 
         let pages_stream =
             is_send(p.pages_ahead_unordered(2, Limit::None, GetContentRequest { page: 0 }));
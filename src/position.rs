@@ -0,0 +1,319 @@
+//! Typed page positions with checked arithmetic.
+//!
+//! Use [`PageNumber`] and [`Offset`] as fields of requests instead of plain integers to get
+//! [`RequestAhead`] for free and to avoid off-by-one and overflow bugs in `next_request`
+//! implementations. [`OffsetPaginator`] adds offset/limit fields to an arbitrary request and
+//! [`TurnedPage::from_total`] finishes offset pagination by the total count of items reported by
+//! an API. [`Cursor`] marks opaque tokens returned by APIs with cursor pagination.
+//!
+//! ```
+//! use page_turner::position::{Offset, OffsetPaginator, PageNumber};
+//! use page_turner::{RequestAhead, TurnedPage};
+//!
+//! let page = PageNumber::FIRST;
+//! assert_eq!(page.nth_request(3), PageNumber::new(5));
+//! assert_eq!(PageNumber::new(usize::MAX).checked_next(), None);
+//!
+//! let offset = Offset::new(0, 50);
+//! assert_eq!(offset.next_request().offset, 50);
+//! assert_eq!(offset.nth_request(1).page_index(), 2);
+//!
+//! let page = TurnedPage::from_total(vec!["a", "b"], 0, 2, 3);
+//! assert_eq!(page.next_request, Some(Offset::new(2, 2)));
+//!
+//! let search = OffsetPaginator::new("rust", 25).starting_at(50);
+//! assert_eq!(search.next_request().offset, 75);
+//! assert_eq!(search.turned_page(vec!["crate"; 25], 80).next_request.unwrap().offset, 75);
+//! assert_eq!(search.turned_page(vec!["crate"; 25], 75).next_request, None);
+//! ```
+
+use crate::{RequestAhead, TurnedPage};
+
+/// A page number counted from 1 as most APIs with numbered pages do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageNumber(usize);
+
+impl PageNumber {
+    pub const FIRST: Self = Self(1);
+
+    /// Page numbers start at 1, `0` is treated as 1
+    pub fn new(page: usize) -> Self {
+        Self(page.max(1))
+    }
+
+    pub fn get(&self) -> usize {
+        self.0
+    }
+
+    /// The zero-based index of the page
+    pub fn index(&self) -> usize {
+        self.0 - 1
+    }
+
+    /// Returns the number of the next page or `None` on overflow
+    pub fn checked_next(&self) -> Option<Self> {
+        self.checked_advance_by(1)
+    }
+
+    /// Returns the number of the page `n` pages later or `None` on overflow
+    pub fn checked_advance_by(&self, n: usize) -> Option<Self> {
+        self.0.checked_add(n).map(Self)
+    }
+}
+
+impl Default for PageNumber {
+    fn default() -> Self {
+        Self::FIRST
+    }
+}
+
+impl std::fmt::Display for PageNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<PageNumber> for usize {
+    fn from(page: PageNumber) -> Self {
+        page.0
+    }
+}
+
+/// Panics on overflow which can't be reached by a page turner in practice
+impl RequestAhead for PageNumber {
+    fn next_request(&self) -> Self {
+        self.checked_next().expect("PageNumber overflow")
+    }
+
+    fn nth_request(&self, n: usize) -> Self {
+        n.checked_add(1)
+            .and_then(|n| self.checked_advance_by(n))
+            .expect("PageNumber overflow")
+    }
+}
+
+/// An offset of the first item of a page along with the page size for APIs with offset/limit
+/// pagination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Offset {
+    pub offset: usize,
+    pub page_size: usize,
+}
+
+impl Offset {
+    pub fn new(offset: usize, page_size: usize) -> Self {
+        Self { offset, page_size }
+    }
+
+    /// The zero-based index of the page if `offset` is aligned to `page_size`
+    pub fn page_index(&self) -> usize {
+        self.offset.checked_div(self.page_size).unwrap_or(0)
+    }
+
+    /// Returns the offset of the next page or `None` on overflow
+    pub fn checked_next(&self) -> Option<Self> {
+        self.checked_advance_by(1)
+    }
+
+    /// Returns the offset of the page `n` pages later or `None` on overflow
+    pub fn checked_advance_by(&self, n: usize) -> Option<Self> {
+        self.page_size
+            .checked_mul(n)
+            .and_then(|items| self.offset.checked_add(items))
+            .map(|offset| Self::new(offset, self.page_size))
+    }
+
+    /// Whether a page with `items_count` items is the last one, i.e. it's shorter than the page
+    /// size
+    pub fn is_last_page(&self, items_count: usize) -> bool {
+        items_count < self.page_size
+    }
+}
+
+/// Panics on overflow which can't be reached by a page turner in practice
+impl RequestAhead for Offset {
+    fn next_request(&self) -> Self {
+        self.checked_next().expect("Offset overflow")
+    }
+
+    fn nth_request(&self, n: usize) -> Self {
+        n.checked_add(1)
+            .and_then(|n| self.checked_advance_by(n))
+            .expect("Offset overflow")
+    }
+}
+
+impl<I> TurnedPage<I, Offset> {
+    /// Makes a page of offset/limit pagination that has the next page while items past it remain
+    /// according to the `total` count, i.e. a `total_count` field of a response
+    pub fn from_total(items: I, offset: usize, limit: usize, total: usize) -> Self {
+        Self::new(
+            items,
+            has_more(offset, limit, total).then(|| Offset::new(offset + limit, limit)),
+        )
+    }
+}
+
+/// A request of offset/limit pagination that wraps the rest of the request, i.e. filters of a
+/// search, and implements [`RequestAhead`] by advancing the offset
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OffsetPaginator<R> {
+    pub request: R,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl<R> OffsetPaginator<R> {
+    /// Requests pages of `limit` items starting from the offset `0`
+    pub fn new(request: R, limit: usize) -> Self {
+        Self {
+            request,
+            offset: 0,
+            limit,
+        }
+    }
+
+    pub fn starting_at(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn position(&self) -> Offset {
+        Offset::new(self.offset, self.limit)
+    }
+
+    /// Makes a page with the next request while items past this page remain according to the
+    /// `total` count. See [`TurnedPage::from_total`].
+    pub fn turned_page<I>(&self, items: I, total: usize) -> TurnedPage<I, Self>
+    where
+        R: Clone,
+    {
+        TurnedPage::new(
+            items,
+            has_more(self.offset, self.limit, total).then(|| self.next_request()),
+        )
+    }
+}
+
+/// Panics on overflow which can't be reached by a page turner in practice
+impl<R: Clone> RequestAhead for OffsetPaginator<R> {
+    fn next_request(&self) -> Self {
+        self.nth_request(0)
+    }
+
+    fn nth_request(&self, n: usize) -> Self {
+        Self {
+            request: self.request.clone(),
+            offset: self.position().nth_request(n).offset,
+            limit: self.limit,
+        }
+    }
+}
+
+/// Whether items remain past the page of `limit` items at the `offset`. A zero limit never
+/// advances, so it has no next page.
+fn has_more(offset: usize, limit: usize, total: usize) -> bool {
+    limit > 0 && offset.saturating_add(limit) < total
+}
+
+/// An opaque token of cursor pagination. Unlike page numbers and offsets cursors can't be
+/// computed in advance, so they don't implement [`RequestAhead`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cursor<T>(pub T);
+
+impl<T> Cursor<T> {
+    pub fn new(token: T) -> Self {
+        Self(token)
+    }
+
+    pub fn get(&self) -> &T {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Converts an optional token of the next page from a response, treating empty tokens that
+    /// some APIs return on the last page as `None`
+    pub fn next_from(token: Option<T>) -> Option<Self>
+    where
+        T: AsRef<str>,
+    {
+        token.filter(|token| !token.as_ref().is_empty()).map(Self)
+    }
+}
+
+impl<T> From<T> for Cursor<T> {
+    fn from(token: T) -> Self {
+        Self(token)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Cursor<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_number() {
+        assert_eq!(PageNumber::new(0), PageNumber::FIRST);
+        assert_eq!(PageNumber::default().index(), 0);
+        assert_eq!(PageNumber::FIRST.next_request(), PageNumber::new(2));
+        assert_eq!(PageNumber::FIRST.nth_request(0), PageNumber::new(2));
+        assert_eq!(
+            PageNumber::new(3).checked_advance_by(2),
+            Some(PageNumber::new(5))
+        );
+        assert_eq!(PageNumber::new(usize::MAX).checked_next(), None);
+        assert_eq!(PageNumber::new(7).to_string(), "7");
+    }
+
+    #[test]
+    fn offset() {
+        let offset = Offset::new(20, 10);
+        assert_eq!(offset.page_index(), 2);
+        assert_eq!(offset.next_request(), Offset::new(30, 10));
+        assert_eq!(offset.nth_request(2), Offset::new(50, 10));
+        assert_eq!(Offset::new(usize::MAX - 5, 10).checked_next(), None);
+        assert_eq!(Offset::new(5, 0).page_index(), 0);
+        assert!(offset.is_last_page(9));
+        assert!(!offset.is_last_page(10));
+    }
+
+    #[test]
+    fn offset_paginator() {
+        let page = TurnedPage::from_total((), 20, 10, 31);
+        assert_eq!(page.next_request, Some(Offset::new(30, 10)));
+        assert_eq!(TurnedPage::from_total((), 30, 10, 31).next_request, None);
+        assert_eq!(TurnedPage::from_total((), 0, 10, 10).next_request, None);
+        assert_eq!(TurnedPage::from_total((), 0, 0, 10).next_request, None);
+
+        let request = OffsetPaginator::new("query", 10).starting_at(20);
+        assert_eq!(request.position(), Offset::new(20, 10));
+        assert_eq!(request.nth_request(1).offset, 40);
+        assert_eq!(request.nth_request(1).request, "query");
+        assert_eq!(
+            request.turned_page((), 31).next_request,
+            Some(request.next_request())
+        );
+        assert_eq!(request.turned_page((), 30).next_request, None);
+    }
+
+    #[test]
+    fn cursor() {
+        assert_eq!(Cursor::next_from(Some("abc")), Some(Cursor("abc")));
+        assert_eq!(Cursor::next_from(Some("")), None);
+        assert_eq!(Cursor::<&str>::next_from(None), None);
+        assert_eq!(Cursor::from(String::from("x")).into_inner(), "x");
+    }
+}
@@ -0,0 +1,212 @@
+//! Progress of a pagination observable from another task, i.e. to render a progress bar of a
+//! long export. See `PageTurner::pages_with_progress`.
+//!
+//! A [`ProgressHandle`] counts fetched pages, their items and failed requests and keeps the
+//! request being queried. Wrap a page turner into [`Reporting`] to report requests of any stream,
+//! with concurrent streams the current request is the latest one sent.
+//!
+//! ```
+//! use page_turner::prelude::*;
+//! use page_turner::progress::ProgressHandle;
+//!
+//! struct Api;
+//!
+//! impl PageTurner<usize> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         Ok(TurnedPage::new(vec![page; 10], (page < 4).then_some(page + 1)))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let progress = ProgressHandle::new();
+//! let watcher = progress.clone();
+//!
+//! let mut pages = std::pin::pin!(Api.pages_with_progress(0, progress));
+//! pages.try_next().await.unwrap();
+//! assert_eq!((watcher.pages(), watcher.items()), (1, 10));
+//!
+//! while pages.try_next().await.unwrap().is_some() {}
+//! assert_eq!((watcher.pages(), watcher.items(), watcher.errors()), (5, 50, 0));
+//! assert!(watcher.is_finished());
+//! # }
+//! ```
+
+use crate::internal::lock_unpoisoned;
+use crate::TurnedPage;
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+/// A handle to the progress of a pagination. Cloning is cheap and all clones observe the same
+/// pagination. See the [module docs](self).
+#[derive(Debug)]
+pub struct ProgressHandle<R>(Arc<Mutex<ProgressState<R>>>);
+
+#[derive(Debug)]
+struct ProgressState<R> {
+    pages: usize,
+    items: usize,
+    errors: usize,
+    in_flight: usize,
+    current_request: Option<R>,
+    finished: bool,
+}
+
+impl<R> ProgressHandle<R> {
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(ProgressState {
+            pages: 0,
+            items: 0,
+            errors: 0,
+            in_flight: 0,
+            current_request: None,
+            finished: false,
+        })))
+    }
+
+    /// The amount of successfully fetched pages
+    pub fn pages(&self) -> usize {
+        self.lock().pages
+    }
+
+    /// The amount of items in fetched pages
+    pub fn items(&self) -> usize {
+        self.lock().items
+    }
+
+    /// The amount of failed requests
+    pub fn errors(&self) -> usize {
+        self.lock().errors
+    }
+
+    /// Returns the request being queried or `None` if there are no requests in flight
+    pub fn current_request(&self) -> Option<R>
+    where
+        R: Clone,
+    {
+        self.lock().current_request.clone()
+    }
+
+    /// Whether the last page was fetched
+    pub fn is_finished(&self) -> bool {
+        self.lock().finished
+    }
+
+    fn start(&self, request: R) {
+        let mut state = self.lock();
+        state.in_flight += 1;
+        state.current_request = Some(request);
+    }
+
+    fn record<I, E>(&self, result: &Result<TurnedPage<I, R>, E>)
+    where
+        for<'t> &'t I: IntoIterator,
+    {
+        let mut state = self.lock();
+        state.in_flight -= 1;
+        if state.in_flight == 0 {
+            state.current_request = None;
+        }
+
+        match result {
+            Ok(page) => {
+                state.pages += 1;
+                state.items += page.items.into_iter().count();
+                state.finished |= page.next_request.is_none();
+            }
+            Err(_) => state.errors += 1,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ProgressState<R>> {
+        lock_unpoisoned(&self.0)
+    }
+}
+
+impl<R> Clone for ProgressHandle<R> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<R> Default for ProgressHandle<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A page turner that reports requests of the inner page turner to a [`ProgressHandle`]. See the
+/// [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Reporting<P, R> {
+    page_turner: P,
+    progress: ProgressHandle<R>,
+}
+
+impl<P, R> Reporting<P, R> {
+    pub fn new(page_turner: P, progress: ProgressHandle<R>) -> Self {
+        Self {
+            page_turner,
+            progress,
+        }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.page_turner
+    }
+
+    async fn turn_page_with<I, E, Fut>(
+        &self,
+        request: R,
+        turn_page: impl FnOnce(R) -> Fut,
+    ) -> Result<TurnedPage<I, R>, E>
+    where
+        R: Clone,
+        Fut: Future<Output = Result<TurnedPage<I, R>, E>>,
+        for<'t> &'t I: IntoIterator,
+    {
+        self.progress.start(request.clone());
+        let result = turn_page(request).await;
+        self.progress.record(&result);
+        result
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, R> crate::mt::PageTurner<R> for Reporting<P, R>
+where
+    P: crate::mt::PageTurner<R>,
+    R: Send + Clone,
+    for<'t> &'t P::PageItems: IntoIterator,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |request| self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, R> crate::local::PageTurner<R> for Reporting<P, R>
+where
+    P: crate::local::PageTurner<R>,
+    R: Clone,
+    for<'t> &'t P::PageItems: IntoIterator,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |request| self.page_turner.turn_page(request))
+            .await
+    }
+}
@@ -0,0 +1,303 @@
+//! Retries of failed page requests.
+//!
+//! Wrap a page turner into [`Retry`] to query a page again when [`RetryPolicy`] decides that its
+//! error is transient.
+//!
+//! A [`RetryBudget`] limits retries of all requests sent through the same [`Retry`] instance and
+//! its clones. With `pages_ahead*` many requests of a lookahead window may fail simultaneously
+//! and per request retry counts alone would multiply the load on an already struggling API.
+//!
+//! ```
+//! use page_turner::prelude::*;
+//! use page_turner::retry::{Retries, Retry, RetryBudget};
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! struct Flaky {
+//!     calls: AtomicUsize,
+//! }
+//!
+//! impl PageTurner<usize> for Flaky {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = &'static str;
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         // Every other call fails
+//!         if self.calls.fetch_add(1, Ordering::Relaxed) % 2 == 0 {
+//!             return Err("503 Service Unavailable");
+//!         }
+//!
+//!         Ok(TurnedPage::new(vec![page], (page < 3).then_some(page + 1)))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let flaky = Flaky { calls: AtomicUsize::new(0) };
+//! let retry = Retry::new(&flaky, Retries::new(2)).with_budget(RetryBudget::retries(3));
+//!
+//! let result: Result<Vec<_>, _> = retry.pages(0).items().try_collect().await;
+//! assert_eq!(result, Err("503 Service Unavailable"), "The budget is exhausted on page 3");
+//! # }
+//! ```
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+/// Decides whether a failed request should be sent again
+pub trait RetryPolicy<E> {
+    /// Returns a delay before the next attempt or `None` if the `error` must be returned. `retry`
+    /// is the number of the upcoming retry of the same request starting from 1.
+    fn retry_after(&self, error: &E, retry: usize) -> Option<Duration>;
+}
+
+impl<E, F> RetryPolicy<E> for F
+where
+    F: Fn(&E, usize) -> Option<Duration>,
+{
+    fn retry_after(&self, error: &E, retry: usize) -> Option<Duration> {
+        self(error, retry)
+    }
+}
+
+/// Retries any error up to the max amount of times per request with an optional exponential
+/// backoff
+#[derive(Debug, Clone, Copy)]
+pub struct Retries {
+    max_retries: usize,
+    delay: Duration,
+    backoff_factor: u32,
+}
+
+impl Retries {
+    pub fn new(max_retries: usize) -> Self {
+        Self {
+            max_retries,
+            delay: Duration::ZERO,
+            backoff_factor: 1,
+        }
+    }
+
+    /// Waits for `delay` before the first retry, defaults to zero
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Multiplies the delay by `factor` after each retry, defaults to 1
+    pub fn backoff(mut self, factor: u32) -> Self {
+        self.backoff_factor = factor;
+        self
+    }
+}
+
+impl<E> RetryPolicy<E> for Retries {
+    fn retry_after(&self, _: &E, retry: usize) -> Option<Duration> {
+        if retry > self.max_retries {
+            return None;
+        }
+
+        let exp = u32::try_from(retry - 1).unwrap_or(u32::MAX);
+        let factor = self.backoff_factor.saturating_pow(exp);
+        Some(self.delay.saturating_mul(factor))
+    }
+}
+
+/// A limit of retries shared by all requests of a [`Retry`] page turner. Cloning is cheap and all
+/// clones share the same budget.
+#[derive(Debug, Clone)]
+pub struct RetryBudget(Arc<Budget>);
+
+#[derive(Debug)]
+enum Budget {
+    Retries(AtomicUsize),
+    Time {
+        limit: Duration,
+        first_retry: OnceLock<Instant>,
+    },
+}
+
+impl RetryBudget {
+    /// Allows `max_retries` in total
+    pub fn retries(max_retries: usize) -> Self {
+        Self(Arc::new(Budget::Retries(AtomicUsize::new(max_retries))))
+    }
+
+    /// Allows retries during `limit` after the first retry
+    pub fn time(limit: Duration) -> Self {
+        Self(Arc::new(Budget::Time {
+            limit,
+            first_retry: OnceLock::new(),
+        }))
+    }
+
+    /// Takes one retry from the budget. Returns `false` if the budget is exhausted.
+    pub fn try_spend(&self) -> bool {
+        match self.0.as_ref() {
+            Budget::Retries(left) => left
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |left| {
+                    left.checked_sub(1)
+                })
+                .is_ok(),
+            Budget::Time { limit, first_retry } => {
+                first_retry.get_or_init(Instant::now).elapsed() < *limit
+            }
+        }
+    }
+}
+
+/// A page turner that retries failed requests of the inner page turner. See the [module
+/// docs](self).
+#[derive(Debug, Clone)]
+pub struct Retry<P, Policy> {
+    page_turner: P,
+    policy: Policy,
+    budget: Option<RetryBudget>,
+}
+
+impl<P, Policy> Retry<P, Policy> {
+    pub fn new(page_turner: P, policy: Policy) -> Self {
+        Self {
+            page_turner,
+            policy,
+            budget: None,
+        }
+    }
+
+    /// Limits the total amount of retries regardless of the policy
+    pub fn with_budget(mut self, budget: RetryBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    pub fn into_inner(self) -> P {
+        self.page_turner
+    }
+
+    /// Runs attempts of `turn_page` until one succeeds or the policy gives up. `turn_page` receives
+    /// the inner page turner, the request and the number of the attempt starting from 1.
+    pub(crate) async fn turn_page_with<'p, R, T, E, F, Fut>(
+        &'p self,
+        request: R,
+        turn_page: F,
+    ) -> Result<T, E>
+    where
+        R: Clone,
+        Policy: RetryPolicy<E>,
+        F: Fn(&'p P, R, usize) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut retry = 0;
+
+        loop {
+            // Attempts are recorded into the span of the stream request if there is one
+            #[cfg(feature = "tracing")]
+            tracing::Span::current().record("attempt", retry);
+
+            let error = match turn_page(&self.page_turner, request.clone(), retry + 1).await {
+                Ok(page) => return Ok(page),
+                Err(e) => e,
+            };
+
+            retry += 1;
+            let delay = match self.policy.retry_after(&error, retry) {
+                Some(delay) if self.budget.as_ref().map_or(true, RetryBudget::try_spend) => delay,
+                _ => return Err(error),
+            };
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                target: "page_turner",
+                retry,
+                delay_ms = delay.as_millis() as u64,
+                "retrying"
+            );
+
+            if !delay.is_zero() {
+                futures_timer::Delay::new(delay).await;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, Policy, R> crate::mt::PageTurner<R> for Retry<P, Policy>
+where
+    P: crate::mt::PageTurner<R>,
+    Policy: Send + Sync + RetryPolicy<P::PageError>,
+    R: Send + Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |page_turner, request, _| {
+            page_turner.turn_page(request)
+        })
+        .await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, Policy, R> crate::local::PageTurner<R> for Retry<P, Policy>
+where
+    P: crate::local::PageTurner<R>,
+    Policy: RetryPolicy<P::PageError>,
+    R: Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |page_turner, request, _| {
+            page_turner.turn_page(request)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_policy() {
+        let policy = Retries::new(3).delay(Duration::from_millis(10)).backoff(2);
+
+        let delays: Vec<_> = (1..=4)
+            .map(|retry| policy.retry_after(&(), retry))
+            .collect();
+        assert_eq!(
+            delays,
+            [
+                Some(Duration::from_millis(10)),
+                Some(Duration::from_millis(20)),
+                Some(Duration::from_millis(40)),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn budgets() {
+        let budget = RetryBudget::retries(2);
+        let shared = budget.clone();
+
+        assert!(budget.try_spend());
+        assert!(shared.try_spend());
+        assert!(!budget.try_spend());
+
+        let budget = RetryBudget::time(Duration::ZERO);
+        assert!(!budget.try_spend());
+
+        let budget = RetryBudget::time(Duration::from_secs(60));
+        assert!(budget.try_spend());
+    }
+}
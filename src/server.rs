@@ -0,0 +1,225 @@
+//! Server-side pagination: the inverse of a page turner.
+//!
+//! [`PageServer`] answers incoming page requests with [`TurnedPage`] responses built from a
+//! [`PageSource`], so services that both consume and serve paginated data can share the request
+//! types from [`crate::position`] and test their endpoints with page turners. Sources are
+//! implemented for vectors, slices and query functions created with [`from_fn`].
+//!
+//! The last page is detected by fetching one item more than the page size, so a page is the last
+//! one only if no items follow it.
+//!
+//! ```
+//! use page_turner::position::{Cursor, Offset, PageNumber};
+//! use page_turner::server::{self, PageServer};
+//!
+//! let server = PageServer::new((0..5).collect::<Vec<u32>>(), 2);
+//!
+//! let page = server.serve_page(PageNumber::new(3));
+//! assert_eq!(page.items, [4]);
+//! assert_eq!(page.next_request, None);
+//!
+//! let page = server.serve_offset(Offset::new(1, 10));
+//! assert_eq!(page.items, [1, 2], "The page size is capped by the server");
+//! assert_eq!(page.next_request, Some(Offset::new(3, 2)));
+//!
+//! let page = server.serve_cursor(None).unwrap();
+//! let page = server.serve_cursor(page.next_request.as_ref()).unwrap();
+//! assert_eq!(page.items, [2, 3]);
+//!
+//! let rows = PageServer::new(server::from_fn(|offset, limit| (offset..5).take(limit).collect()), 4);
+//! assert_eq!(rows.serve_page(PageNumber::FIRST).items, [0, 1, 2, 3]);
+//! ```
+
+use crate::position::{Cursor, Offset, PageNumber};
+use crate::TurnedPage;
+
+/// A source of items that can be fetched by their positions
+pub trait PageSource {
+    type Item;
+
+    /// Returns up to `limit` items starting at `offset`
+    fn fetch(&self, offset: usize, limit: usize) -> Vec<Self::Item>;
+}
+
+impl<T: Clone> PageSource for Vec<T> {
+    type Item = T;
+
+    fn fetch(&self, offset: usize, limit: usize) -> Vec<T> {
+        self.as_slice().fetch(offset, limit)
+    }
+}
+
+impl<T: Clone> PageSource for &[T] {
+    type Item = T;
+
+    fn fetch(&self, offset: usize, limit: usize) -> Vec<T> {
+        let start = offset.min(self.len());
+        let end = start.saturating_add(limit).min(self.len());
+        self[start..end].to_vec()
+    }
+}
+
+/// A source created with [`from_fn`]
+#[derive(Debug, Clone)]
+pub struct FromFn<F>(F);
+
+/// Creates a source from a query function that returns up to `limit` items starting at `offset`,
+/// i.e. a database query with `OFFSET` and `LIMIT` clauses
+pub fn from_fn<F, T>(query: F) -> FromFn<F>
+where
+    F: Fn(usize, usize) -> Vec<T>,
+{
+    FromFn(query)
+}
+
+impl<F, T> PageSource for FromFn<F>
+where
+    F: Fn(usize, usize) -> Vec<T>,
+{
+    type Item = T;
+
+    fn fetch(&self, offset: usize, limit: usize) -> Vec<T> {
+        (self.0)(offset, limit)
+    }
+}
+
+/// Answers page requests with pages of at most `page_size` items. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct PageServer<S> {
+    source: S,
+    page_size: usize,
+}
+
+impl<S> PageServer<S>
+where
+    S: PageSource,
+{
+    /// # Panics
+    ///
+    /// Panics if `page_size` is 0
+    pub fn new(source: S, page_size: usize) -> Self {
+        assert!(page_size > 0, "page_size must be positive");
+        Self { source, page_size }
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    /// Serves a page of numbered pages of `page_size` items
+    pub fn serve_page(&self, page: PageNumber) -> TurnedPage<Vec<S::Item>, PageNumber> {
+        let offset = page.index().saturating_mul(self.page_size);
+        let (items, has_next) = self.fetch(offset, self.page_size);
+
+        TurnedPage::new(items, has_next.then(|| page.checked_next()).flatten())
+    }
+
+    /// Serves a page at the requested offset. The requested page size is capped by the
+    /// server's `page_size` and the next request uses the resulting page size.
+    pub fn serve_offset(&self, request: Offset) -> TurnedPage<Vec<S::Item>, Offset> {
+        let page_size = request.page_size.clamp(1, self.page_size);
+        let (items, has_next) = self.fetch(request.offset, page_size);
+        let next_request = Offset::new(request.offset, page_size).checked_next();
+
+        TurnedPage::new(items, has_next.then_some(next_request).flatten())
+    }
+
+    /// Serves a page after an opaque cursor returned with a previous page, `None` requests the
+    /// first page
+    pub fn serve_cursor(
+        &self,
+        cursor: Option<&Cursor<String>>,
+    ) -> Result<TurnedPage<Vec<S::Item>, Cursor<String>>, InvalidCursor> {
+        let offset = match cursor {
+            Some(cursor) => cursor
+                .get()
+                .parse::<usize>()
+                .map_err(|_| InvalidCursor(cursor.get().clone()))?,
+            None => 0,
+        };
+
+        let (items, has_next) = self.fetch(offset, self.page_size);
+        let next_request = offset
+            .checked_add(items.len())
+            .map(|offset| Cursor::new(offset.to_string()));
+
+        Ok(TurnedPage::new(
+            items,
+            has_next.then_some(next_request).flatten(),
+        ))
+    }
+
+    /// Fetches a page and reports whether more items follow it
+    fn fetch(&self, offset: usize, limit: usize) -> (Vec<S::Item>, bool) {
+        let mut items = self.source.fetch(offset, limit.saturating_add(1));
+        let has_next = items.len() > limit;
+        items.truncate(limit);
+
+        (items, has_next)
+    }
+}
+
+/// A cursor that wasn't issued by a [`PageServer`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCursor(pub String);
+
+impl std::fmt::Display for InvalidCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid cursor: {}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidCursor {}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+
+    struct Client<'s>(&'s PageServer<Vec<usize>>);
+
+    impl PageTurner<Offset> for Client<'_> {
+        type PageItems = Vec<usize>;
+        type PageError = ();
+
+        async fn turn_page(&self, request: Offset) -> TurnedPageResult<Self, Offset> {
+            Ok(self.0.serve_offset(request))
+        }
+    }
+
+    #[tokio::test]
+    async fn serves_page_turners() {
+        let server = PageServer::new((0..23).collect::<Vec<_>>(), 5);
+
+        let items: Vec<_> = Client(&server)
+            .pages_ahead(3, Limit::None, Offset::new(0, 5))
+            .items()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items, (0..23).collect::<Vec<_>>());
+
+        let empty = PageServer::new(Vec::<usize>::new(), 5);
+        assert_eq!(
+            empty.serve_page(PageNumber::FIRST),
+            TurnedPage::last(Vec::new())
+        );
+
+        let exact = PageServer::new((0..10).collect::<Vec<_>>(), 5);
+        assert_eq!(
+            exact.serve_page(PageNumber::new(2)),
+            TurnedPage::last(vec![5, 6, 7, 8, 9])
+        );
+
+        let cursor = Cursor::new("abc".to_owned());
+        assert_eq!(
+            server.serve_cursor(Some(&cursor)),
+            Err(InvalidCursor("abc".to_owned()))
+        );
+    }
+}
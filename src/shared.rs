@@ -0,0 +1,302 @@
+//! Deduplication of identical in-flight requests.
+//!
+//! Wrap a page turner into [`SharedPageTurner`] and paginate the same query from many streams,
+//! i.e. per user session, without hammering the API: while a request is in flight all equal
+//! requests wait for its result instead of being sent again. Results aren't cached, a request sent
+//! after the previous one completed reaches the inner page turner.
+//!
+//! Requests must implement `Eq + Hash + Clone` and results are cloned for each waiting request.
+//!
+//! ```
+//! use page_turner::prelude::*;
+//! use page_turner::shared::SharedPageTurner;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use std::time::Duration;
+//!
+//! struct Api {
+//!     calls: AtomicUsize,
+//! }
+//!
+//! impl PageTurner<usize> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         self.calls.fetch_add(1, Ordering::Relaxed);
+//!         tokio::time::sleep(Duration::from_millis(10)).await;
+//!         Ok(TurnedPage::new(vec![page], (page < 2).then_some(page + 1)))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let api = SharedPageTurner::new(Api { calls: AtomicUsize::new(0) });
+//!
+//! let (first, second) = futures::future::try_join(
+//!     api.pages(0).items().try_collect::<Vec<_>>(),
+//!     api.pages(0).items().try_collect::<Vec<_>>(),
+//! )
+//! .await
+//! .unwrap();
+//!
+//! assert_eq!(first, second);
+//! assert_eq!(api.into_inner().calls.into_inner(), 3);
+//! # }
+//! ```
+
+use crate::internal::lock_unpoisoned;
+use crate::TurnedPage;
+use futures::channel::oneshot;
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    future::Future,
+    hash::Hash,
+    marker::PhantomData,
+    sync::{Mutex, MutexGuard},
+};
+
+/// Senders of results to requests waiting for the equal in-flight request
+type Senders<R, I, E> = Vec<oneshot::Sender<Result<TurnedPage<I, R>, E>>>;
+type Waiters<R, I, E> = HashMap<R, Senders<R, I, E>>;
+
+/// A page turner that shares results of in-flight requests among equal requests. See the [module
+/// docs](self).
+#[derive(Debug)]
+pub struct SharedPageTurner<P> {
+    page_turner: P,
+    // `Waiters` keyed by their `TypeId` because a page turner may accept many request types
+    in_flight: Mutex<HashMap<TypeId, Box<dyn Any + Send>>>,
+}
+
+impl<P> SharedPageTurner<P> {
+    pub fn new(page_turner: P) -> Self {
+        Self {
+            page_turner,
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.page_turner
+    }
+
+    async fn turn_page_with<R, I, E, Fut>(
+        &self,
+        request: R,
+        turn_page: impl FnOnce(R) -> Fut,
+    ) -> Result<TurnedPage<I, R>, E>
+    where
+        R: 'static + Send + Eq + Hash + Clone,
+        I: 'static + Send + Clone,
+        E: 'static + Send + Clone,
+        Fut: Future<Output = Result<TurnedPage<I, R>, E>>,
+    {
+        loop {
+            let waiting = {
+                let mut in_flight = self.lock();
+                let waiters = waiters::<R, I, E>(&mut in_flight);
+
+                match waiters.get_mut(&request) {
+                    Some(senders) => {
+                        let (tx, rx) = oneshot::channel();
+                        senders.push(tx);
+                        rx
+                    }
+                    None => {
+                        waiters.insert(request.clone(), Vec::new());
+                        break;
+                    }
+                }
+            };
+
+            match waiting.await {
+                Ok(result) => return result,
+                // The request that was in flight has been dropped, send it again
+                Err(oneshot::Canceled) => continue,
+            }
+        }
+
+        let guard = InFlight::<P, R, I, E> {
+            shared: self,
+            request: Some(request.clone()),
+            _results: PhantomData,
+        };
+
+        let result = turn_page(request).await;
+
+        for sender in guard.complete() {
+            // The waiting request might have been dropped
+            let _ = sender.send(result.clone());
+        }
+
+        result
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<TypeId, Box<dyn Any + Send>>> {
+        lock_unpoisoned(&self.in_flight)
+    }
+}
+
+fn waiters<R, I, E>(in_flight: &mut HashMap<TypeId, Box<dyn Any + Send>>) -> &mut Waiters<R, I, E>
+where
+    R: 'static + Send + Eq + Hash,
+    I: 'static + Send,
+    E: 'static + Send,
+{
+    in_flight
+        .entry(TypeId::of::<Waiters<R, I, E>>())
+        .or_insert_with(|| Box::new(Waiters::<R, I, E>::new()))
+        .downcast_mut()
+        .expect("Waiters are keyed by their TypeId")
+}
+
+/// Removes the request from in-flight ones when it completes or is dropped, in the latter case
+/// the waiting requests are woken up to send the request again.
+struct InFlight<'s, P, R, I, E>
+where
+    R: 'static + Send + Eq + Hash,
+    I: 'static + Send,
+    E: 'static + Send,
+{
+    shared: &'s SharedPageTurner<P>,
+    request: Option<R>,
+    _results: PhantomData<fn() -> (I, E)>,
+}
+
+impl<P, R, I, E> InFlight<'_, P, R, I, E>
+where
+    R: 'static + Send + Eq + Hash,
+    I: 'static + Send,
+    E: 'static + Send,
+{
+    fn complete(mut self) -> Senders<R, I, E> {
+        self.remove().unwrap_or_default()
+    }
+
+    fn remove(&mut self) -> Option<Senders<R, I, E>> {
+        let request = self.request.take()?;
+        waiters::<R, I, E>(&mut self.shared.lock()).remove(&request)
+    }
+}
+
+impl<P, R, I, E> Drop for InFlight<'_, P, R, I, E>
+where
+    R: 'static + Send + Eq + Hash,
+    I: 'static + Send,
+    E: 'static + Send,
+{
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, R> crate::mt::PageTurner<R> for SharedPageTurner<P>
+where
+    P: crate::mt::PageTurner<R>,
+    R: 'static + Send + Eq + Hash + Clone,
+    P::PageItems: 'static + Clone,
+    P::PageError: 'static + Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |request| self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, R> crate::local::PageTurner<R> for SharedPageTurner<P>
+where
+    P: crate::local::PageTurner<R>,
+    R: 'static + Send + Eq + Hash + Clone,
+    P::PageItems: 'static + Send + Clone,
+    P::PageError: 'static + Send + Clone,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |request| self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use futures::{StreamExt, TryStreamExt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    struct Counted {
+        calls: AtomicUsize,
+        delay: Duration,
+    }
+
+    impl PageTurner<usize> for Counted {
+        type PageItems = Vec<usize>;
+        type PageError = String;
+
+        async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(self.delay).await;
+
+            match page {
+                3 => Err("Page 3 is broken".to_owned()),
+                _ => Ok(TurnedPage::new(vec![page], (page < 4).then_some(page + 1))),
+            }
+        }
+    }
+
+    fn counted() -> SharedPageTurner<Counted> {
+        SharedPageTurner::new(Counted {
+            calls: AtomicUsize::new(0),
+            delay: Duration::from_millis(20),
+        })
+    }
+
+    #[tokio::test]
+    async fn coalesces_equal_requests() {
+        let shared = counted();
+
+        let results =
+            futures::future::join_all((0..4).map(|_| shared.pages(0).try_collect::<Vec<_>>()))
+                .await;
+
+        for result in results {
+            assert_eq!(result, Err("Page 3 is broken".to_owned()));
+        }
+        assert_eq!(shared.page_turner.calls.load(Ordering::Relaxed), 4);
+
+        let pages: Vec<_> = shared.pages(0).take(2).try_collect().await.unwrap();
+        assert_eq!(pages, [vec![0], vec![1]]);
+        assert_eq!(
+            shared.page_turner.calls.load(Ordering::Relaxed),
+            6,
+            "Completed requests aren't cached"
+        );
+    }
+
+    #[tokio::test]
+    async fn resends_dropped_requests() {
+        let shared = counted();
+
+        let dropped = tokio::time::timeout(Duration::from_millis(5), shared.turn_page(0));
+        let (dropped, waiting) = futures::future::join(dropped, async {
+            tokio::task::yield_now().await;
+            shared.turn_page(0).await
+        })
+        .await;
+
+        assert!(dropped.is_err());
+        assert_eq!(waiting, Ok(TurnedPage::next(vec![0], 1)));
+        assert_eq!(shared.page_turner.calls.load(Ordering::Relaxed), 2);
+    }
+}
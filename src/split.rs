@@ -0,0 +1,209 @@
+//! Allows to consume the same pages stream as pages and as items simultaneously without querying
+//! pages twice. See `PagesStream::split_items`.
+
+use crate::internal::{async_iterator_impl, lock_unpoisoned};
+use futures::{
+    stream::Stream,
+    task::{waker, ArcWake},
+};
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+const PAGES: usize = 0;
+const ITEMS: usize = 1;
+
+type Page<T, E> = Result<Arc<T>, E>;
+
+struct SplitState<S, T, E> {
+    stream: Option<Pin<Box<S>>>,
+    queues: [VecDeque<Page<T, E>>; 2],
+    dropped: [bool; 2],
+}
+
+struct SplitWaker {
+    wakers: Mutex<[Option<Waker>; 2]>,
+}
+
+impl ArcWake for SplitWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        let wakers = std::mem::take(&mut *lock_unpoisoned(&arc_self.wakers));
+        for waker in wakers.into_iter().flatten() {
+            waker.wake();
+        }
+    }
+}
+
+struct Shared<S, T, E> {
+    state: Mutex<SplitState<S, T, E>>,
+    waker: Arc<SplitWaker>,
+}
+
+impl<S, T, E> Shared<S, T, E>
+where
+    S: Stream<Item = Result<T, E>>,
+    E: Clone,
+{
+    fn poll_next_page(&self, half: usize, cx: &mut Context<'_>) -> Poll<Option<Page<T, E>>> {
+        let mut state = lock_unpoisoned(&self.state);
+
+        if let Some(page) = state.queues[half].pop_front() {
+            return Poll::Ready(Some(page));
+        }
+
+        let Some(stream) = state.stream.as_mut() else {
+            return Poll::Ready(None);
+        };
+
+        // The inner stream must wake both halves because the one that polled it last may never
+        // poll it again.
+        lock_unpoisoned(&self.waker.wakers)[half] = Some(cx.waker().clone());
+        let shared_waker = waker(self.waker.clone());
+
+        match stream
+            .as_mut()
+            .poll_next(&mut Context::from_waker(&shared_waker))
+        {
+            Poll::Ready(Some(result)) => {
+                let page = result.map(Arc::new);
+                let other = 1 - half;
+
+                if !state.dropped[other] {
+                    let copy = match &page {
+                        Ok(page) => Ok(page.clone()),
+                        Err(e) => Err(e.clone()),
+                    };
+
+                    state.queues[other].push_back(copy);
+                    if let Some(waker) = lock_unpoisoned(&self.waker.wakers)[other].take() {
+                        waker.wake();
+                    }
+                }
+
+                Poll::Ready(Some(page))
+            }
+            Poll::Ready(None) => {
+                state.stream = None;
+                ArcWake::wake_by_ref(&self.waker);
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<S, T, E> Shared<S, T, E> {
+    fn drop_half(&self, half: usize) {
+        let mut state = lock_unpoisoned(&self.state);
+        state.dropped[half] = true;
+        state.queues[half].clear();
+    }
+}
+
+pub(crate) fn split<S, T, E>(stream: S) -> (SplitPages<S, T, E>, SplitItems<S, T, E>)
+where
+    T: IntoIterator,
+{
+    let shared = Arc::new(Shared {
+        state: Mutex::new(SplitState {
+            stream: Some(Box::pin(stream)),
+            queues: [VecDeque::new(), VecDeque::new()],
+            dropped: [false; 2],
+        }),
+        waker: Arc::new(SplitWaker {
+            wakers: Mutex::new([None, None]),
+        }),
+    });
+
+    (
+        SplitPages {
+            shared: shared.clone(),
+        },
+        SplitItems {
+            shared,
+            items: None,
+        },
+    )
+}
+
+/// The pages half of a split pages stream. Pages are shared with [`SplitItems`] via [`Arc`].
+pub struct SplitPages<S, T, E> {
+    shared: Arc<Shared<S, T, E>>,
+}
+
+impl<S, T, E> Stream for SplitPages<S, T, E>
+where
+    S: Stream<Item = Result<T, E>>,
+    E: Clone,
+{
+    type Item = Result<Arc<T>, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.shared.poll_next_page(PAGES, cx)
+    }
+}
+
+async_iterator_impl!([S, T, E] SplitPages<S, T, E>);
+
+impl<S, T, E> Drop for SplitPages<S, T, E> {
+    fn drop(&mut self) {
+        self.shared.drop_half(PAGES);
+    }
+}
+
+/// The items half of a split pages stream. If [`SplitPages`] is still holding a page by the time
+/// its items are requested the page gets cloned, otherwise the page is reused without copying.
+pub struct SplitItems<S, T, E>
+where
+    T: IntoIterator,
+{
+    shared: Arc<Shared<S, T, E>>,
+    items: Option<T::IntoIter>,
+}
+
+// Page items are never pinned and the inner stream is pinned on the heap
+impl<S, T, E> Unpin for SplitItems<S, T, E> where T: IntoIterator {}
+
+impl<S, T, E> Stream for SplitItems<S, T, E>
+where
+    S: Stream<Item = Result<T, E>>,
+    T: IntoIterator + Clone,
+    E: Clone,
+{
+    type Item = Result<T::Item, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(item) = this.items.as_mut().and_then(Iterator::next) {
+                return Poll::Ready(Some(Ok(item)));
+            }
+
+            this.items = None;
+
+            match std::task::ready!(this.shared.poll_next_page(ITEMS, cx)) {
+                Some(Ok(page)) => {
+                    let page = Arc::try_unwrap(page).unwrap_or_else(|page| T::clone(&page));
+                    this.items = Some(page.into_iter());
+                }
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+async_iterator_impl!([S, T: IntoIterator, E] SplitItems<S, T, E>);
+
+impl<S, T, E> Drop for SplitItems<S, T, E>
+where
+    T: IntoIterator,
+{
+    fn drop(&mut self) {
+        self.shared.drop_half(ITEMS);
+    }
+}
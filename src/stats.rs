@@ -0,0 +1,344 @@
+//! Counters and request latencies of pagination runs for dashboards. See `PagesStream::with_stats`.
+//!
+//! A [`StreamStats`] handle is shared by the stream and the page turner it queries:
+//!
+//! - a stream wrapped with `PagesStream::with_stats` counts yielded pages, items and errors,
+//! - a page turner wrapped into [`Measured`] counts issued and failed requests and sorts their
+//!   latencies into histogram buckets. With `pages_ahead` streams it observes every lookahead
+//!   request, including the ones whose pages are never yielded.
+//!
+//! Either of them can be used alone. Statistics can be read at any time, during or after the run.
+//!
+//! ```
+//! use page_turner::prelude::*;
+//! use page_turner::stats::{Measured, StreamStats};
+//! use std::time::Duration;
+//!
+//! struct Api;
+//!
+//! impl PageTurner<usize> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         Ok(TurnedPage::new(vec![page; 2], (page < 2).then_some(page + 1)))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let stats = StreamStats::with_buckets([Duration::from_millis(100), Duration::from_secs(1)]);
+//! let api = Measured::new(Api, stats.clone());
+//!
+//! let items: Vec<_> = api
+//!     .pages(0)
+//!     .with_stats(stats.clone())
+//!     .items()
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//! assert_eq!(items, [0, 0, 1, 1, 2, 2]);
+//!
+//! assert_eq!((stats.pages(), stats.items(), stats.errors()), (3, 6, 0));
+//! assert_eq!((stats.requests(), stats.failed_requests()), (3, 0));
+//! assert_eq!(stats.latency_buckets()[0].count, 3);
+//! # }
+//! ```
+
+use crate::internal::{async_iterator_impl, lock_unpoisoned};
+use crate::TurnedPage;
+use futures::Stream;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+const DEFAULT_BUCKETS: [Duration; 8] = [
+    Duration::from_millis(10),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+
+/// A handle to statistics of a pagination run. Cloning is cheap and all clones observe the same
+/// run. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct StreamStats(Arc<Mutex<StatsState>>);
+
+#[derive(Debug)]
+struct StatsState {
+    pages: usize,
+    items: usize,
+    errors: usize,
+    requests: usize,
+    failed_requests: usize,
+    buckets: Vec<LatencyBucket>,
+}
+
+/// The amount of requests that took longer than the upper bound of the previous bucket and not
+/// longer than the `upper_bound` of this one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyBucket {
+    /// `None` for the last bucket that counts requests slower than all bounds
+    pub upper_bound: Option<Duration>,
+    pub count: usize,
+}
+
+impl StreamStats {
+    /// Creates stats with latency buckets from 10 milliseconds to 10 seconds
+    pub fn new() -> Self {
+        Self::with_buckets(DEFAULT_BUCKETS)
+    }
+
+    /// Creates stats with latency buckets bounded by `upper_bounds`. A bucket for requests slower
+    /// than all bounds is always added.
+    pub fn with_buckets(upper_bounds: impl IntoIterator<Item = Duration>) -> Self {
+        let mut upper_bounds: Vec<_> = upper_bounds.into_iter().collect();
+        upper_bounds.sort();
+        upper_bounds.dedup();
+
+        let buckets = upper_bounds
+            .into_iter()
+            .map(Some)
+            .chain([None])
+            .map(|upper_bound| LatencyBucket {
+                upper_bound,
+                count: 0,
+            })
+            .collect();
+
+        Self(Arc::new(Mutex::new(StatsState {
+            pages: 0,
+            items: 0,
+            errors: 0,
+            requests: 0,
+            failed_requests: 0,
+            buckets,
+        })))
+    }
+
+    /// The amount of pages yielded by the stream
+    pub fn pages(&self) -> usize {
+        self.lock().pages
+    }
+
+    /// The amount of items in pages yielded by the stream
+    pub fn items(&self) -> usize {
+        self.lock().items
+    }
+
+    /// The amount of errors yielded by the stream
+    pub fn errors(&self) -> usize {
+        self.lock().errors
+    }
+
+    /// The amount of requests completed by the [`Measured`] page turner
+    pub fn requests(&self) -> usize {
+        self.lock().requests
+    }
+
+    /// The amount of requests completed by the [`Measured`] page turner with an error
+    pub fn failed_requests(&self) -> usize {
+        self.lock().failed_requests
+    }
+
+    /// Latencies of requests completed by the [`Measured`] page turner, ordered by upper bounds
+    pub fn latency_buckets(&self) -> Vec<LatencyBucket> {
+        self.lock().buckets.clone()
+    }
+
+    fn record_page(&self, items: Option<usize>) {
+        let mut state = self.lock();
+        match items {
+            Some(items) => {
+                state.pages += 1;
+                state.items += items;
+            }
+            None => state.errors += 1,
+        }
+    }
+
+    fn record_request(&self, latency: Duration, failed: bool) {
+        let mut state = self.lock();
+        state.requests += 1;
+        state.failed_requests += usize::from(failed);
+
+        if let Some(bucket) = state.buckets.iter_mut().find(|bucket| {
+            bucket
+                .upper_bound
+                .map_or(true, |upper_bound| latency <= upper_bound)
+        }) {
+            bucket.count += 1;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StatsState> {
+        lock_unpoisoned(&self.0)
+    }
+}
+
+impl Default for StreamStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A stream returned by `PagesStream::with_stats`
+pub struct WithStats<S> {
+    stream: Pin<Box<S>>,
+    stats: StreamStats,
+}
+
+impl<S> WithStats<S> {
+    #[cfg(any(feature = "mt", feature = "local"))]
+    pub(crate) fn new(stream: S, stats: StreamStats) -> Self {
+        Self {
+            stream: Box::pin(stream),
+            stats,
+        }
+    }
+}
+
+// The inner stream is boxed
+impl<S> Unpin for WithStats<S> {}
+
+impl<S, T, E> Stream for WithStats<S>
+where
+    S: Stream<Item = Result<T, E>>,
+    for<'t> &'t T: IntoIterator,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let page = std::task::ready!(this.stream.as_mut().poll_next(cx));
+
+        match &page {
+            Some(Ok(items)) => this.stats.record_page(Some(items.into_iter().count())),
+            Some(Err(_)) => this.stats.record_page(None),
+            None => {}
+        }
+
+        Poll::Ready(page)
+    }
+}
+
+async_iterator_impl!([S] WithStats<S>);
+
+/// A page turner that records requests of the inner page turner into [`StreamStats`]. See the
+/// [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Measured<P> {
+    page_turner: P,
+    stats: StreamStats,
+}
+
+impl<P> Measured<P> {
+    pub fn new(page_turner: P, stats: StreamStats) -> Self {
+        Self { page_turner, stats }
+    }
+
+    pub fn stats(&self) -> &StreamStats {
+        &self.stats
+    }
+
+    pub fn into_inner(self) -> P {
+        self.page_turner
+    }
+
+    async fn turn_page_with<I, R, E>(
+        &self,
+        page: impl Future<Output = Result<TurnedPage<I, R>, E>>,
+    ) -> Result<TurnedPage<I, R>, E> {
+        let started = Instant::now();
+        let page = page.await;
+        self.stats.record_request(started.elapsed(), page.is_err());
+
+        page
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, R> crate::mt::PageTurner<R> for Measured<P>
+where
+    P: crate::mt::PageTurner<R>,
+    R: Send,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        self.turn_page_with(self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, R> crate::local::PageTurner<R> for Measured<P>
+where
+    P: crate::local::PageTurner<R>,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        self.turn_page_with(self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::adapters::slice::{SlicePage, SlicePageTurner};
+    use crate::mt::prelude::*;
+    use futures::StreamExt;
+
+    #[tokio::test]
+    async fn records_pages_and_requests() {
+        let stats =
+            StreamStats::with_buckets([Duration::from_millis(15), Duration::from_millis(1)]);
+        let numbers = Measured::new(
+            SlicePageTurner::new((0..10).collect::<Vec<u8>>(), 3)
+                .delay(Duration::from_millis(5))
+                .fail_at(2, 1),
+            stats.clone(),
+        );
+
+        let pages: Vec<_> = numbers
+            .pages_ahead(4, Limit::None, SlicePage(0))
+            .with_stats(stats.clone())
+            .collect()
+            .await;
+
+        assert_eq!(pages.len(), 3);
+        assert_eq!((stats.pages(), stats.items(), stats.errors()), (2, 6, 1));
+        assert_eq!(stats.failed_requests(), 1);
+        assert!(stats.requests() >= 3, "Lookahead requests are measured");
+
+        let buckets = stats.latency_buckets();
+        let bounds: Vec<_> = buckets.iter().map(|bucket| bucket.upper_bound).collect();
+        assert_eq!(
+            bounds,
+            [
+                Some(Duration::from_millis(1)),
+                Some(Duration::from_millis(15)),
+                None
+            ]
+        );
+        assert_eq!(buckets[0].count, 0);
+        assert_eq!(
+            buckets.iter().map(|bucket| bucket.count).sum::<usize>(),
+            stats.requests()
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Validation of [`RequestAhead`] implementations.
+//!
+//! `pages_ahead*` streams generate requests with [`RequestAhead::next_request`] instead of taking
+//! them from responses, therefore, a mismatch between the two silently makes them yield different
+//! data than `pages`. Wrap a page turner into [`Strict`] to compare every `next_request` returned
+//! by `turn_page` with the predicted one and fail with [`StrictError::Diverged`] on a mismatch.
+//!
+//! ```
+//! use page_turner::prelude::*;
+//! use page_turner::strict::{Strict, StrictError};
+//!
+//! #[derive(Debug, Clone, PartialEq)]
+//! struct Offset(usize);
+//!
+//! impl RequestAhead for Offset {
+//!     fn next_request(&self) -> Self {
+//!         Offset(self.0 + 10)
+//!     }
+//! }
+//!
+//! struct Api;
+//!
+//! impl PageTurner<Offset> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, Offset(offset): Offset) -> TurnedPageResult<Self, Offset> {
+//!         // The API actually returns pages of 20 items
+//!         Ok(TurnedPage::new((offset..offset + 20).collect(), Some(Offset(offset + 20))))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let result = Strict::new(Api)
+//!     .pages_ahead(2, Limit::Pages(2), Offset(0))
+//!     .try_collect::<Vec<_>>()
+//!     .await;
+//!
+//! assert!(matches!(result, Err(StrictError::Diverged { .. })));
+//! # }
+//! ```
+
+use crate::{RequestAhead, TurnedPage};
+
+/// A page turner that validates its requests against [`RequestAhead`]. See the [module
+/// docs](self).
+#[derive(Debug, Clone)]
+pub struct Strict<P>(P);
+
+impl<P> Strict<P> {
+    pub fn new(page_turner: P) -> Self {
+        Self(page_turner)
+    }
+
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+fn validate<I, R, E>(
+    predicted: R,
+    result: Result<TurnedPage<I, R>, E>,
+) -> Result<TurnedPage<I, R>, StrictError<E, R>>
+where
+    R: PartialEq,
+{
+    match result {
+        Ok(TurnedPage {
+            next_request: Some(actual),
+            ..
+        }) if actual != predicted => Err(StrictError::Diverged { predicted, actual }),
+        Ok(page) => Ok(page),
+        Err(e) => Err(StrictError::Page(e)),
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, R> crate::mt::PageTurner<R> for Strict<P>
+where
+    P: crate::mt::PageTurner<R>,
+    R: Send + RequestAhead + PartialEq,
+{
+    type PageItems = P::PageItems;
+    type PageError = StrictError<P::PageError, R>;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        let predicted = request.next_request();
+        validate(predicted, self.0.turn_page(request).await)
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, R> crate::local::PageTurner<R> for Strict<P>
+where
+    P: crate::local::PageTurner<R>,
+    R: RequestAhead + PartialEq,
+{
+    type PageItems = P::PageItems;
+    type PageError = StrictError<P::PageError, R>;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        let predicted = request.next_request();
+        validate(predicted, self.0.turn_page(request).await)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StrictError<E, R> {
+    /// An error of the inner page turner
+    Page(E),
+    /// `turn_page` returned a next request different from [`RequestAhead::next_request`]
+    Diverged { predicted: R, actual: R },
+}
+
+impl<E, R> std::fmt::Display for StrictError<E, R>
+where
+    E: std::fmt::Display,
+    R: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Page(e) => e.fmt(f),
+            Self::Diverged { predicted, actual } => write!(
+                f,
+                "turn_page returned the next request {actual:?} while RequestAhead::next_request \
+                 predicted {predicted:?}, pages_ahead streams would query different pages than \
+                 pages"
+            ),
+        }
+    }
+}
+
+impl<E, R> std::error::Error for StrictError<E, R>
+where
+    E: std::error::Error + 'static,
+    R: std::fmt::Debug,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Page(e) => Some(e),
+            Self::Diverged { .. } => None,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::mt::prelude::*;
+    use crate::test_utils::*;
+    use futures::TryStreamExt;
+
+    #[tokio::test]
+    async fn accepts_consistent_requests() {
+        let blog = Strict::new(BlogClient::new(7));
+        let records: Vec<_> = blog
+            .pages_ahead(3, Limit::None, GetContentRequest { page: 0 })
+            .items()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(records.len(), 7);
+    }
+}
@@ -0,0 +1,271 @@
+//! A summary of a pagination run available once its stream finishes. See
+//! `PagesStream::with_summary`.
+//!
+//! [`RunReport`] combines what the stream observes (yielded pages and items, elapsed time and how
+//! the run ended) with requests recorded by an [`Audited`](crate::audit::Audited) page turner
+//! (retries, wasted lookahead requests and the request to resume from). The summary is finalized
+//! when the stream ends, yields an error or is dropped, so a checkpoint can be taken in any case.
+//!
+//! ```
+//! use page_turner::audit::Audited;
+//! use page_turner::prelude::*;
+//! use page_turner::summary::{RunOutcome, RunReport};
+//!
+//! struct Api;
+//!
+//! impl PageTurner<usize> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         Ok(TurnedPage::next(vec![page; 3], page + 1))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::StreamExt;
+//!
+//! let api = Audited::new(Api);
+//! let report = RunReport::new(api.log());
+//!
+//! let pages: Vec<_> = api.pages(0).with_summary(report.clone()).take(2).collect().await;
+//! assert_eq!(pages.len(), 2);
+//!
+//! let summary = report.summary().unwrap();
+//! assert_eq!(summary.outcome, RunOutcome::Dropped);
+//! assert_eq!((summary.pages, summary.items), (2, 6));
+//! assert_eq!(summary.next_request, Some(2));
+//! # }
+//! ```
+
+use crate::audit::AuditLog;
+use crate::internal::{async_iterator_impl, lock_unpoisoned};
+use futures::Stream;
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+/// How a pagination run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RunOutcome {
+    /// The stream yielded all pages
+    Completed,
+    /// The stream yielded an error
+    Failed,
+    /// The stream was dropped before it ended
+    Dropped,
+}
+
+/// Statistics of a finished pagination run
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunSummary<R> {
+    pub outcome: RunOutcome,
+    /// The amount of pages yielded by the stream
+    pub pages: usize,
+    /// The amount of items in yielded pages
+    pub items: usize,
+    /// The amount of requests that repeated an earlier failed request
+    pub retries: usize,
+    /// The amount of completed requests whose pages were never yielded, i.e. lookahead requests
+    /// past the last page or ahead of the page the stream was dropped at
+    pub wasted_requests: usize,
+    /// The time passed between the first poll of the stream and the end of the run
+    pub elapsed: Duration,
+    /// The request to resume the pagination from, `None` if the run completed. With concurrent
+    /// streams it's the cursor of the audit log, see [`crate::audit::AuditSummary::cursor`].
+    pub next_request: Option<R>,
+}
+
+/// A handle to the [`RunSummary`] of a stream wrapped with `PagesStream::with_summary`. Cloning is
+/// cheap and all clones observe the same run.
+#[derive(Debug)]
+pub struct RunReport<R>(Arc<Mutex<ReportState<R>>>);
+
+#[derive(Debug)]
+struct ReportState<R> {
+    log: AuditLog<R>,
+    started: Option<Instant>,
+    pages: usize,
+    items: usize,
+    finished: Option<(RunOutcome, Duration)>,
+}
+
+impl<R> RunReport<R> {
+    /// Creates a report that takes requests from the `log` of the page turner the stream queries
+    pub fn new(log: AuditLog<R>) -> Self {
+        Self(Arc::new(Mutex::new(ReportState {
+            log,
+            started: None,
+            pages: 0,
+            items: 0,
+            finished: None,
+        })))
+    }
+
+    /// Returns the summary of the run or `None` if the stream hasn't finished yet
+    pub fn summary(&self) -> Option<RunSummary<R>>
+    where
+        R: Clone + PartialEq,
+    {
+        let state = self.lock();
+        let (outcome, elapsed) = state.finished?;
+        let audit = state.log.summary();
+
+        let retries = audit.retries();
+        let failed_requests = usize::from(outcome == RunOutcome::Failed);
+        let wasted_requests = audit
+            .requests_issued()
+            .saturating_sub(retries + state.pages + failed_requests);
+
+        Some(RunSummary {
+            outcome,
+            pages: state.pages,
+            items: state.items,
+            retries,
+            wasted_requests,
+            elapsed,
+            next_request: match outcome {
+                RunOutcome::Completed => None,
+                RunOutcome::Failed | RunOutcome::Dropped => audit.cursor,
+            },
+        })
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.lock().finished.is_some()
+    }
+
+    fn start(&self) {
+        self.lock().started.get_or_insert_with(Instant::now);
+    }
+
+    fn record(&self, items: usize) {
+        let mut state = self.lock();
+        state.pages += 1;
+        state.items += items;
+    }
+
+    /// Finishes the run with the `outcome` unless it has already finished
+    fn finish(&self, outcome: RunOutcome) {
+        let mut state = self.lock();
+        if state.finished.is_none() {
+            let elapsed = state.started.map(|started| started.elapsed());
+            state.finished = Some((outcome, elapsed.unwrap_or_default()));
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ReportState<R>> {
+        lock_unpoisoned(&self.0)
+    }
+}
+
+impl<R> Clone for RunReport<R> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+/// A stream returned by `PagesStream::with_summary`
+pub struct Summarized<S, R> {
+    stream: Pin<Box<S>>,
+    report: RunReport<R>,
+}
+
+impl<S, R> Summarized<S, R> {
+    #[cfg(any(feature = "mt", feature = "local"))]
+    pub(crate) fn new(stream: S, report: RunReport<R>) -> Self {
+        Self {
+            stream: Box::pin(stream),
+            report,
+        }
+    }
+}
+
+// The inner stream is boxed
+impl<S, R> Unpin for Summarized<S, R> {}
+
+impl<S, R, T, E> Stream for Summarized<S, R>
+where
+    S: Stream<Item = Result<T, E>>,
+    for<'t> &'t T: IntoIterator,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        this.report.start();
+
+        let page = std::task::ready!(this.stream.as_mut().poll_next(cx));
+        match &page {
+            Some(Ok(items)) => this.report.record(items.into_iter().count()),
+            Some(Err(_)) => this.report.finish(RunOutcome::Failed),
+            None => this.report.finish(RunOutcome::Completed),
+        }
+
+        Poll::Ready(page)
+    }
+}
+
+impl<S, R> Drop for Summarized<S, R> {
+    fn drop(&mut self) {
+        self.report.finish(RunOutcome::Dropped);
+    }
+}
+
+async_iterator_impl!([S, R] Summarized<S, R>);
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::audit::Audited;
+    use crate::mt::prelude::*;
+    use crate::retry::{Retries, Retry};
+    use crate::test_utils::*;
+    use futures::{StreamExt, TryStreamExt};
+
+    #[tokio::test]
+    async fn summarizes_runs() {
+        let mut blog = BlogClient::new(6);
+        blog.set_error(3);
+
+        let audited = Audited::new(blog);
+        let report = RunReport::new(audited.log());
+        let blog = Retry::new(audited, Retries::new(1));
+
+        let result = blog
+            .pages(GetContentRequest { page: 0 })
+            .with_summary(report.clone())
+            .try_collect::<Vec<_>>()
+            .await;
+        assert!(result.is_err());
+
+        let summary = report.summary().unwrap();
+        assert_eq!(summary.outcome, RunOutcome::Failed);
+        assert_eq!((summary.pages, summary.items), (3, 3));
+        assert_eq!((summary.retries, summary.wasted_requests), (1, 0));
+        assert_eq!(summary.next_request, Some(GetContentRequest { page: 3 }));
+
+        let audited = Audited::new(BlogClient::new(6));
+        let log = audited.log();
+        let report = RunReport::new(log.clone());
+
+        let stream = audited
+            .pages_ahead(4, Limit::None, GetContentRequest { page: 0 })
+            .with_summary(report.clone());
+        assert!(!report.is_finished());
+
+        let pages: Vec<_> = stream.take(2).try_collect().await.unwrap();
+        assert_eq!(pages.len(), 2);
+
+        let summary = report.summary().unwrap();
+        assert_eq!(summary.outcome, RunOutcome::Dropped);
+        assert_eq!(summary.pages, 2);
+        assert_eq!(summary.wasted_requests, log.summary().requests_issued() - 2);
+    }
+}
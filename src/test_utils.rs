@@ -31,7 +31,7 @@ fn clone(&self) -> Self {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct BlogRecord(pub usize);
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GetContentRequest {
     pub page: usize,
 }
@@ -217,6 +217,1397 @@ fn consumed_numbers_client() -> impl futures::stream::Stream<Item = Result<Vec<u
     };
 }
 
+macro_rules! pages_requests_test {
+    ($($mutability:tt)*) => {
+        async {
+            let $($mutability)* client = NumbersClient::new(10, 4);
+
+            let mut stream = client.pages(GetNumbersQuery::default());
+            assert_eq!(stream.peek_next_request().map(|req| req.key), Some(0));
+
+            let page = stream.try_next().await.unwrap();
+            assert_eq!(page, Some(vec![1, 2, 3, 4]));
+            assert_eq!(stream.peek_next_request().map(|req| req.key), Some(4));
+            assert_eq!(stream.into_next_request().map(|req| req.key), Some(4));
+
+            let mut stream = client.pages(GetNumbersQuery { key: 8 });
+            assert_eq!(stream.try_next().await.unwrap(), Some(vec![9, 10]));
+            assert!(stream.peek_next_request().is_none());
+            assert!(stream.try_next().await.unwrap().is_none());
+            assert!(stream.into_next_request().is_none());
+
+            let mut blog = BlogClient::new(5);
+            blog.set_error(2);
+
+            let mut stream = blog
+                .pages(GetContentRequest { page: 0 })
+                .track_current_request();
+
+            assert!(stream.current_request().is_none());
+            assert!(stream.try_next().await.is_ok());
+            assert!(stream.try_next().await.is_ok());
+            assert!(stream.try_next().await.is_err());
+            assert_eq!(stream.current_request().map(|req| req.page), Some(2));
+            assert!(stream.peek_next_request().is_none());
+            assert_eq!(stream.into_next_request().map(|req| req.page), Some(2));
+
+            let $($mutability)* blog = BlogClient::new(5);
+            let mut stream = blog.pages(GetContentRequest { page: 0 }).skip_pages(3);
+            assert_eq!(stream.peek_next_request().map(|req| req.page), Some(3));
+            assert_eq!(stream.try_next().await.unwrap(), Some(vec![BlogRecord(3)]));
+            let mut stream = stream.skip_pages(0);
+            let stream_id = stream.stream_id();
+            assert_eq!(stream.try_next().await.unwrap(), Some(vec![BlogRecord(4)]));
+            assert_eq!(stream.stream_id(), stream_id);
+
+            let $($mutability)* blog = BlogClient::new(5);
+            assert_ne!(blog.pages(GetContentRequest { page: 0 }).stream_id(), stream_id);
+        }
+    };
+}
+
+macro_rules! pages_split_test {
+    () => {
+        async {
+            let client = NumbersClient::new(30, 4);
+            let (pages, items) = client.pages(GetNumbersQuery::default()).split_items();
+
+            let (pages, items) =
+                tokio::join!(pages.try_collect::<Vec<_>>(), items.try_collect::<Vec<_>>());
+
+            let pages = pages.unwrap();
+            assert_eq!(pages.len(), 8);
+            assert_eq!(*pages[7], vec![29, 30]);
+            assert_eq!(items.unwrap(), (1..=30).collect::<Vec<_>>());
+
+            let (pages, items) = client.pages(GetNumbersQuery::default()).split_items();
+            drop(pages);
+            let items: Vec<_> = items.try_collect().await.unwrap();
+            assert_eq!(items.len(), 30);
+
+            let mut blog = BlogClient::new(5);
+            blog.set_error(3);
+
+            let (pages, items) = blog.pages(GetContentRequest { page: 0 }).split_items();
+            let items: Vec<_> = items.collect().await;
+            assert_eq!(items.len(), 4);
+            assert_eq!(items[3], Err("Custom error".to_owned()));
+
+            let pages: Vec<_> = pages.collect().await;
+            assert_eq!(pages.len(), 4);
+            assert_eq!(pages[3], Err("Custom error".to_owned()));
+        }
+    };
+}
+
+macro_rules! pages_infallible_test {
+    () => {
+        async {
+            let pages = || {
+                futures::stream::iter([
+                    Ok::<_, std::convert::Infallible>(vec![1, 2]),
+                    Ok(vec![]),
+                    Ok(vec![3]),
+                ])
+            };
+
+            let output: Vec<Vec<usize>> = pages().pages_infallible().collect().await;
+            assert_eq!(output, [vec![1, 2], vec![], vec![3]]);
+
+            let output: Vec<usize> = pages().items_infallible().collect().await;
+            assert_eq!(output, [1, 2, 3]);
+
+            let output: Vec<usize> = pages().collect_items().await;
+            assert_eq!(output, [1, 2, 3]);
+        }
+    };
+}
+
+macro_rules! pages_try_collect_items_test {
+    ($($mutability:tt)*) => {
+        async {
+            let $($mutability)* client = NumbersClient::new(10, 4);
+
+            let items: Vec<usize> = client
+                .pages(GetNumbersQuery::default())
+                .try_collect_items()
+                .await
+                .unwrap();
+            assert_eq!(items, (1..=10).collect::<Vec<_>>());
+
+            let items: Vec<usize> = client
+                .pages(GetNumbersQuery::default())
+                .try_collect_items_capped(5)
+                .await
+                .unwrap();
+            assert_eq!(items, [1, 2, 3, 4, 5]);
+
+            let items = client
+                .pages(GetNumbersQuery::default())
+                .try_collect_items_into(vec![0])
+                .await
+                .unwrap();
+            assert_eq!(items, (0..=10).collect::<Vec<_>>());
+
+            let mut blog = BlogClient::new(5);
+            blog.set_error(2);
+
+            let result = blog
+                .pages(GetContentRequest { page: 0 })
+                .try_collect_items::<Vec<_>>()
+                .await;
+            assert!(result.is_err());
+
+            let items = blog
+                .pages(GetContentRequest { page: 0 })
+                .try_collect_items_capped::<Vec<_>>(2)
+                .await
+                .unwrap();
+            assert_eq!(items.len(), 2);
+        }
+    };
+}
+
+macro_rules! pages_process_concurrently_test {
+    () => {
+        async {
+            let client = NumbersClient::new(30, 3);
+            let in_flight = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            let max_in_flight = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+            let summary = client
+                .pages(GetNumbersQuery::default())
+                .process_pages_concurrently(4, |numbers| {
+                    let in_flight = in_flight.clone();
+                    let max_in_flight = max_in_flight.clone();
+
+                    async move {
+                        use std::sync::atomic::Ordering;
+
+                        let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_in_flight.fetch_max(now, Ordering::SeqCst);
+                        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                        in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                        match numbers.iter().any(|n| n % 10 == 0) {
+                            true => Err(numbers[0]),
+                            false => Ok(()),
+                        }
+                    }
+                })
+                .await;
+
+            assert_eq!(summary.processed, 7);
+            let mut failed = summary.failed.clone();
+            failed.sort();
+            assert_eq!(failed, [10, 19, 28]);
+            assert_eq!(summary.page_error, None);
+            assert!(!summary.is_success());
+            assert_eq!(max_in_flight.load(std::sync::atomic::Ordering::SeqCst), 4);
+
+            let mut blog = BlogClient::new(5);
+            blog.set_error(2);
+
+            let summary = blog
+                .pages(GetContentRequest { page: 0 })
+                .process_pages_concurrently(2, |_| async { Ok::<_, ()>(()) })
+                .await;
+
+            assert_eq!(summary.processed, 2);
+            assert!(summary.failed.is_empty());
+            assert!(summary.page_error.is_some());
+        }
+    };
+}
+
+macro_rules! pages_boxed_err_test {
+    () => {
+        async {
+            let mut blog = BlogClient::new(5);
+            blog.set_error(2);
+
+            let io_pages = futures::stream::iter([
+                Ok(vec![BlogRecord(100)]),
+                Err(std::io::Error::other("connection reset")),
+            ]);
+
+            let results: Vec<_> = blog
+                .pages(GetContentRequest { page: 0 })
+                .pages_boxed_err()
+                .chain(io_pages.pages_boxed_err())
+                .collect()
+                .await;
+
+            let errors: Vec<_> = results
+                .iter()
+                .filter_map(|page| page.as_ref().err().map(|e| e.to_string()))
+                .collect();
+
+            assert_eq!(results.len(), 5);
+            assert_eq!(errors.len(), 2);
+            assert_eq!(errors[1], "connection reset");
+
+            let items: Vec<_> = BlogClient::new(3)
+                .pages(GetContentRequest { page: 0 })
+                .items_boxed_err()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(items, [BlogRecord(0), BlogRecord(1), BlogRecord(2)]);
+        }
+    };
+}
+
+macro_rules! pages_decode_test {
+    () => {
+        async {
+            let mut blog = BlogClient::new(6);
+            blog.set_error(4);
+
+            let results: Vec<_> = blog
+                .pages(GetContentRequest { page: 0 })
+                .decode_pages(3, |records| async move {
+                    // Earlier pages take longer to decode but they are still yielded in order
+                    let BlogRecord(num) = records[0];
+                    futures_timer::Delay::new(std::time::Duration::from_millis(
+                        10 * (4 - num as u64),
+                    ))
+                    .await;
+
+                    match num {
+                        2 => Err(format!("Failed to decode {num}")),
+                        _ => Ok(num.to_string()),
+                    }
+                })
+                .collect()
+                .await;
+
+            assert_eq!(
+                results,
+                [
+                    Ok("0".to_owned()),
+                    Ok("1".to_owned()),
+                    Err("Failed to decode 2".to_owned()),
+                ]
+            );
+
+            let mut blog = BlogClient::new(6);
+            blog.set_error(1);
+
+            let results: Vec<_> = blog
+                .pages(GetContentRequest { page: 0 })
+                .decode_pages(1, |records| async move { Ok(records.len()) })
+                .collect()
+                .await;
+
+            assert_eq!(results, [Ok(1), Err("Custom error".to_owned())]);
+        }
+    };
+}
+
+macro_rules! pages_inspect_test {
+    () => {
+        async {
+            let mut blog = BlogClient::new(5);
+            blog.set_error(3);
+
+            let mut seen_pages = Vec::new();
+            let mut seen_errors = Vec::new();
+
+            let results: Vec<_> = blog
+                .pages(GetContentRequest { page: 0 })
+                .inspect_page(|records| seen_pages.extend(records.iter().copied()))
+                .inspect_page_err(|e| seen_errors.push(e.clone()))
+                .collect()
+                .await;
+
+            assert_eq!(
+                results,
+                [
+                    Ok(vec![BlogRecord(0)]),
+                    Ok(vec![BlogRecord(1)]),
+                    Ok(vec![BlogRecord(2)]),
+                    Err("Custom error".to_owned()),
+                ]
+            );
+            assert_eq!(seen_pages, [BlogRecord(0), BlogRecord(1), BlogRecord(2)]);
+            assert_eq!(seen_errors, ["Custom error"]);
+        }
+    };
+}
+
+macro_rules! pages_only_new_items_test {
+    () => {
+        async {
+            use crate::watch::KeyOrder;
+
+            let mut client = NumbersClient::new(10, 4);
+            let mark = crate::watch::HighWaterMark::new();
+
+            let numbers: Vec<usize> = client
+                .pages(GetNumbersQuery::default())
+                .only_new_items(mark.clone(), KeyOrder::Ascending, |n| *n)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(numbers, (1..=10).collect::<Vec<_>>());
+            assert_eq!(mark.get(), Some(10));
+
+            client.numbers.extend(11..=13);
+            let numbers: Vec<usize> = client
+                .pages(GetNumbersQuery::default())
+                .only_new_items(mark.clone(), KeyOrder::Ascending, |n| *n)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(numbers, [11, 12, 13]);
+
+            // The same page is queried twice within one stream
+            let mark = crate::watch::HighWaterMark::starting_at(12);
+            let numbers: Vec<usize> =
+                futures::stream::iter([Ok::<_, ()>(vec![12, 13]), Ok(vec![12, 13]), Ok(vec![14])])
+                    .only_new_items(mark.clone(), KeyOrder::Ascending, |n| *n)
+                    .try_collect()
+                    .await
+                    .unwrap();
+            assert_eq!(numbers, [13, 14]);
+            assert_eq!(mark.get(), Some(14));
+
+            // A newest-first feed where the page boundary shifted between requests
+            let mark = crate::watch::HighWaterMark::starting_at(10);
+            let feed =
+                |pages: Vec<Vec<usize>>| futures::stream::iter(pages.into_iter().map(Ok::<_, ()>));
+            let numbers: Vec<usize> = feed(vec![vec![14, 13, 12], vec![12, 11, 10, 9]])
+                .only_new_items(mark.clone(), KeyOrder::Descending, |n| *n)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(numbers, [14, 13, 12, 11]);
+            assert_eq!(mark.get(), Some(14));
+
+            let numbers: Vec<usize> = feed(vec![vec![17, 16, 15], vec![14, 13, 12]])
+                .only_new_items(mark.clone(), KeyOrder::Descending, |n| *n)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(numbers, [17, 16, 15]);
+            assert_eq!(mark.get(), Some(17));
+        }
+    };
+}
+
+macro_rules! pages_from_requests_test {
+    () => {
+        async {
+            use crate::SchedulingMode;
+
+            let blog = BlogClient::new(20);
+            let requests = || [0, 3, 7, 10].map(|page| GetContentRequest { page });
+
+            let records: Vec<_> = blog
+                .pages_from_requests(requests(), 2, SchedulingMode::Ordered)
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, [0, 3, 7, 10].map(BlogRecord));
+
+            let mut records: Vec<_> = blog
+                .pages_from_requests(requests(), 2, SchedulingMode::Unordered)
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            records.sort_by_key(|record| record.0);
+            assert_eq!(records, [0, 3, 7, 10].map(BlogRecord));
+
+            for mode in [SchedulingMode::Ordered, SchedulingMode::Unordered] {
+                let mut records: Vec<_> = blog
+                    .pages_from_requests((15..).map(|page| GetContentRequest { page }), 3, mode)
+                    .items()
+                    .try_collect()
+                    .await
+                    .unwrap();
+                records.sort_by_key(|record| record.0);
+                assert_eq!(records, (15..20).map(BlogRecord).collect::<Vec<_>>());
+            }
+        }
+    };
+}
+
+macro_rules! pages_items_streamed_test {
+    () => {
+        async {
+            let blog = BlogClient::new(5);
+            let records: Vec<_> = blog
+                .pages(GetContentRequest { page: 0 })
+                .map_ok(|records| futures::stream::iter(records.into_iter().map(Ok::<_, String>)))
+                .items_streamed()
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap();
+            assert_eq!(records, (0..5).map(BlogRecord).collect::<Vec<_>>());
+
+            let results: Vec<Result<BlogRecord, String>> = blog
+                .pages(GetContentRequest { page: 0 })
+                .map_ok(|records| {
+                    let record = records.into_iter().next().unwrap();
+                    let decoded = match record.0 {
+                        2 => Err("Malformed record".to_owned()),
+                        _ => Ok(record),
+                    };
+
+                    futures::stream::iter([decoded])
+                })
+                .items_streamed()
+                .collect()
+                .await;
+            assert_eq!(results.len(), 5);
+            assert_eq!(results[2], Err("Malformed record".to_owned()));
+
+            let mut blog = BlogClient::new(5);
+            blog.set_error(1);
+            let results: Vec<Result<BlogRecord, String>> = blog
+                .pages(GetContentRequest { page: 0 })
+                .map_ok(|records| futures::stream::iter(records.into_iter().map(Ok::<_, String>)))
+                .items_streamed()
+                .collect()
+                .await;
+            assert_eq!(results, [Ok(BlogRecord(0)), Err("Custom error".to_owned())]);
+        }
+    };
+}
+
+macro_rules! pages_limit_items_test {
+    () => {
+        async {
+            let client = NumbersClient::new(48, 7);
+            let pages: Vec<_> = client
+                .pages(GetNumbersQuery::default())
+                .limit_items(10)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(pages, [(1..=7).collect::<Vec<_>>(), (8..=14).collect()]);
+
+            let pages: Vec<_> = client
+                .pages(GetNumbersQuery::default())
+                .limit_items(0)
+                .try_collect()
+                .await
+                .unwrap();
+            assert!(pages.is_empty());
+
+            let blog = crate::audit::Audited::new(BlogClient::new(20));
+            let log = blog.log();
+            let records: Vec<_> = blog
+                .pages_ahead(4, Limit::None, GetContentRequest { page: 0 })
+                .limit_items(6)
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, (0..6).map(BlogRecord).collect::<Vec<_>>());
+            assert!(log.summary().requests_issued() <= 8);
+        }
+    };
+}
+
+macro_rules! pages_resumable_test {
+    () => {
+        async {
+            let mut blog = BlogClient::new(6);
+            blog.set_error(4);
+
+            let mut pages = blog.pages_resumable(GetContentRequest { page: 0 });
+            assert_eq!(pages.peek_next_request().map(|req| req.page), Some(0));
+
+            let (request, records) = pages.try_next().await.unwrap().unwrap();
+            assert_eq!((request.page, records), (0, vec![BlogRecord(0)]));
+
+            let pages_and_requests: Vec<_> = pages.by_ref().take(3).try_collect().await.unwrap();
+            let requests: Vec<_> = pages_and_requests.iter().map(|(req, _)| req.page).collect();
+            assert_eq!(requests, [1, 2, 3]);
+            assert!(pages.try_next().await.is_err());
+            assert_eq!(pages.into_next_request().map(|req| req.page), Some(4));
+
+            let pages = blog.pages_resumable(GetContentRequest { page: 5 });
+            let pages: Vec<_> = pages.try_collect().await.unwrap();
+            assert_eq!(
+                pages,
+                [(GetContentRequest { page: 5 }, vec![BlogRecord(5)])]
+            );
+
+            let mut pages = blog.pages_resumable(GetContentRequest { page: 5 });
+            while pages.try_next().await.unwrap().is_some() {}
+            assert!(pages.into_next_request().is_none());
+        }
+    };
+}
+
+macro_rules! pages_ahead_batched_test {
+    () => {
+        async {
+            use std::sync::atomic::{AtomicUsize, Ordering};
+
+            struct Batched {
+                blog: BlogClient,
+                batches: AtomicUsize,
+            }
+
+            impl PageTurner<GetContentRequest> for Batched {
+                type PageItems = Vec<BlogRecord>;
+                type PageError = String;
+
+                async fn turn_page(
+                    &self,
+                    request: GetContentRequest,
+                ) -> TurnedPageResult<Self, GetContentRequest> {
+                    self.blog.turn_page(request).await
+                }
+
+                async fn turn_pages(
+                    &self,
+                    requests: Vec<GetContentRequest>,
+                ) -> Vec<TurnedPageResult<Self, GetContentRequest>> {
+                    self.batches.fetch_add(1, Ordering::Relaxed);
+
+                    let mut pages = Vec::with_capacity(requests.len());
+                    for request in requests {
+                        pages.push(self.blog.turn_page(request).await);
+                    }
+
+                    pages
+                }
+            }
+
+            let batched = Batched {
+                blog: BlogClient::new(10),
+                batches: AtomicUsize::new(0),
+            };
+
+            let records: Vec<_> = batched
+                .pages_ahead_batched(4, Limit::None, GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, (0..10).map(BlogRecord).collect::<Vec<_>>());
+            assert_eq!(batched.batches.load(Ordering::Relaxed), 3);
+
+            let records: Vec<_> = batched
+                .pages_ahead_batched(4, Limit::Pages(5), GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, (0..5).map(BlogRecord).collect::<Vec<_>>());
+            assert_eq!(batched.batches.load(Ordering::Relaxed), 5);
+
+            let mut blog = BlogClient::new(10);
+            blog.set_error(5);
+            let results: Vec<_> = blog
+                .pages_ahead_batched(4, Limit::None, GetContentRequest { page: 0 })
+                .collect()
+                .await;
+            assert_eq!(results.len(), 6);
+            assert_eq!(results[5], Err("Custom error".to_owned()));
+        }
+    };
+}
+
+macro_rules! pages_ahead_throttled_test {
+    () => {
+        async {
+            use crate::throttle::{Rate, Throttled};
+            use std::time::{Duration, Instant};
+
+            let rate = Rate::new(2, Duration::from_millis(30));
+            let blog = BlogClient::new(6);
+
+            let start = Instant::now();
+            let records: Vec<_> = blog
+                .pages_ahead_throttled(4, Limit::None, rate, GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, (0..6).map(BlogRecord).collect::<Vec<_>>());
+            // 6 pages need at least 3 windows
+            assert!(start.elapsed() >= Duration::from_millis(60));
+
+            let throttled = Throttled::new(&blog, rate);
+            let start = Instant::now();
+            let first: Vec<_> = throttled
+                .pages(GetContentRequest { page: 0 })
+                .take(2)
+                .try_collect()
+                .await
+                .unwrap();
+            let second: Vec<_> = throttled
+                .pages(GetContentRequest { page: 2 })
+                .take(1)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(first.len() + second.len(), 3);
+            assert!(
+                start.elapsed() >= Duration::from_millis(30),
+                "Streams of the same throttled page turner share the rate"
+            );
+        }
+    };
+}
+
+macro_rules! pages_with_progress_handle_test {
+    () => {
+        async {
+            use crate::progress::ProgressHandle;
+
+            struct Watched {
+                blog: BlogClient,
+                progress: ProgressHandle<GetContentRequest>,
+            }
+
+            impl PageTurner<GetContentRequest> for Watched {
+                type PageItems = Vec<BlogRecord>;
+                type PageError = String;
+
+                async fn turn_page(
+                    &self,
+                    request: GetContentRequest,
+                ) -> TurnedPageResult<Self, GetContentRequest> {
+                    assert_eq!(self.progress.current_request(), Some(request.clone()));
+                    self.blog.turn_page(request).await
+                }
+            }
+
+            let progress = ProgressHandle::new();
+            let watched = Watched {
+                blog: BlogClient::new(4),
+                progress: progress.clone(),
+            };
+
+            let records: Vec<_> = watched
+                .pages_with_progress(GetContentRequest { page: 0 }, progress.clone())
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records.len(), 4);
+            assert_eq!((progress.pages(), progress.items()), (4, 4));
+            assert_eq!(progress.errors(), 0);
+            assert_eq!(progress.current_request(), None);
+            assert!(progress.is_finished());
+
+            let mut blog = BlogClient::new(6);
+            blog.set_error(2);
+
+            let progress = ProgressHandle::new();
+            let result: Result<Vec<_>, _> = blog
+                .pages_with_progress(GetContentRequest { page: 0 }, progress.clone())
+                .try_collect()
+                .await;
+            assert_eq!(result, Err("Custom error".to_owned()));
+            assert_eq!((progress.pages(), progress.errors()), (2, 1));
+            assert!(!progress.is_finished());
+        }
+    };
+}
+
+macro_rules! turner_fn_test {
+    () => {
+        async {
+            let blog = BlogClient::new(5);
+            let page_turner = turner_fn(|request: GetContentRequest| blog.turn_page(request));
+
+            let records: Vec<_> = page_turner
+                .pages_ahead(2, Limit::None, GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, (0..5).map(BlogRecord).collect::<Vec<_>>());
+
+            let page_turner = turner_fn(|page: usize| async move {
+                match page {
+                    0..=2 => Ok(TurnedPage::next(vec![page], page + 1)),
+                    3 => Ok(TurnedPage::last(vec![page])),
+                    _ => Err(page),
+                }
+            });
+
+            let items: Vec<_> = page_turner.pages(1).items().try_collect().await.unwrap();
+            assert_eq!(items, [1, 2, 3]);
+
+            let result: Result<Vec<_>, _> = page_turner.pages(7).try_collect().await;
+            assert_eq!(result, Err(7));
+        }
+    };
+}
+
+macro_rules! pages_bidirectional_test {
+    () => {
+        async {
+            use crate::bidirectional::WithPrev;
+
+            /// Messages `0..total` in pages of 3, the request is the first message of a page
+            struct Chat {
+                total: usize,
+                broken: usize,
+            }
+
+            impl PageTurner<usize> for Chat {
+                type PageItems = WithPrev<Vec<usize>, usize>;
+                type PageError = String;
+
+                async fn turn_page(&self, first: usize) -> TurnedPageResult<Self, usize> {
+                    if first == self.broken {
+                        return Err(format!("Page {first} is broken"));
+                    }
+
+                    let messages = (first..self.total.min(first + 3)).collect();
+                    let next = (first + 3 < self.total).then_some(first + 3);
+                    Ok(TurnedPage::with_prev(messages, next, first.checked_sub(3)))
+                }
+            }
+
+            let chat = Chat {
+                total: 11,
+                broken: 100,
+            };
+
+            let messages: Vec<_> = chat.pages_backward(9).items().try_collect().await.unwrap();
+            assert_eq!(messages, [9, 10, 6, 7, 8, 3, 4, 5, 0, 1, 2]);
+
+            let messages: Vec<_> = chat.pages(3).items().try_collect().await.unwrap();
+            assert_eq!(messages, (3..11).collect::<Vec<_>>());
+
+            let mut pages = chat.pages_bidirectional(3);
+            assert_eq!(
+                (pages.peek_next_request(), pages.peek_prev_request()),
+                (Some(&3), Some(&3))
+            );
+            assert_eq!(pages.prev_page().await, Some(Ok(vec![3, 4, 5])));
+            assert_eq!(pages.next_page().await, Some(Ok(vec![6, 7, 8])));
+            assert_eq!(pages.prev_page().await, Some(Ok(vec![0, 1, 2])));
+            assert_eq!(pages.prev_page().await, None);
+            assert_eq!(pages.next_page().await, Some(Ok(vec![9, 10])));
+            assert_eq!(pages.next_page().await, None);
+
+            let chat = Chat {
+                total: 11,
+                broken: 0,
+            };
+
+            let mut pages = chat.pages_bidirectional(6);
+            assert_eq!(pages.next_page().await, Some(Ok(vec![6, 7, 8])));
+            assert_eq!(pages.prev_page().await, Some(Ok(vec![3, 4, 5])));
+            assert_eq!(
+                pages.prev_page().await,
+                Some(Err("Page 0 is broken".to_owned()))
+            );
+            assert_eq!(
+                pages.peek_prev_request(),
+                Some(&0),
+                "Failed requests are kept"
+            );
+            assert_eq!(pages.next_page().await, Some(Ok(vec![9, 10])));
+        }
+    };
+}
+
+macro_rules! page_turner_combinators_test {
+    () => {
+        async {
+            let mut blog = BlogClient::new(6);
+            blog.set_error(4);
+
+            let ids = (&blog)
+                .map_items(|records: Vec<BlogRecord>| {
+                    records
+                        .into_iter()
+                        .map(|BlogRecord(id)| id)
+                        .collect::<Vec<_>>()
+                })
+                .map_err(|e: String| e.len());
+
+            let result: Result<Vec<_>, _> = ids
+                .pages_ahead(2, Limit::None, GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await;
+            assert_eq!(result, Err("Custom error".len()));
+
+            let items: Vec<_> = ids
+                .pages_ahead(2, Limit::Pages(4), GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(items, [0, 1, 2, 3]);
+
+            let odd = (&blog).and_then_page(|records: Vec<BlogRecord>| match records[..] {
+                [BlogRecord(id)] if id % 2 == 1 => Ok(id),
+                _ => Err(format!("{records:?} isn't odd")),
+            });
+
+            let result: Result<Vec<_>, _> =
+                odd.pages(GetContentRequest { page: 1 }).try_collect().await;
+            assert_eq!(result, Err("[BlogRecord(2)] isn't odd".to_owned()));
+
+            let mut mirror = BlogClient::new(6);
+            mirror.set_error(5);
+
+            let records: Vec<_> = (&blog)
+                .or_else(&mirror)
+                .pages(GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, (0..6).map(BlogRecord).collect::<Vec<_>>());
+
+            mirror.set_error(4);
+            let result: Result<Vec<_>, _> = (&blog)
+                .or_else(&mirror)
+                .pages(GetContentRequest { page: 0 })
+                .try_collect()
+                .await;
+            assert_eq!(result, Err("Custom error".to_owned()));
+        }
+    };
+}
+
+macro_rules! pages_chain_test {
+    () => {
+        async {
+            use crate::chain::ChainSummary;
+
+            #[derive(Clone)]
+            struct ListProjects(usize);
+            struct ListIssues(usize);
+
+            /// 3 pages of 2 projects, then a page of issues for each project
+            struct Tracker;
+
+            impl PageTurner<ListProjects> for Tracker {
+                type PageItems = Vec<String>;
+                type PageError = String;
+
+                async fn turn_page(
+                    &self,
+                    ListProjects(page): ListProjects,
+                ) -> TurnedPageResult<Self, ListProjects> {
+                    let projects = vec![format!("p{}", page * 2), format!("p{}", page * 2 + 1)];
+                    Ok(TurnedPage::new(
+                        projects,
+                        (page < 2).then_some(ListProjects(page + 1)),
+                    ))
+                }
+            }
+
+            impl PageTurner<ListIssues> for Tracker {
+                type PageItems = Vec<String>;
+                type PageError = String;
+
+                async fn turn_page(
+                    &self,
+                    ListIssues(project): ListIssues,
+                ) -> TurnedPageResult<Self, ListIssues> {
+                    match project {
+                        0..=5 => Ok(TurnedPage::new(
+                            vec![format!("p{project}#1")],
+                            (project < 5).then_some(ListIssues(project + 1)),
+                        )),
+                        _ => Err(format!("Project {project} doesn't exist")),
+                    }
+                }
+            }
+
+            let items: Vec<_> = Tracker
+                .pages_chain(ListProjects(1), |summary: ChainSummary<ListProjects>| {
+                    assert_eq!((summary.pages, summary.items), (2, 4));
+                    assert_eq!(summary.last_request.0, 2);
+                    Some(ListIssues(4))
+                })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(items, ["p2", "p3", "p4", "p5", "p4#1", "p5#1"]);
+
+            let pages: Vec<_> = Tracker
+                .pages_chain(ListProjects(2), |_| None::<ListIssues>)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(pages, [vec!["p4", "p5"]]);
+
+            let result: Result<Vec<_>, _> = Tracker
+                .pages_chain(ListProjects(2), |_| Some(ListIssues(6)))
+                .try_collect()
+                .await;
+            assert_eq!(result, Err("Project 6 doesn't exist".to_owned()));
+        }
+    };
+}
+
+macro_rules! pages_map_ahead_test {
+    () => {
+        async {
+            let mut blog = BlogClient::new(10);
+
+            // Earlier pages take longer to process but outputs keep the order of pages
+            let outputs: Vec<_> = blog
+                .pages_map_ahead(
+                    3,
+                    Limit::None,
+                    GetContentRequest { page: 0 },
+                    |records| async move {
+                        let BlogRecord(id) = records[0];
+                        tokio::time::sleep(std::time::Duration::from_millis(10 - id as u64)).await;
+                        Ok(id * 10)
+                    },
+                )
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(outputs, (0..10).map(|id| id * 10).collect::<Vec<_>>());
+
+            let result: Result<Vec<_>, _> = blog
+                .pages_map_ahead(
+                    2,
+                    Limit::None,
+                    GetContentRequest { page: 0 },
+                    |records| async move {
+                        match records[0] {
+                            BlogRecord(4) => Err("Can't process record 4".to_owned()),
+                            record => Ok(record),
+                        }
+                    },
+                )
+                .try_collect()
+                .await;
+            assert_eq!(result, Err("Can't process record 4".to_owned()));
+
+            blog.set_error(2);
+            let result: Result<Vec<_>, _> = blog
+                .pages_map_ahead(
+                    2,
+                    Limit::None,
+                    GetContentRequest { page: 0 },
+                    |records| async { Ok(records) },
+                )
+                .try_collect()
+                .await;
+            assert_eq!(result, Err("Custom error".to_owned()));
+        }
+    };
+}
+
+macro_rules! pages_while_test {
+    () => {
+        async {
+            let mut blog = BlogClient::new(10);
+
+            // The page that fails the predicate is still yielded
+            let records: Vec<_> = blog
+                .pages_while(GetContentRequest { page: 0 }, |records| records[0].0 < 3)
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, (0..4).map(BlogRecord).collect::<Vec<_>>());
+
+            let records: Vec<_> = blog
+                .pages_ahead_while(3, Limit::None, GetContentRequest { page: 0 }, |records| {
+                    records[0].0 < 3
+                })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, (0..4).map(BlogRecord).collect::<Vec<_>>());
+
+            let records: Vec<_> = blog
+                .pages_while(GetContentRequest { page: 0 }, |_| true)
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records.len(), 10);
+
+            blog.set_error(2);
+            let result: Result<Vec<_>, _> = blog
+                .pages_ahead_while(3, Limit::None, GetContentRequest { page: 0 }, |_| true)
+                .try_collect()
+                .await;
+            assert_eq!(result, Err("Custom error".to_owned()));
+
+            let records: Vec<_> = blog
+                .pages_ahead_while(3, Limit::None, GetContentRequest { page: 0 }, |records| {
+                    records[0].0 < 1
+                })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, [BlogRecord(0), BlogRecord(1)]);
+        }
+    };
+}
+
+macro_rules! pages_with_timeout_test {
+    () => {
+        async {
+            use crate::timeout::TimeoutError;
+            use std::time::Duration;
+
+            struct Hanging {
+                blog: BlogClient,
+                hanging_page: usize,
+            }
+
+            impl PageTurner<GetContentRequest> for Hanging {
+                type PageItems = Vec<BlogRecord>;
+                type PageError = String;
+
+                async fn turn_page(
+                    &self,
+                    request: GetContentRequest,
+                ) -> TurnedPageResult<Self, GetContentRequest> {
+                    if request.page == self.hanging_page {
+                        std::future::pending::<()>().await;
+                    }
+
+                    self.blog.turn_page(request).await
+                }
+            }
+
+            let timeout = Duration::from_millis(20);
+            let hanging = Hanging {
+                blog: BlogClient::new(6),
+                hanging_page: 3,
+            };
+
+            let result: Result<Vec<_>, _> = hanging
+                .pages_with_timeout(timeout, GetContentRequest { page: 0 })
+                .try_collect()
+                .await;
+            assert_eq!(result, Err(TimeoutError::Timeout(timeout)));
+
+            let records: Vec<_> = hanging
+                .pages_ahead_with_timeout(
+                    4,
+                    Limit::Pages(3),
+                    timeout,
+                    GetContentRequest { page: 0 },
+                )
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, (0..3).map(BlogRecord).collect::<Vec<_>>());
+
+            let results: Vec<_> = hanging
+                .pages_ahead_with_timeout(4, Limit::None, timeout, GetContentRequest { page: 0 })
+                .collect()
+                .await;
+            assert_eq!(results.len(), 4);
+            assert_eq!(results[3], Err(TimeoutError::Timeout(timeout)));
+
+            let mut blog = BlogClient::new(6);
+            blog.set_error(1);
+            let result: Result<Vec<_>, _> = blog
+                .pages_with_timeout(timeout, GetContentRequest { page: 0 })
+                .try_collect()
+                .await;
+            assert_eq!(result, Err(TimeoutError::Page("Custom error".to_owned())));
+        }
+    };
+}
+
+macro_rules! pages_with_retry_test {
+    () => {
+        async {
+            let policy_calls = std::sync::atomic::AtomicUsize::new(0);
+            let policy = |_: &String, retry: usize| {
+                policy_calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                (retry <= 2).then_some(std::time::Duration::ZERO)
+            };
+
+            let blog = BlogClient::new(6);
+            let records: Vec<_> = blog
+                .pages_with_retry(policy, GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, (0..6).map(BlogRecord).collect::<Vec<_>>());
+            assert_eq!(policy_calls.load(std::sync::atomic::Ordering::Relaxed), 0);
+
+            let mut blog = BlogClient::new(6);
+            blog.set_error(3);
+            let result: Result<Vec<_>, _> = blog
+                .pages_with_retry(policy, GetContentRequest { page: 0 })
+                .try_collect()
+                .await;
+            assert_eq!(result, Err("Custom error".to_owned()));
+            assert_eq!(policy_calls.load(std::sync::atomic::Ordering::Relaxed), 3);
+
+            let records: Vec<_> = blog
+                .pages_ahead_with_retry(3, Limit::Pages(3), policy, GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, (0..3).map(BlogRecord).collect::<Vec<_>>());
+
+            let result: Result<Vec<_>, _> = blog
+                .pages_ahead_with_retry(3, Limit::None, policy, GetContentRequest { page: 0 })
+                .try_collect()
+                .await;
+            assert_eq!(result, Err("Custom error".to_owned()));
+        }
+    };
+}
+
+macro_rules! pages_with_state_test {
+    () => {
+        async {
+            // Skips every other page by building requests from the amount of seen records
+            struct SkipOdd {
+                seen: usize,
+            }
+
+            impl crate::PaginationState<GetContentRequest, Vec<BlogRecord>> for SkipOdd {
+                fn next_request(
+                    &mut self,
+                    records: &Vec<BlogRecord>,
+                    next_request: Option<GetContentRequest>,
+                ) -> Option<GetContentRequest> {
+                    self.seen += records.len();
+                    next_request.map(|_| GetContentRequest {
+                        page: self.seen * 2,
+                    })
+                }
+            }
+
+            let blog = BlogClient::new(9);
+            let records: Vec<_> = blog
+                .pages_with_state(GetContentRequest { page: 0 }, SkipOdd { seen: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+
+            assert_eq!(records, [0, 2, 4, 6, 8].map(BlogRecord));
+
+            let mut blog = BlogClient::new(9);
+            blog.set_error(4);
+            let result: Result<Vec<_>, _> = blog
+                .pages_with_state(GetContentRequest { page: 0 }, SkipOdd { seen: 0 })
+                .try_collect()
+                .await;
+            assert_eq!(result, Err("Custom error".to_owned()));
+        }
+    };
+}
+
+macro_rules! pages_tail_durable_test {
+    () => {
+        async {
+            use crate::watch::KeyOrder;
+
+            let interval = std::time::Duration::from_millis(5);
+            let mut client = NumbersClient::new(10, 4);
+            let store = crate::watch::MemoryCursorStore::new();
+
+            let pages: Vec<Vec<usize>> = client
+                .pages_tail_durable(store.clone(), GetNumbersQuery::default(), interval)
+                .take(4)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(
+                pages,
+                [vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 10], vec![9, 10]]
+            );
+
+            let store = crate::watch::MemoryCursorStore::new();
+            let numbers: Vec<usize> = client
+                .pages_tail_durable(store.clone(), GetNumbersQuery::default(), interval)
+                .items()
+                .take(10)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(numbers, (1..=10).collect::<Vec<_>>());
+            // The last page wasn't polled past its items so it's not marked as processed
+            assert_eq!(store.get().map(|req| req.key), Some(8));
+
+            // Restart from the saved cursor
+            client.numbers.extend(11..=13);
+            let numbers: Vec<usize> = client
+                .pages_tail_durable(store.clone(), GetNumbersQuery::default(), interval)
+                .only_new_items(
+                    crate::watch::HighWaterMark::starting_at(10),
+                    KeyOrder::Ascending,
+                    |n| *n,
+                )
+                .take(3)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(numbers, [11, 12, 13]);
+            assert_eq!(store.get().map(|req| req.key), Some(12));
+
+            // The unchanged last page is polled many times but its items are emitted once
+            let store = crate::watch::MemoryCursorStore::new();
+            let numbers: Vec<usize> = client
+                .pages_tail_durable(store, GetNumbersQuery::default(), interval)
+                .only_new_items(
+                    crate::watch::HighWaterMark::new(),
+                    KeyOrder::Ascending,
+                    |n| *n,
+                )
+                .take_until(tokio::time::sleep(interval * 10))
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(numbers, (1..=13).collect::<Vec<_>>());
+        }
+    };
+}
+
+macro_rules! pages_lazy_test {
+    () => {
+        async {
+            use std::sync::atomic::{AtomicUsize, Ordering};
+
+            let client = NumbersClient::new(10, 4);
+            let decoded = std::sync::Arc::new(AtomicUsize::new(0));
+
+            let decode = {
+                let decoded = decoded.clone();
+                move |n: usize| {
+                    decoded.fetch_add(1, Ordering::Relaxed);
+                    n.to_string()
+                }
+            };
+
+            let mut pages = Box::pin(client.pages(GetNumbersQuery::default()).lazy_pages(decode));
+            let mut firsts = Vec::new();
+            while let Some(page) = pages.try_next().await.unwrap() {
+                firsts.push(Box::pin(page).next().await.unwrap());
+            }
+
+            assert_eq!(firsts, ["1", "5", "9"]);
+            assert_eq!(decoded.load(Ordering::Relaxed), 3);
+        }
+    };
+}
+
+macro_rules! into_pages_ahead_arc_test {
+    () => {
+        async {
+            /// A client that can't be cloned, i.e. because it owns a connection
+            struct Unique(BlogClient);
+
+            impl PageTurner<GetContentRequest> for Unique {
+                type PageItems = Vec<BlogRecord>;
+                type PageError = String;
+
+                async fn turn_page(
+                    &self,
+                    request: GetContentRequest,
+                ) -> TurnedPageResult<Self, GetContentRequest> {
+                    self.0.turn_page(request).await
+                }
+            }
+
+            let expected: Vec<_> = (0..5).map(BlogRecord).collect();
+
+            let records: Vec<_> = Unique(BlogClient::new(5))
+                .into_pages_ahead_arc(3, Limit::None, GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, expected);
+
+            let mut records: Vec<_> = Unique(BlogClient::new(5))
+                .into_pages_ahead_unordered_arc(3, Limit::Pages(5), GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            records.sort_by_key(|record| record.0);
+            assert_eq!(records, expected);
+        }
+    };
+}
+
+macro_rules! arc_pages_test {
+    () => {
+        async {
+            fn assert_static<S: 'static>(stream: S) -> S {
+                stream
+            }
+
+            let blog = std::sync::Arc::new(BlogClient::new(5));
+            let request = GetContentRequest { page: 0 };
+
+            let pages = assert_static(blog.arc_pages(request.clone()));
+            let pages_ahead = assert_static(blog.arc_pages_ahead(2, Limit::None, request.clone()));
+            let pages_ahead_unordered =
+                assert_static(blog.arc_pages_ahead_unordered(2, Limit::Pages(5), request));
+            assert_eq!(std::sync::Arc::strong_count(&blog), 4);
+
+            let expected: Vec<_> = (0..5).map(BlogRecord).collect();
+
+            let records: Vec<_> = pages.items().try_collect().await.unwrap();
+            assert_eq!(records, expected);
+
+            let records: Vec<_> = pages_ahead.items().try_collect().await.unwrap();
+            assert_eq!(records, expected);
+
+            let mut records: Vec<_> = pages_ahead_unordered.items().try_collect().await.unwrap();
+            records.sort_by_key(|record| record.0);
+            assert_eq!(records, expected);
+
+            assert_eq!(std::sync::Arc::strong_count(&blog), 1);
+        }
+    };
+}
+
+macro_rules! boxed_pages_test {
+    () => {
+        async {
+            fn records(
+                blog: &BlogClient,
+                ahead: bool,
+            ) -> BoxPagesStream<'_, Vec<BlogRecord>, String> {
+                let request = GetContentRequest { page: 0 };
+
+                match ahead {
+                    true => blog.pages_ahead(3, Limit::None, request).boxed_pages(),
+                    false => blog.pages(request).boxed_pages(),
+                }
+            }
+
+            let blog = BlogClient::new(7);
+            let expected: Vec<_> = (0..7).map(BlogRecord).collect();
+
+            for ahead in [false, true] {
+                let output: Vec<_> = records(&blog, ahead).items().try_collect().await.unwrap();
+                assert_eq!(output, expected);
+            }
+        }
+    };
+}
+
 macro_rules! blogs_client_page_turner_impl {
     (@types) => {
         type PageItems = Vec<BlogRecord>;
@@ -437,6 +1828,344 @@ macro_rules! blogs_client_pages_ahead_unordered_base_test {
     };
 }
 
+macro_rules! blogs_client_overquery_stats_test {
+    () => {
+        async {
+            let blog = BlogClient::new(5);
+
+            let stream = blog.pages_ahead_unordered(4, Limit::None, GetContentRequest { page: 0 });
+            let stats = stream.stats();
+
+            let results: Vec<_> = stream.items().try_collect().await.unwrap();
+            assert_eq!(results.len(), 5);
+
+            // Every request past the last page fails with an out of bounds error
+            assert!(stats.requests_sent() > 5);
+            assert_eq!(stats.wasted_requests(), stats.requests_sent() - 5);
+            assert_eq!(stats.discarded_errors(), stats.wasted_requests());
+
+            let stream =
+                blog.pages_ahead_unordered(4, Limit::Pages(5), GetContentRequest { page: 0 });
+            let stats = stream.stats();
+
+            let results: Vec<_> = stream.items().try_collect().await.unwrap();
+            assert_eq!(results.len(), 5);
+            assert_eq!(stats.requests_sent(), 5);
+            assert_eq!(stats.wasted_requests(), 0);
+            assert_eq!(stats.discarded_errors(), 0);
+        }
+    };
+}
+
+macro_rules! blogs_client_pagination_equivalent_test {
+    () => {
+        async {
+            let blog = BlogClient::new(17);
+
+            testing::assert_pagination_equivalent(
+                &blog,
+                GetContentRequest { page: 0 },
+                4,
+                Limit::None,
+            )
+            .await;
+
+            testing::assert_pagination_equivalent(
+                &blog,
+                GetContentRequest { page: 3 },
+                4,
+                Limit::Pages(5),
+            )
+            .await;
+        }
+    };
+}
+
+macro_rules! blogs_client_indexed_unordered_test {
+    () => {
+        async {
+            let blog = BlogClient::new(20);
+
+            let mut pages: Vec<_> = blog
+                .pages_ahead_unordered(3, Limit::Pages(6), GetContentRequest { page: 2 })
+                .indexed()
+                .try_collect()
+                .await
+                .unwrap();
+
+            pages.sort_by_key(|(ix, _)| *ix);
+
+            let expected: Vec<_> = (0..6).map(|ix| (ix, vec![BlogRecord(ix + 2)])).collect();
+            assert_eq!(pages, expected);
+
+            let mut pages: Vec<_> = blog
+                .pages_ahead_unordered_indexed(3, Limit::None, GetContentRequest { page: 15 })
+                .try_collect()
+                .await
+                .unwrap();
+
+            pages.sort_by_key(|(ix, _)| *ix);
+
+            let expected: Vec<_> = (0..5).map(|ix| (ix, vec![BlogRecord(ix + 15)])).collect();
+            assert_eq!(pages, expected);
+        }
+    };
+}
+
+macro_rules! pages_builder_retry_timeout_test {
+    () => {
+        async {
+            let mut broken_blog = BlogClient::new(100);
+            broken_blog.set_error(95);
+
+            let builder = PagesBuilder::new(&broken_blog)
+                .ahead(4)
+                .start_at_page(90)
+                .timeout(std::time::Duration::from_secs(10))
+                .retry(crate::retry::Retries::new(2));
+
+            let records: Vec<_> = builder
+                .clone()
+                .limit(Limit::Pages(5))
+                .build(GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(records, (90..95).map(BlogRecord).collect::<Vec<_>>());
+
+            let result: Result<Vec<_>, _> = builder
+                .build(GetContentRequest { page: 0 })
+                .try_collect()
+                .await;
+            assert_eq!(
+                result,
+                Err(crate::timeout::TimeoutError::Page(
+                    "Custom error".to_owned()
+                ))
+            );
+        }
+    };
+}
+
+macro_rules! blogs_client_pages_builder_test {
+    () => {
+        async {
+            let blog = BlogClient::new(100);
+
+            let records: Vec<_> = PagesBuilder::new(&blog)
+                .start_at_page(50)
+                .limit(Limit::Pages(25))
+                .build(GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+
+            assert_eq!(records, (50..75).map(BlogRecord).collect::<Vec<_>>());
+
+            let records: Vec<_> = PagesBuilder::new(&blog)
+                .ahead(8)
+                .start_at_page(50)
+                .limit(Limit::Pages(25))
+                .build(GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+
+            assert_eq!(records, (50..75).map(BlogRecord).collect::<Vec<_>>());
+
+            let mut records: Vec<_> = PagesBuilder::new(&blog)
+                .ahead(8)
+                .unordered()
+                .start_at_page(90)
+                .build(GetContentRequest { page: 0 })
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+
+            records.sort_by_key(|record| record.0);
+            assert_eq!(records, (90..100).map(BlogRecord).collect::<Vec<_>>());
+
+            let head_and_tail = PagesBuilder::new(&blog)
+                .ahead(4)
+                .start_at_page(10)
+                .build_head_and_tail(GetContentRequest { page: 0 }, 19)
+                .await
+                .unwrap();
+
+            assert_eq!(head_and_tail.head, vec![BlogRecord(10)]);
+            assert_eq!(head_and_tail.tail, Some(vec![BlogRecord(19)]));
+
+            let middle: Vec<_> = head_and_tail.middle.items().try_collect().await.unwrap();
+            assert_eq!(middle, (11..19).map(BlogRecord).collect::<Vec<_>>());
+
+            for last_page in [0, 1] {
+                let head_and_tail = PagesBuilder::new(&blog)
+                    .ahead(4)
+                    .build_head_and_tail(GetContentRequest { page: 0 }, last_page)
+                    .await
+                    .unwrap();
+
+                assert_eq!(head_and_tail.head, vec![BlogRecord(0)]);
+                assert_eq!(head_and_tail.tail.is_some(), last_page == 1);
+                let middle: Vec<_> = head_and_tail.middle.items().try_collect().await.unwrap();
+                assert!(middle.is_empty());
+            }
+
+            let records: Vec<_> = PagesBuilder::new(&blog)
+                .start_at_page(20)
+                .limit(Limit::Pages(3))
+                .build_prioritized(GetContentRequest { page: 0 }, 10, |req| req.page)
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+
+            assert_eq!(records, (20..30).rev().map(BlogRecord).collect::<Vec<_>>());
+
+            let blog = BlogClient::new(25);
+            let mut records: Vec<_> = PagesBuilder::new(&blog)
+                .ahead(3)
+                .start_at_page(15)
+                .build_prioritized(GetContentRequest { page: 0 }, 20, |req| req.page)
+                .items()
+                .try_collect()
+                .await
+                .unwrap();
+
+            records.sort_by_key(|record| record.0);
+            assert_eq!(records, (15..25).map(BlogRecord).collect::<Vec<_>>());
+        }
+    };
+}
+
+macro_rules! blogs_client_request_injector_test {
+    () => {
+        async {
+            let blog = BlogClient::new(30);
+            let injector = crate::inject::RequestInjector::new();
+
+            let mut stream = std::pin::pin!(PagesBuilder::new(&blog)
+                .ahead(2)
+                .limit(Limit::Pages(5))
+                .build_with_injector(GetContentRequest { page: 0 }, injector.clone()));
+
+            let mut records = Vec::new();
+            while let Some(page) = stream.try_next().await.unwrap() {
+                if page.contains(&BlogRecord(2)) {
+                    injector.push(GetContentRequest { page: 25 });
+                }
+
+                records.extend(page);
+            }
+
+            records.sort_by_key(|record| record.0);
+            assert_eq!(
+                records,
+                (0..5).chain(25..30).map(BlogRecord).collect::<Vec<_>>()
+            );
+            assert!(injector.is_empty());
+
+            let mut blog = BlogClient::new(30);
+            blog.set_error(26);
+            let injector = crate::inject::RequestInjector::new();
+            injector.push(GetContentRequest { page: 25 });
+
+            let result: Result<Vec<_>, _> = PagesBuilder::new(&blog)
+                .build_with_injector(GetContentRequest { page: 0 }, injector)
+                .try_collect()
+                .await;
+            assert_eq!(result, Err("Custom error".to_owned()));
+        }
+    };
+}
+
+macro_rules! pages_with_progress_test {
+    () => {
+        async {
+            let blog = BlogClient::new(4);
+
+            let progress: Vec<_> = blog
+                .pages(GetContentRequest { page: 0 })
+                .with_progress(Total::Pages(4))
+                .map_ok(|(_, progress)| (progress.done, progress.fraction))
+                .try_collect()
+                .await
+                .unwrap();
+
+            assert_eq!(progress, [(1, 0.25), (2, 0.5), (3, 0.75), (4, 1.0)]);
+
+            let numbers = NumbersClient::new(10, 4);
+            let progress: Vec<_> = numbers
+                .pages(GetNumbersQuery::default())
+                .with_progress(Total::Items(8))
+                .map_ok(|(items, progress)| (items.len(), progress))
+                .try_collect()
+                .await
+                .unwrap();
+
+            assert_eq!(
+                progress,
+                [
+                    (
+                        4,
+                        Progress {
+                            done: 4,
+                            total: 8,
+                            fraction: 0.5
+                        }
+                    ),
+                    (
+                        4,
+                        Progress {
+                            done: 8,
+                            total: 8,
+                            fraction: 1.0
+                        }
+                    ),
+                    (
+                        2,
+                        Progress {
+                            done: 10,
+                            total: 8,
+                            fraction: 1.0
+                        }
+                    ),
+                ]
+            );
+
+            #[cfg(feature = "indicatif")]
+            {
+                let bar = indicatif::ProgressBar::hidden();
+                let pages: Vec<_> = numbers
+                    .pages(GetNumbersQuery::default())
+                    .with_pages_progress_bar(bar.clone())
+                    .try_collect()
+                    .await
+                    .unwrap();
+
+                assert_eq!(pages.len(), 3);
+                assert_eq!(bar.position(), 3);
+
+                let bar = indicatif::ProgressBar::hidden();
+                let items: Vec<_> = numbers
+                    .pages(GetNumbersQuery::default())
+                    .with_items_progress_bar(bar.clone())
+                    .items()
+                    .try_collect()
+                    .await
+                    .unwrap();
+
+                assert_eq!(items.len(), 10);
+                assert_eq!(bar.position(), 10);
+            }
+        }
+    };
+}
+
 macro_rules! page_turner_impls {
     ($($modifier:tt)*) => {
         numbers_client_page_turner_impl!($($modifier)*);
@@ -463,15 +2192,52 @@ macro_rules! pages_ahead_unordered_base_test {
     }};
 }
 
+pub(crate) use arc_pages_test;
+pub(crate) use blogs_client_indexed_unordered_test;
+pub(crate) use blogs_client_overquery_stats_test;
 pub(crate) use blogs_client_page_turner_impl;
 pub(crate) use blogs_client_pages_ahead_base_test;
 pub(crate) use blogs_client_pages_ahead_unordered_base_test;
 pub(crate) use blogs_client_pages_base_test;
+pub(crate) use blogs_client_pages_builder_test;
+pub(crate) use blogs_client_pagination_equivalent_test;
+pub(crate) use blogs_client_request_injector_test;
+pub(crate) use boxed_pages_test;
+pub(crate) use into_pages_ahead_arc_test;
 pub(crate) use numbers_client_page_turner_impl;
 pub(crate) use numbers_client_pages_base_test;
+pub(crate) use page_turner_combinators_test;
 pub(crate) use page_turner_impls;
 pub(crate) use pages_ahead_base_test;
+pub(crate) use pages_ahead_batched_test;
+pub(crate) use pages_ahead_throttled_test;
 pub(crate) use pages_ahead_unordered_base_test;
 pub(crate) use pages_base_test;
+pub(crate) use pages_bidirectional_test;
+pub(crate) use pages_boxed_err_test;
+pub(crate) use pages_builder_retry_timeout_test;
+pub(crate) use pages_chain_test;
+pub(crate) use pages_decode_test;
+pub(crate) use pages_from_requests_test;
+pub(crate) use pages_infallible_test;
+pub(crate) use pages_inspect_test;
+pub(crate) use pages_items_streamed_test;
+pub(crate) use pages_lazy_test;
+pub(crate) use pages_limit_items_test;
+pub(crate) use pages_map_ahead_test;
+pub(crate) use pages_only_new_items_test;
+pub(crate) use pages_process_concurrently_test;
+pub(crate) use pages_requests_test;
+pub(crate) use pages_resumable_test;
+pub(crate) use pages_split_test;
+pub(crate) use pages_tail_durable_test;
+pub(crate) use pages_try_collect_items_test;
+pub(crate) use pages_while_test;
+pub(crate) use pages_with_progress_handle_test;
+pub(crate) use pages_with_progress_test;
+pub(crate) use pages_with_retry_test;
+pub(crate) use pages_with_state_test;
+pub(crate) use pages_with_timeout_test;
+pub(crate) use turner_fn_test;
 
 use super::RequestAhead;
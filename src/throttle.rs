@@ -0,0 +1,188 @@
+//! Request rate limits for APIs with quotas like "10 requests per second".
+//!
+//! [`Throttled`] delays requests of the inner page turner so that at most [`Rate`] requests start
+//! within any sliding window of the rate's duration. Unlike `requests_ahead_count` which only
+//! limits requests in flight, the rate also holds when an API responds faster than the quota
+//! allows. The window is shared by all clones of the page turner and by all streams using them.
+//!
+//! ```
+//! use page_turner::position::PageNumber;
+//! use page_turner::prelude::*;
+//! use page_turner::throttle::{Rate, Throttled};
+//! use std::time::{Duration, Instant};
+//!
+//! struct Api;
+//!
+//! impl PageTurner<PageNumber> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: PageNumber) -> TurnedPageResult<Self, PageNumber> {
+//!         let next_page = (page.get() < 6).then(|| page.next_request());
+//!         Ok(TurnedPage::new(vec![page.get()], next_page))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let start = Instant::now();
+//! let api = Throttled::new(Api, Rate::new(2, Duration::from_millis(20)));
+//!
+//! let items: Vec<_> = api
+//!     .pages_ahead(4, Limit::None, PageNumber::FIRST)
+//!     .items()
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//!
+//! assert_eq!(items, [1, 2, 3, 4, 5, 6]);
+//! assert!(start.elapsed() >= Duration::from_millis(40));
+//! # }
+//! ```
+
+use crate::internal::lock_unpoisoned;
+use std::{
+    collections::VecDeque,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The maximum amount of requests that can start within a sliding window of time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rate {
+    requests: usize,
+    per: Duration,
+}
+
+impl Rate {
+    /// Allows `requests` per `per` duration. `0` requests is treated as `1`.
+    pub fn new(requests: usize, per: Duration) -> Self {
+        Self {
+            requests: requests.max(1),
+            per,
+        }
+    }
+
+    pub fn per_second(requests: usize) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    pub fn requests(&self) -> usize {
+        self.requests
+    }
+
+    pub fn per(&self) -> Duration {
+        self.per
+    }
+}
+
+/// A page turner that limits the rate of requests. See the [module docs](self).
+#[derive(Debug)]
+pub struct Throttled<P> {
+    page_turner: P,
+    rate: Rate,
+    // Start times of requests within the current window in ascending order
+    window: Arc<Mutex<VecDeque<Instant>>>,
+}
+
+impl<P> Throttled<P> {
+    pub fn new(page_turner: P, rate: Rate) -> Self {
+        Self {
+            page_turner,
+            rate,
+            window: Arc::new(Mutex::new(VecDeque::with_capacity(rate.requests))),
+        }
+    }
+
+    pub fn rate(&self) -> Rate {
+        self.rate
+    }
+
+    pub fn into_inner(self) -> P {
+        self.page_turner
+    }
+
+    async fn turn_page_with<R, Fut>(
+        &self,
+        request: R,
+        turn_page: impl FnOnce(R) -> Fut,
+    ) -> Fut::Output
+    where
+        Fut: Future,
+    {
+        self.acquire().await;
+        turn_page(request).await
+    }
+
+    /// Waits until a request can start without exceeding the rate and reserves its slot
+    async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut window = self.lock();
+                let now = Instant::now();
+
+                while window
+                    .front()
+                    .is_some_and(|started| now.duration_since(*started) >= self.rate.per)
+                {
+                    window.pop_front();
+                }
+
+                if window.len() < self.rate.requests {
+                    window.push_back(now);
+                    return;
+                }
+
+                window[0] + self.rate.per - now
+            };
+
+            futures_timer::Delay::new(wait).await;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<Instant>> {
+        lock_unpoisoned(&self.window)
+    }
+}
+
+impl<P: Clone> Clone for Throttled<P> {
+    fn clone(&self) -> Self {
+        Self {
+            page_turner: self.page_turner.clone(),
+            rate: self.rate,
+            window: Arc::clone(&self.window),
+        }
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, R> crate::mt::PageTurner<R> for Throttled<P>
+where
+    P: crate::mt::PageTurner<R>,
+    R: Send,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |request| self.page_turner.turn_page(request))
+            .await
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, R> crate::local::PageTurner<R> for Throttled<P>
+where
+    P: crate::local::PageTurner<R>,
+{
+    type PageItems = P::PageItems;
+    type PageError = P::PageError;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        self.turn_page_with(request, |request| self.page_turner.turn_page(request))
+            .await
+    }
+}
@@ -0,0 +1,220 @@
+//! Lightweight throughput monitoring of pages streams. See `PagesStream::with_throughput`.
+
+use crate::internal::{async_iterator_impl, lock_unpoisoned};
+use futures::Stream;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+const DEFAULT_WINDOW: Duration = Duration::from_secs(10);
+
+/// A handle to throughput statistics of a stream wrapped with `PagesStream::with_throughput`.
+/// Rates are computed over a rolling window of recent pages. Cloning is cheap and all clones
+/// observe the same stream.
+#[derive(Debug, Clone)]
+pub struct ThroughputStats(Arc<Mutex<ThroughputState>>);
+
+#[derive(Debug)]
+struct ThroughputState {
+    window: Duration,
+    started: Option<Instant>,
+    recent: VecDeque<(Instant, usize)>,
+    pages: usize,
+    items: usize,
+    total_latency: Duration,
+}
+
+impl ThroughputStats {
+    /// Creates stats with a rolling window of 10 seconds
+    pub fn new() -> Self {
+        Self::with_window(DEFAULT_WINDOW)
+    }
+
+    pub fn with_window(window: Duration) -> Self {
+        Self(Arc::new(Mutex::new(ThroughputState {
+            window,
+            started: None,
+            recent: VecDeque::new(),
+            pages: 0,
+            items: 0,
+            total_latency: Duration::ZERO,
+        })))
+    }
+
+    /// Pages per second yielded during the window
+    pub fn pages_per_sec(&self) -> f64 {
+        self.rate(|recent| recent.len())
+    }
+
+    /// Items per second yielded during the window
+    pub fn items_per_sec(&self) -> f64 {
+        self.rate(|recent| recent.iter().map(|(_, items)| items).sum())
+    }
+
+    /// The average time the stream took to produce a page once it was polled for it. Returns
+    /// `None` until the first page is yielded.
+    pub fn average_latency(&self) -> Option<Duration> {
+        let state = self.lock();
+        let pages = u32::try_from(state.pages).ok().filter(|pages| *pages > 0)?;
+        Some(state.total_latency / pages)
+    }
+
+    /// The total amount of pages yielded
+    pub fn pages(&self) -> usize {
+        self.lock().pages
+    }
+
+    /// The total amount of items yielded
+    pub fn items(&self) -> usize {
+        self.lock().items
+    }
+
+    fn rate(&self, count: impl FnOnce(&VecDeque<(Instant, usize)>) -> usize) -> f64 {
+        let mut state = self.lock();
+        let now = Instant::now();
+        state.evict(now);
+
+        let Some(started) = state.started else {
+            return 0.0;
+        };
+
+        let elapsed = now.duration_since(started).min(state.window);
+        if elapsed.is_zero() {
+            return 0.0;
+        }
+
+        count(&state.recent) as f64 / elapsed.as_secs_f64()
+    }
+
+    fn start(&self) {
+        self.lock().started.get_or_insert_with(Instant::now);
+    }
+
+    fn record(&self, items: usize, latency: Duration) {
+        let mut state = self.lock();
+        let now = Instant::now();
+
+        state.pages += 1;
+        state.items += items;
+        state.total_latency += latency;
+        state.recent.push_back((now, items));
+        state.evict(now);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ThroughputState> {
+        lock_unpoisoned(&self.0)
+    }
+}
+
+impl Default for ThroughputStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ThroughputState {
+    fn evict(&mut self, now: Instant) {
+        while let Some((yielded_at, _)) = self.recent.front() {
+            if now.duration_since(*yielded_at) <= self.window {
+                break;
+            }
+
+            self.recent.pop_front();
+        }
+    }
+}
+
+/// A stream returned by `PagesStream::with_throughput`
+pub struct Throughput<S> {
+    stream: Pin<Box<S>>,
+    stats: ThroughputStats,
+    polled_at: Option<Instant>,
+}
+
+impl<S> Throughput<S> {
+    #[cfg(any(feature = "mt", feature = "local"))]
+    pub(crate) fn new(stream: S, stats: ThroughputStats) -> Self {
+        Self {
+            stream: Box::pin(stream),
+            stats,
+            polled_at: None,
+        }
+    }
+}
+
+// The inner stream is boxed
+impl<S> Unpin for Throughput<S> {}
+
+impl<S, T, E> Stream for Throughput<S>
+where
+    S: Stream<Item = Result<T, E>>,
+    for<'t> &'t T: IntoIterator,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        let polled_at = *this.polled_at.get_or_insert_with(|| {
+            this.stats.start();
+            Instant::now()
+        });
+
+        let page = std::task::ready!(this.stream.as_mut().poll_next(cx));
+        this.polled_at = None;
+
+        if let Some(Ok(items)) = &page {
+            this.stats
+                .record(items.into_iter().count(), polled_at.elapsed());
+        }
+
+        Poll::Ready(page)
+    }
+}
+
+async_iterator_impl!([S] Throughput<S>);
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::adapters::slice::{SlicePage, SlicePageTurner};
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+
+    #[tokio::test]
+    async fn records_throughput() {
+        let numbers =
+            SlicePageTurner::new((0..10).collect::<Vec<u8>>(), 3).delay(Duration::from_millis(20));
+
+        let stats = ThroughputStats::new();
+        assert_eq!(stats.average_latency(), None);
+        assert_eq!(stats.pages_per_sec(), 0.0);
+
+        let pages: Vec<_> = numbers
+            .pages(SlicePage(0))
+            .with_throughput(stats.clone())
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(pages.len(), 4);
+        assert_eq!(stats.pages(), 4);
+        assert_eq!(stats.items(), 10);
+        assert!(stats.average_latency().unwrap() >= Duration::from_millis(20));
+
+        let pages_per_sec = stats.pages_per_sec();
+        assert!(pages_per_sec > 0.0 && pages_per_sec <= 4.0 / 0.08);
+        assert!(stats.items_per_sec() > pages_per_sec);
+
+        let stats = ThroughputStats::with_window(Duration::ZERO);
+        stats.start();
+        stats.record(5, Duration::from_millis(1));
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(stats.items_per_sec(), 0.0);
+        assert_eq!(stats.items(), 5);
+    }
+}
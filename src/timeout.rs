@@ -0,0 +1,141 @@
+//! Timeouts of page requests.
+//!
+//! Wrap a page turner into [`Timeout`] to fail every `turn_page` call that doesn't complete in
+//! time with [`TimeoutError::Timeout`] instead of stalling the stream on a hanging upstream.
+//! Combine it with [`crate::retry::Retry`] to send timed out requests again.
+//!
+//! ```
+//! use page_turner::prelude::*;
+//! use page_turner::timeout::TimeoutError;
+//! use std::time::Duration;
+//!
+//! struct Api;
+//!
+//! impl PageTurner<usize> for Api {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         if page == 2 {
+//!             std::future::pending::<()>().await;
+//!         }
+//!
+//!         Ok(TurnedPage::next(vec![page], page + 1))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::StreamExt;
+//!
+//! let results: Vec<_> = Api.pages_with_timeout(Duration::from_millis(10), 0).collect().await;
+//! assert_eq!(results[..2], [Ok(vec![0]), Ok(vec![1])]);
+//! assert_eq!(results[2], Err(TimeoutError::Timeout(Duration::from_millis(10))));
+//! # }
+//! ```
+
+use futures::future::{self, Either};
+use std::{future::Future, time::Duration};
+
+/// A page turner that fails requests of the inner page turner which take longer than the timeout.
+/// See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Timeout<P> {
+    page_turner: P,
+    timeout: Duration,
+}
+
+impl<P> Timeout<P> {
+    pub fn new(page_turner: P, timeout: Duration) -> Self {
+        Self {
+            page_turner,
+            timeout,
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn into_inner(self) -> P {
+        self.page_turner
+    }
+
+    pub(crate) fn get_ref(&self) -> &P {
+        &self.page_turner
+    }
+}
+
+/// Completes `fut` or returns `None` once the `timeout` passes
+pub(crate) async fn timeout<F: Future>(timeout: Duration, fut: F) -> Option<F::Output> {
+    let fut = std::pin::pin!(fut);
+    match future::select(fut, futures_timer::Delay::new(timeout)).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
+
+#[cfg(feature = "mt")]
+impl<P, R> crate::mt::PageTurner<R> for Timeout<P>
+where
+    P: crate::mt::PageTurner<R>,
+    R: Send,
+{
+    type PageItems = P::PageItems;
+    type PageError = TimeoutError<P::PageError>;
+
+    async fn turn_page(&self, request: R) -> crate::mt::TurnedPageResult<Self, R> {
+        timeout(self.timeout, self.page_turner.turn_page(request))
+            .await
+            .ok_or(TimeoutError::Timeout(self.timeout))?
+            .map_err(TimeoutError::Page)
+    }
+}
+
+#[cfg(feature = "local")]
+impl<P, R> crate::local::PageTurner<R> for Timeout<P>
+where
+    P: crate::local::PageTurner<R>,
+{
+    type PageItems = P::PageItems;
+    type PageError = TimeoutError<P::PageError>;
+
+    async fn turn_page(&self, request: R) -> crate::local::TurnedPageResult<Self, R> {
+        timeout(self.timeout, self.page_turner.turn_page(request))
+            .await
+            .ok_or(TimeoutError::Timeout(self.timeout))?
+            .map_err(TimeoutError::Page)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeoutError<E> {
+    /// An error of the inner page turner
+    Page(E),
+    /// The request didn't complete within the timeout
+    Timeout(Duration),
+}
+
+impl<E> std::fmt::Display for TimeoutError<E>
+where
+    E: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Page(e) => e.fmt(f),
+            Self::Timeout(timeout) => write!(f, "the request has timed out after {timeout:?}"),
+        }
+    }
+}
+
+impl<E> std::error::Error for TimeoutError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Page(e) => Some(e),
+            Self::Timeout(_) => None,
+        }
+    }
+}
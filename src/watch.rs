@@ -0,0 +1,227 @@
+//! Helpers for jobs that poll the same feed over and over. See `PagesStream::only_new_items` and
+//! `PageTurner::pages_tail_durable`.
+
+use crate::internal::lock_unpoisoned;
+use futures::future;
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+/// The greatest item key emitted by `PagesStream::only_new_items`. Keep it between polls of a feed
+/// so that re-queried pages emit only items that weren't seen before. Cloning is cheap and all
+/// clones observe the same mark.
+#[derive(Debug)]
+pub struct HighWaterMark<K>(Arc<Mutex<Option<K>>>);
+
+impl<K> HighWaterMark<K> {
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(None)))
+    }
+
+    /// Restores a mark persisted by a previous run
+    pub fn starting_at(key: K) -> Self {
+        Self(Arc::new(Mutex::new(Some(key))))
+    }
+
+    /// Returns the greatest key seen so far or `None` if nothing was seen yet
+    pub fn get(&self) -> Option<K>
+    where
+        K: Clone,
+    {
+        self.lock().clone()
+    }
+
+    /// Raises the mark to `key` if it's greater than the current one. Returns whether the mark was
+    /// raised.
+    pub fn advance(&self, key: K) -> bool
+    where
+        K: Ord,
+    {
+        let mut mark = self.lock();
+        let is_new = mark.as_ref().map_or(true, |mark| key > *mark);
+        if is_new {
+            *mark = Some(key);
+        }
+
+        is_new
+    }
+
+    /// Returns a predicate that tells whether the key of the next item of a feed with the `order`
+    /// is new and raises the mark
+    pub(crate) fn new_keys(&self, order: KeyOrder) -> impl FnMut(K) -> bool
+    where
+        K: Ord + Clone,
+    {
+        let mark = self.clone();
+        let mut since = None;
+        let mut last = None;
+
+        move |key| match order {
+            KeyOrder::Ascending => mark.advance(key),
+            KeyOrder::Descending => {
+                let since = since.get_or_insert_with(|| mark.get());
+                let is_new = since.as_ref().map_or(true, |since| key > *since)
+                    && last.as_ref().map_or(true, |last| key < *last);
+
+                if is_new {
+                    last = Some(key.clone());
+                }
+
+                mark.advance(key);
+                is_new
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<K>> {
+        lock_unpoisoned(&self.0)
+    }
+}
+
+impl<K> Clone for HighWaterMark<K> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<K> Default for HighWaterMark<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The order of item keys in a feed filtered with `PagesStream::only_new_items`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyOrder {
+    /// From the oldest to the newest item. Every item is compared with the mark raised by the
+    /// items before it.
+    Ascending,
+    /// From the newest to the oldest item, like most feeds list them. Every item is compared with
+    /// the mark taken when the stream yields its first item, while the mark is raised to the
+    /// greatest key of the stream.
+    Descending,
+}
+
+/// A persistent storage of a pagination cursor used by `PageTurner::pages_tail_durable` to resume
+/// tailing after a restart. Loading and saving are awaited by the stream, so a cursor kept in a
+/// file, Redis or a database row doesn't block the executor.
+pub trait CursorStore<R> {
+    type Error;
+
+    /// Returns the saved cursor or `None` if nothing was saved yet
+    fn load(&self) -> impl Send + Future<Output = Result<Option<R>, Self::Error>>;
+
+    fn save(&mut self, cursor: &R) -> impl Send + Future<Output = Result<(), Self::Error>>;
+}
+
+/// A [`CursorStore`] for the `local` flavor whose futures don't have to be `Send`, i.e. to keep
+/// the cursor behind an `Rc`. Every [`CursorStore`] is also a `LocalCursorStore`.
+pub trait LocalCursorStore<R> {
+    type Error;
+
+    /// Returns the saved cursor or `None` if nothing was saved yet
+    fn load(&self) -> impl Future<Output = Result<Option<R>, Self::Error>>;
+
+    fn save(&mut self, cursor: &R) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+impl<R, S> LocalCursorStore<R> for S
+where
+    S: CursorStore<R>,
+{
+    type Error = S::Error;
+
+    fn load(&self) -> impl Future<Output = Result<Option<R>, Self::Error>> {
+        CursorStore::load(self)
+    }
+
+    fn save(&mut self, cursor: &R) -> impl Future<Output = Result<(), Self::Error>> {
+        CursorStore::save(self, cursor)
+    }
+}
+
+/// A [`CursorStore`] that keeps the cursor in memory. Cloning is cheap and all clones share the
+/// same cursor, which makes it handy in tests and for restarting tailing within the same process.
+#[derive(Debug)]
+pub struct MemoryCursorStore<R>(Arc<Mutex<Option<R>>>);
+
+impl<R> MemoryCursorStore<R> {
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(None)))
+    }
+
+    /// Returns the saved cursor
+    pub fn get(&self) -> Option<R>
+    where
+        R: Clone,
+    {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<R>> {
+        lock_unpoisoned(&self.0)
+    }
+}
+
+impl<R> Clone for MemoryCursorStore<R> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<R> Default for MemoryCursorStore<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R> CursorStore<R> for MemoryCursorStore<R>
+where
+    R: Send + Clone,
+{
+    type Error = std::convert::Infallible;
+
+    fn load(&self) -> impl Send + Future<Output = Result<Option<R>, Self::Error>> {
+        future::ready(Ok(self.get()))
+    }
+
+    fn save(&mut self, cursor: &R) -> impl Send + Future<Output = Result<(), Self::Error>> {
+        *self.lock() = Some(cursor.clone());
+        future::ready(Ok(()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TailError<E, S> {
+    /// A page error
+    Page(E),
+    /// A [`CursorStore`] error
+    Store(S),
+}
+
+impl<E, S> std::fmt::Display for TailError<E, S>
+where
+    E: std::fmt::Display,
+    S: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Page(e) => e.fmt(f),
+            Self::Store(e) => write!(f, "failed to access the cursor store: {e}"),
+        }
+    }
+}
+
+impl<E, S> std::error::Error for TailError<E, S>
+where
+    E: std::error::Error + 'static,
+    S: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Page(e) => Some(e),
+            Self::Store(e) => Some(e),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Detection of stalled streams.
+//!
+//! A misbehaving server may accept a connection and never respond which blocks a pagination job
+//! forever. Wrap a pages stream into [`Watchdog`] to be notified each time the stream doesn't
+//! yield anything for the configured period and optionally end it with
+//! [`WatchdogError::Stalled`].
+//!
+//! ```
+//! use page_turner::prelude::*;
+//! use page_turner::watchdog::{Watchdog, WatchdogError};
+//! use std::time::Duration;
+//!
+//! struct HangingApi;
+//!
+//! impl PageTurner<usize> for HangingApi {
+//!     type PageItems = Vec<usize>;
+//!     type PageError = ();
+//!
+//!     async fn turn_page(&self, page: usize) -> TurnedPageResult<Self, usize> {
+//!         if page > 0 {
+//!             std::future::pending::<()>().await;
+//!         }
+//!
+//!         Ok(TurnedPage::next(vec![page], page + 1))
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use futures::TryStreamExt;
+//!
+//! let result = Watchdog::new(HangingApi.pages(0), Duration::from_millis(10))
+//!     .on_stall(|waited| eprintln!("No pages for {waited:?}"))
+//!     .abort_after(Duration::from_millis(30))
+//!     .try_collect::<Vec<_>>()
+//!     .await;
+//!
+//! assert!(matches!(result, Err(WatchdogError::Stalled { .. })));
+//! # }
+//! ```
+
+use crate::internal::async_iterator_impl;
+use futures::Stream;
+use futures_timer::Delay;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// A stream adapter reporting periods of inactivity of the inner pages stream. See the [module
+/// docs](self).
+pub struct Watchdog<S, F = fn(Duration)> {
+    stream: Pin<Box<S>>,
+    period: Duration,
+    on_stall: F,
+    abort_after: Option<Duration>,
+    timer: Option<Delay>,
+    waited: Duration,
+    aborted: bool,
+}
+
+impl<S> Watchdog<S> {
+    pub fn new(stream: S, period: Duration) -> Self {
+        Self {
+            stream: Box::pin(stream),
+            period,
+            on_stall: |_| {},
+            abort_after: None,
+            timer: None,
+            waited: Duration::ZERO,
+            aborted: false,
+        }
+    }
+}
+
+impl<S, F> Watchdog<S, F> {
+    /// Calls `on_stall` with the total time the stream has been waiting for the next page each
+    /// time another period passes without pages
+    pub fn on_stall<G>(self, on_stall: G) -> Watchdog<S, G>
+    where
+        G: FnMut(Duration),
+    {
+        Watchdog {
+            stream: self.stream,
+            period: self.period,
+            on_stall,
+            abort_after: self.abort_after,
+            timer: self.timer,
+            waited: self.waited,
+            aborted: self.aborted,
+        }
+    }
+
+    /// Ends the stream with [`WatchdogError::Stalled`] once it has been waiting for the next page
+    /// for at least `timeout`. The check happens at the end of each period.
+    pub fn abort_after(mut self, timeout: Duration) -> Self {
+        self.abort_after = Some(timeout);
+        self
+    }
+
+    pub fn into_inner(self) -> Pin<Box<S>> {
+        self.stream
+    }
+}
+
+// The inner stream is boxed and the timer is `Unpin`
+impl<S, F> Unpin for Watchdog<S, F> {}
+
+impl<S, F, T, E> Stream for Watchdog<S, F>
+where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(Duration),
+{
+    type Item = Result<T, WatchdogError<E>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.aborted {
+            return Poll::Ready(None);
+        }
+
+        if let Poll::Ready(page) = this.stream.as_mut().poll_next(cx) {
+            this.timer = None;
+            this.waited = Duration::ZERO;
+            return Poll::Ready(page.map(|page| page.map_err(WatchdogError::Page)));
+        }
+
+        loop {
+            let period = this.period;
+            let timer = this.timer.get_or_insert_with(|| Delay::new(period));
+            std::task::ready!(Pin::new(timer).poll(cx));
+
+            this.timer = None;
+            this.waited += this.period;
+            (this.on_stall)(this.waited);
+
+            if this
+                .abort_after
+                .is_some_and(|timeout| this.waited >= timeout)
+            {
+                this.aborted = true;
+                return Poll::Ready(Some(Err(WatchdogError::Stalled {
+                    waited: this.waited,
+                })));
+            }
+        }
+    }
+}
+
+async_iterator_impl!([S, F] Watchdog<S, F>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchdogError<E> {
+    /// An error of the inner stream
+    Page(E),
+    /// The stream hasn't yielded anything for `waited`
+    Stalled { waited: Duration },
+}
+
+impl<E> std::fmt::Display for WatchdogError<E>
+where
+    E: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Page(e) => e.fmt(f),
+            Self::Stalled { waited } => write!(f, "the stream has stalled for {waited:?}"),
+        }
+    }
+}
+
+impl<E> std::error::Error for WatchdogError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Page(e) => Some(e),
+            Self::Stalled { .. } => None,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mt"))]
+mod tests {
+    use super::*;
+    use crate::adapters::slice::{SlicePage, SlicePageTurner};
+    use crate::mt::prelude::*;
+    use futures::TryStreamExt;
+
+    #[tokio::test]
+    async fn reports_slow_pages() {
+        let numbers =
+            SlicePageTurner::new((0..4).collect::<Vec<u8>>(), 2).delay(Duration::from_millis(35));
+
+        let mut stalls = Vec::new();
+        let pages: Vec<_> = Watchdog::new(numbers.pages(SlicePage(0)), Duration::from_millis(10))
+            .on_stall(|waited| stalls.push(waited))
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(pages.len(), 2);
+        assert!(stalls.len() >= 4);
+        assert!(stalls
+            .iter()
+            .all(|waited| *waited <= Duration::from_millis(40)));
+
+        let result = Watchdog::new(numbers.pages(SlicePage(0)), Duration::from_millis(10))
+            .abort_after(Duration::from_millis(20))
+            .try_collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            result,
+            Err(WatchdogError::Stalled {
+                waited: Duration::from_millis(20)
+            })
+        );
+    }
+}